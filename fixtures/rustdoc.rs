@@ -0,0 +1,94 @@
+//! A small crate documenting one of each kind of item.
+
+pub mod shapes {
+    //! Shapes with an area.
+
+    /// Anything with an area.
+    pub trait Area {
+        /// The unit the area is measured in.
+        type Unit;
+        /// Sides of a regular polygon; zero for curves.
+        const SIDES: u32 = 0;
+        /// The area of the shape.
+        fn area(&self) -> f64;
+    }
+
+    /// A circle.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Circle {
+        /// Distance from the centre to the edge.
+        pub radius: f64,
+    }
+
+    impl Circle {
+        /// A circle of the given radius.
+        pub const fn new(radius: f64) -> Self {
+            Circle { radius }
+        }
+
+        /// Grow the circle in place.
+        pub fn scale(&mut self, by: f64) {
+            self.radius *= by;
+        }
+    }
+
+    impl Area for Circle {
+        type Unit = f64;
+        fn area(&self) -> f64 {
+            std::f64::consts::PI * self.radius * self.radius
+        }
+    }
+}
+
+/// A value or an error message.
+pub enum Outcome<T> {
+    /// It worked.
+    Done(T),
+    /// It failed.
+    Failed { reason: String },
+    /// Nothing happened.
+    Skipped,
+}
+
+/// How loud.
+pub enum Level {
+    Low = 1,
+    High = 10,
+}
+
+/// Wraps a value with a label.
+pub struct Labelled<'a, T: Clone + 'a>(pub &'a str, pub T);
+
+/// Apply `f` to every item of `items` that passes `keep`.
+pub fn apply_all<'a, T, F>(items: &'a mut [T], keep: impl Fn(&T) -> bool, f: F) -> usize
+where
+    F: FnMut(&mut T),
+{
+    let _ = (items, keep, f);
+    0
+}
+
+/// Not recommended any more.
+#[deprecated(since = "0.1.0", note = "use apply_all")]
+pub async unsafe fn old(values: Vec<Option<u8>>) -> Result<(), Box<dyn std::error::Error + Send>> {
+    let _ = values;
+    Ok(())
+}
+
+/// The largest supported size.
+pub const MAX: usize = 64;
+
+/// A shorthand for results.
+pub type Res<T> = Result<T, String>;
+
+pub use shapes::Circle as Round;
+
+/// Make a list.
+#[macro_export]
+macro_rules! list {
+    ($($x:expr),*) => { vec![$($x),*] };
+}
+
+mod hidden {
+    pub fn not_exported() {}
+}
@@ -1,9 +1,101 @@
+use crate::config;
+use crate::policy::{Level, Policy};
 use chrono::DateTime;
 use reqwest::blocking::Client;
+use reqwest::{Proxy, StatusCode, Url};
 use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{LazyLock, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
-const CRATES_API: &str = "https://crates.io/api/v1";
-const GITHUB_API: &str = "https://api.github.com";
+static CACHE: LazyLock<Mutex<HashMap<String, (Instant, String)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// Base URLs from the config, without trailing slashes
+fn crates_io() -> &'static str {
+    config::get().endpoints.crates_io.trim_end_matches('/')
+}
+
+fn github() -> &'static str {
+    config::get().endpoints.github.trim_end_matches('/')
+}
+
+fn osv() -> &'static str {
+    config::get().endpoints.osv.trim_end_matches('/')
+}
+
+fn docs_rs() -> &'static str {
+    config::get().endpoints.docs_rs.trim_end_matches('/')
+}
+
+/// The crate's page on the crates.io website, next to the API it is served from
+pub fn crate_page_url(name: &str) -> String {
+    let site = crates_io().trim_end_matches("/api/v1");
+    format!("{}/crates/{}", site, name)
+}
+
+/// The crate's own documentation link, or its docs.rs page
+pub fn docs_url(crate_data: &Crate) -> String {
+    crate_data.documentation.clone().unwrap_or_else(|| {
+        format!(
+            "{}/{}/{}",
+            docs_rs(),
+            crate_data.name,
+            crate_data.max_version
+        )
+    })
+}
+
+// Every request goes through a client built from the network settings
+fn client() -> Result<Client, Box<dyn std::error::Error>> {
+    let network = &config::get().network;
+    let mut builder = Client::builder()
+        .user_agent(network.user_agent.as_str())
+        .timeout(Duration::from_secs(network.timeout_secs));
+    if let Some(ref proxy) = network.proxy {
+        builder = builder.proxy(Proxy::all(proxy)?);
+    }
+    Ok(builder.build()?)
+}
+
+/// GET a URL, reusing the body of a successful response fetched less than
+/// `ttl` ago. `what` names the resource in errors.
+fn get_cached(url: &str, ttl: Duration, what: &str) -> Result<String, Box<dyn std::error::Error>> {
+    cached(url, ttl, || {
+        let response = client()?.get(url).send()?;
+        if !response.status().is_success() {
+            return Err(format!("Failed to fetch {}: {}", what, response.status()).into());
+        }
+        Ok(response.text()?)
+    })
+}
+
+// Reuse a body cached under `key` less than `ttl` ago, or fetch and cache it.
+// Requests other than GETs pick a key that identifies them.
+fn cached(
+    key: &str,
+    ttl: Duration,
+    fetch: impl FnOnce() -> Result<String, Box<dyn std::error::Error>>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let now = Instant::now();
+    if let Some((expires, body)) = CACHE.lock().unwrap().get(key) {
+        if *expires > now {
+            return Ok(body.clone());
+        }
+    }
+
+    let body = fetch()?;
+
+    if !ttl.is_zero() {
+        let mut cache = CACHE.lock().unwrap();
+        cache.retain(|_, (expires, _)| *expires > now);
+        cache.insert(key.to_string(), (now + ttl, body.clone()));
+    }
+    Ok(body)
+}
 
 #[derive(Debug, Deserialize, Clone)]
 #[allow(dead_code)]
@@ -11,10 +103,14 @@ pub struct Crate {
     pub name: String,
     pub description: Option<String>,
     pub downloads: u64,
+    /// Downloads in the last 90 days
+    #[serde(default)]
+    pub recent_downloads: Option<u64>,
     pub created_at: String,
     pub updated_at: String,
     pub documentation: Option<String>,
     pub repository: Option<String>,
+    pub homepage: Option<String>,
     pub max_version: String,
     pub license: Option<String>,
     pub keywords: Option<Vec<String>>,
@@ -24,6 +120,101 @@ pub struct Crate {
 #[derive(Debug, Deserialize)]
 struct CratesResponse {
     crates: Vec<Crate>,
+    #[serde(default)]
+    meta: PageMeta,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct PageMeta {
+    #[serde(default)]
+    total: u64,
+}
+
+/// One page of a crate listing along with the size of the whole listing
+#[derive(Debug)]
+pub struct CratesPage {
+    pub crates: Vec<Crate>,
+    pub total: u64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(dead_code)]
+pub struct Version {
+    pub num: String,
+    pub created_at: String,
+    pub downloads: u64,
+    pub yanked: bool,
+    pub license: Option<String>,
+    pub crate_size: Option<u64>,
+    pub published_by: Option<User>,
+    /// The `rust-version` declared in the version's manifest
+    #[serde(default)]
+    pub rust_version: Option<String>,
+    #[serde(default)]
+    pub edition: Option<String>,
+    /// Feature name -> the features and optional dependencies it enables
+    #[serde(default)]
+    pub features: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(dead_code)]
+pub struct User {
+    pub id: u64,
+    pub login: String,
+    pub name: Option<String>,
+    // Only set for crate owners: either "user" or "team"
+    pub kind: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OwnersResponse {
+    users: Vec<User>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+}
+
+impl Severity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Severity::Low => "LOW",
+            Severity::Medium => "MEDIUM",
+            Severity::High => "HIGH",
+        }
+    }
+}
+
+/// A single result from one of the security heuristics
+#[derive(Debug, Clone)]
+pub struct Finding {
+    /// Id of the policy rule that produced this finding
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub level: Level,
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[{}] {} ({})",
+            self.severity.label(),
+            self.message,
+            self.rule
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct VersionsResponse {
+    versions: Vec<Version>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -38,43 +229,245 @@ pub struct Repository {
     pub language: Option<String>,
 }
 
-pub fn search_crates(query: &str, limit: usize) -> Result<Vec<Crate>, Box<dyn std::error::Error>> {
-    let client = Client::new();
+/// Result orderings supported by the crates.io search endpoint
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortOrder {
+    Relevance,
+    Downloads,
+    RecentDownloads,
+    RecentUpdates,
+    NewlyAdded,
+    Alphabetical,
+}
+
+impl SortOrder {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortOrder::Relevance => "Relevance",
+            SortOrder::Downloads => "All-time downloads",
+            SortOrder::RecentDownloads => "Recent downloads",
+            SortOrder::RecentUpdates => "Recently updated",
+            SortOrder::NewlyAdded => "Newly added",
+            SortOrder::Alphabetical => "Alphabetical",
+        }
+    }
+
+    /// Value of the `sort` query parameter
+    fn param(&self) -> &'static str {
+        match self {
+            SortOrder::Relevance => "relevance",
+            SortOrder::Downloads => "downloads",
+            SortOrder::RecentDownloads => "recent-downloads",
+            SortOrder::RecentUpdates => "recent-updates",
+            SortOrder::NewlyAdded => "new",
+            SortOrder::Alphabetical => "alpha",
+        }
+    }
+
+    pub fn next(&self) -> SortOrder {
+        match self {
+            SortOrder::Relevance => SortOrder::Downloads,
+            SortOrder::Downloads => SortOrder::RecentDownloads,
+            SortOrder::RecentDownloads => SortOrder::RecentUpdates,
+            SortOrder::RecentUpdates => SortOrder::NewlyAdded,
+            SortOrder::NewlyAdded => SortOrder::Alphabetical,
+            SortOrder::Alphabetical => SortOrder::Relevance,
+        }
+    }
+}
+
+/// Search parameters handled by crates.io itself
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchParams {
+    pub text: String,
+    pub category: Option<String>,
+    pub keyword: Option<String>,
+}
+
+// Pages are numbered from 1, as crates.io expects
+pub fn search_crates(
+    params: &SearchParams,
+    sort: SortOrder,
+    page: usize,
+    per_page: usize,
+) -> Result<CratesPage, Box<dyn std::error::Error>> {
+    let mut query = vec![
+        ("q", params.text.clone()),
+        ("sort", sort.param().to_string()),
+        ("page", page.to_string()),
+        ("per_page", per_page.to_string()),
+    ];
+    if let Some(ref category) = params.category {
+        query.push(("category", category.clone()));
+    }
+    if let Some(ref keyword) = params.keyword {
+        query.push(("keyword", keyword.clone()));
+    }
+
+    let url = Url::parse_with_params(&format!("{}/crates", crates_io()), &query)?;
+    let body = get_cached(url.as_str(), config::get().cache.listing_ttl(), "crates")?;
+    let response: CratesResponse = serde_json::from_str(&body)?;
+
+    Ok(CratesPage {
+        crates: response.crates,
+        total: response.meta.total,
+    })
+}
+
+// Crate names for autocompletion, names starting with the prefix first
+pub fn suggest_crate_names(
+    prefix: &str,
+    limit: usize,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let params = SearchParams {
+        text: prefix.to_string(),
+        ..SearchParams::default()
+    };
+    let page = search_crates(&params, SortOrder::Relevance, 1, limit)?;
+
+    // crates.io treats `-` and `_` as the same character in names
+    let normalize = |s: &str| s.to_lowercase().replace('_', "-");
+    let prefix = normalize(prefix);
+
+    let mut names: Vec<String> = page.crates.into_iter().map(|c| c.name).collect();
+    names.sort_by_key(|name| !normalize(name).starts_with(&prefix));
+    Ok(names)
+}
+
+pub fn recent_crates(
+    page: usize,
+    per_page: usize,
+) -> Result<CratesPage, Box<dyn std::error::Error>> {
+    let url = format!(
+        "{}/crates?sort=recent-updates&page={}&per_page={}",
+        crates_io(),
+        page,
+        per_page
+    );
+
+    let body = get_cached(&url, config::get().cache.listing_ttl(), "crates")?;
+    let response: CratesResponse = serde_json::from_str(&body)?;
+
+    Ok(CratesPage {
+        crates: response.crates,
+        total: response.meta.total,
+    })
+}
+
+// Top crates by downloads for a category slug, e.g. `web-programming::http-client`
+pub fn category_crates(
+    slug: &str,
+    page: usize,
+    per_page: usize,
+) -> Result<CratesPage, Box<dyn std::error::Error>> {
     let url = format!(
-        "{}/crates?q={}&sort=downloads&per_page={}",
-        CRATES_API, query, limit
+        "{}/crates?category={}&sort=downloads&page={}&per_page={}",
+        crates_io(),
+        slug,
+        page,
+        per_page
     );
 
-    let response = client
-        .get(&url)
-        .header("User-Agent", "crates cli app")
-        .send()?
-        .json::<CratesResponse>()?;
+    let body = get_cached(&url, config::get().cache.listing_ttl(), "crates")?;
+    let response: CratesResponse = serde_json::from_str(&body)?;
 
-    Ok(response.crates)
+    Ok(CratesPage {
+        crates: response.crates,
+        total: response.meta.total,
+    })
 }
 
-pub fn recent_crates(limit: usize) -> Result<Vec<Crate>, Box<dyn std::error::Error>> {
-    let client = Client::new();
+pub fn keyword_crates(
+    keyword: &str,
+    page: usize,
+    per_page: usize,
+) -> Result<CratesPage, Box<dyn std::error::Error>> {
     let url = format!(
-        "{}/crates?sort=recent-updates&per_page={}",
-        CRATES_API, limit
+        "{}/crates?keyword={}&sort=downloads&page={}&per_page={}",
+        crates_io(),
+        keyword,
+        page,
+        per_page
     );
 
-    let response = client
-        .get(&url)
-        .header("User-Agent", "crates cli app")
-        .send()?
-        .json::<CratesResponse>()?;
+    let body = get_cached(&url, config::get().cache.listing_ttl(), "crates")?;
+    let response: CratesResponse = serde_json::from_str(&body)?;
+
+    Ok(CratesPage {
+        crates: response.crates,
+        total: response.meta.total,
+    })
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Category {
+    /// Display name; subcategories include their parents, e.g. `Web programming::HTTP client`
+    pub category: String,
+    pub slug: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub crates_cnt: u64,
+    /// Only present when a single category is requested
+    #[serde(default)]
+    pub subcategories: Vec<Category>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CategoriesResponse {
+    categories: Vec<Category>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CategoryResponse {
+    category: Category,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Keyword {
+    pub keyword: String,
+    pub crates_cnt: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct KeywordsResponse {
+    keywords: Vec<Keyword>,
+}
+
+// crates.io only lists top-level categories here; children come from `subcategories`
+pub fn categories() -> Result<Vec<Category>, Box<dyn std::error::Error>> {
+    let url = format!("{}/categories?sort=alpha&per_page=100", crates_io());
+
+    let body = get_cached(&url, config::get().cache.listing_ttl(), "categories")?;
+    let response: CategoriesResponse = serde_json::from_str(&body)?;
+
+    Ok(response.categories)
+}
+
+pub fn subcategories(slug: &str) -> Result<Vec<Category>, Box<dyn std::error::Error>> {
+    let url = format!("{}/categories/{}", crates_io(), slug);
+
+    let body = get_cached(&url, config::get().cache.listing_ttl(), "category")?;
+    let response: CategoryResponse = serde_json::from_str(&body)?;
 
-    Ok(response.crates)
+    Ok(response.category.subcategories)
+}
+
+// Keywords used by the most crates
+pub fn popular_keywords(limit: usize) -> Result<Vec<Keyword>, Box<dyn std::error::Error>> {
+    let url = format!("{}/keywords?sort=crates&per_page={}", crates_io(), limit);
+
+    let body = get_cached(&url, config::get().cache.listing_ttl(), "keywords")?;
+    let response: KeywordsResponse = serde_json::from_str(&body)?;
+
+    Ok(response.keywords)
 }
 
 pub fn trending_repos(
     period: &str,
     limit: usize,
 ) -> Result<Vec<Repository>, Box<dyn std::error::Error>> {
-    let client = Client::new();
+    let client = client()?;
 
     // GitHub API doesn't directly provide "trending" repositories,
     // so we need to search for popular Rust repos created in the recent period
@@ -87,14 +480,12 @@ pub fn trending_repos(
 
     let url = format!(
         "{}/search/repositories?q=language:rust+created:>{}&sort=stars&order=desc&per_page={}",
-        GITHUB_API, since, limit
+        github(),
+        since,
+        limit
     );
 
-    let response = client
-        .get(&url)
-        .header("User-Agent", "crates cli app")
-        .send()?
-        .json::<serde_json::Value>()?;
+    let response = client.get(&url).send()?.json::<serde_json::Value>()?;
 
     let items = response["items"].as_array().unwrap();
     let mut repos = Vec::new();
@@ -108,19 +499,10 @@ pub fn trending_repos(
 }
 
 pub fn get_crate_details(name: &str) -> Result<Crate, Box<dyn std::error::Error>> {
-    let client = Client::new();
-    let url = format!("{}/crates/{}", CRATES_API, name);
-
-    let response = client
-        .get(&url)
-        .header("User-Agent", "crates cli app")
-        .send()?;
-
-    if !response.status().is_success() {
-        return Err(format!("Failed to fetch crate details: {}", response.status()).into());
-    }
+    let url = format!("{}/crates/{}", crates_io(), name);
 
-    let json: serde_json::Value = response.json()?;
+    let body = get_cached(&url, config::get().cache.crate_ttl(), "crate details")?;
+    let json: serde_json::Value = serde_json::from_str(&body)?;
     let crate_data = &json["crate"];
 
     // Parse the crate data from the response
@@ -129,74 +511,409 @@ pub fn get_crate_details(name: &str) -> Result<Crate, Box<dyn std::error::Error>
     Ok(crate_info)
 }
 
-// Security check for crates - simple heuristic approach
-pub fn security_check(crate_data: &Crate) -> Vec<String> {
-    let mut warnings = Vec::new();
+// Versions are returned newest first
+pub fn crate_versions(name: &str) -> Result<Vec<Version>, Box<dyn std::error::Error>> {
+    let url = format!("{}/crates/{}/versions", crates_io(), name);
 
-    // 1. License check - more sophisticated
-    // Add debug logging to see what we're actually receiving
-    if let Some(license) = &crate_data.license {
-        if license.trim().is_empty() {
-            warnings.push("Empty license specified".to_string());
-        } else {
-            // License exists and is not empty - check for common types
-            let license_lower = license.to_lowercase();
-
-            // Uncommon or proprietary license warning
-            let common_licenses = [
-                "mit",
-                "apache",
-                "gpl",
-                "lgpl",
-                "bsd",
-                "mpl",
-                "unlicense",
-                "isc",
-                "zlib",
-                "wtfpl",
-                "cc0",
-                "boost",
-                "artistic",
-                "mozilla",
-                "zlib/libpng",
-            ];
-
-            let mut is_common = false;
-            for common in common_licenses.iter() {
-                if license_lower.contains(common) {
-                    is_common = true;
-                    break;
-                }
+    let body = get_cached(&url, config::get().cache.crate_ttl(), "versions")?;
+    Ok(serde_json::from_str::<VersionsResponse>(&body)?.versions)
+}
+
+pub fn crate_owners(name: &str) -> Result<Vec<User>, Box<dyn std::error::Error>> {
+    let url = format!("{}/crates/{}/owners", crates_io(), name);
+
+    let body = get_cached(&url, config::get().cache.crate_ttl(), "owners")?;
+    Ok(serde_json::from_str::<OwnersResponse>(&body)?.users)
+}
+
+pub fn reverse_dependency_count(name: &str) -> Result<u64, Box<dyn std::error::Error>> {
+    let url = format!(
+        "{}/crates/{}/reverse_dependencies?per_page=1",
+        crates_io(),
+        name
+    );
+
+    let body = get_cached(
+        &url,
+        config::get().cache.crate_ttl(),
+        "reverse dependencies",
+    )?;
+    let response: serde_json::Value = serde_json::from_str(&body)?;
+
+    response["meta"]["total"]
+        .as_u64()
+        .ok_or_else(|| "Missing reverse dependency count".into())
+}
+
+#[derive(Debug, Deserialize)]
+struct DailyDownloads {
+    date: String,
+    downloads: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct DownloadsMeta {
+    #[serde(default)]
+    extra_downloads: Vec<DailyDownloads>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DownloadsResponse {
+    version_downloads: Vec<DailyDownloads>,
+    meta: DownloadsMeta,
+}
+
+/// Downloads per day over the last 90 days, oldest first. crates.io splits
+/// them by version, with the less popular versions lumped together.
+pub fn download_history(name: &str) -> Result<Vec<u64>, Box<dyn std::error::Error>> {
+    let url = format!("{}/crates/{}/downloads", crates_io(), name);
+
+    let body = get_cached(&url, config::get().cache.crate_ttl(), "downloads")?;
+    let response: DownloadsResponse = serde_json::from_str(&body)?;
+
+    let mut days: BTreeMap<String, u64> = BTreeMap::new();
+    for day in response
+        .version_downloads
+        .iter()
+        .chain(&response.meta.extra_downloads)
+    {
+        *days.entry(day.date.clone()).or_default() += day.downloads;
+    }
+    Ok(days.into_values().collect())
+}
+
+/// A dependency of a published version
+#[derive(Debug, Deserialize, Clone)]
+#[allow(dead_code)]
+pub struct Dependency {
+    #[serde(rename = "crate_id")]
+    pub name: String,
+    pub req: String,
+    /// normal, build or dev
+    pub kind: String,
+    pub optional: bool,
+}
+
+impl Dependency {
+    /// Whether it is built for users of the crate, unlike dev-dependencies
+    pub fn is_used(&self) -> bool {
+        self.kind != "dev"
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DependenciesResponse {
+    dependencies: Vec<Dependency>,
+}
+
+pub fn dependencies(
+    name: &str,
+    version: &str,
+) -> Result<Vec<Dependency>, Box<dyn std::error::Error>> {
+    let url = format!("{}/crates/{}/{}/dependencies", crates_io(), name, version);
+
+    let body = get_cached(&url, config::get().cache.crate_ttl(), "dependencies")?;
+    Ok(serde_json::from_str::<DependenciesResponse>(&body)?.dependencies)
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[allow(dead_code)]
+pub struct Advisory {
+    pub id: String,
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdvisoriesResponse {
+    #[serde(default)]
+    vulns: Vec<Advisory>,
+}
+
+// Advisories from the OSV database (which mirrors RustSec) affecting a version
+pub fn advisories(name: &str, version: &str) -> Result<Vec<Advisory>, Box<dyn std::error::Error>> {
+    let url = format!("{}/query", osv());
+    let key = format!("{}#{}@{}", url, name, version);
+
+    let body = cached(&key, config::get().cache.crate_ttl(), || {
+        let query = serde_json::json!({
+            "version": version,
+            "package": { "name": name, "ecosystem": "crates.io" },
+        });
+        let response = client()?.post(&url).json(&query).send()?;
+        if !response.status().is_success() {
+            return Err(format!("Failed to fetch advisories: {}", response.status()).into());
+        }
+        Ok(response.text()?)
+    })?;
+
+    Ok(serde_json::from_str::<AdvisoriesResponse>(&body)?.vulns)
+}
+
+/// How a version's documentation fared on docs.rs
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DocsBuild {
+    Built,
+    Failed,
+    /// Queued or running, with no earlier successful build
+    Building,
+    /// docs.rs has not tried yet
+    NotBuilt,
+}
+
+impl DocsBuild {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DocsBuild::Built => "built",
+            DocsBuild::Failed => "failed",
+            DocsBuild::Building => "building",
+            DocsBuild::NotBuilt => "not built",
+        }
+    }
+}
+
+/// The docs.rs build of one version
+#[derive(Debug, Clone)]
+pub struct DocsStatus {
+    pub version: String,
+    pub build: DocsBuild,
+    /// When the latest build ran
+    pub built_at: Option<String>,
+    /// Compiler the latest build used, e.g. `rustc 1.80.0-nightly (...)`
+    pub rustc: Option<String>,
+}
+
+// docs.rs keeps serving the last successful build, so a failed rebuild
+// still counts as built
+pub fn docs_status(name: &str, version: &str) -> Result<DocsStatus, Box<dyn std::error::Error>> {
+    let url = format!("{}/crate/{}/{}/builds.json", docs_rs(), name, version);
+
+    let body = get_cached(&url, config::get().cache.crate_ttl(), "docs.rs builds")?;
+    let mut builds: Vec<serde_json::Value> = serde_json::from_str(&body)?;
+    builds.sort_by_key(|b| std::cmp::Reverse(b["id"].as_u64().unwrap_or(0)));
+
+    // Older responses report the status as a boolean
+    let status = |build: &serde_json::Value| match &build["build_status"] {
+        serde_json::Value::Bool(true) => DocsBuild::Built,
+        serde_json::Value::Bool(false) => DocsBuild::Failed,
+        serde_json::Value::String(s) if s == "success" => DocsBuild::Built,
+        serde_json::Value::String(s) if s == "failure" => DocsBuild::Failed,
+        _ => DocsBuild::Building,
+    };
+    let build = if builds.iter().any(|b| status(b) == DocsBuild::Built) {
+        DocsBuild::Built
+    } else {
+        builds.first().map(status).unwrap_or(DocsBuild::NotBuilt)
+    };
+
+    let latest = builds.first();
+    let field = |key: &str| latest.and_then(|b| b[key].as_str()).map(str::to_string);
+
+    Ok(DocsStatus {
+        version: version.to_string(),
+        build,
+        built_at: field("build_time"),
+        rustc: field("rustc_version"),
+    })
+}
+
+/// A version and its docs.rs status
+pub type DocsStatusResult = (String, Result<DocsStatus, String>);
+
+/// Fetch the docs.rs status of each version in turn on a background thread,
+/// stopping early once the receiver is dropped
+pub fn spawn_docs_statuses(name: String, versions: Vec<String>) -> Receiver<DocsStatusResult> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for version in versions {
+            let status = docs_status(&name, &version).map_err(|e| e.to_string());
+            if sender.send((version, status)).is_err() {
+                break;
             }
+        }
+    });
+    receiver
+}
+
+/// What GitHub knows about a crate's repository
+#[derive(Debug, Clone, Copy)]
+pub struct RepoInfo {
+    pub archived: bool,
+    pub stars: u64,
+    /// Open issues and pull requests, as GitHub counts them
+    pub open_issues: u64,
+}
+
+// Stars, open issues and whether a GitHub repository has been archived;
+// other hosts are not supported
+pub fn repository_info(repository: &str) -> Result<RepoInfo, Box<dyn std::error::Error>> {
+    let path = repository
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .split_once("github.com/")
+        .map(|(_, path)| path)
+        .ok_or("Not a GitHub repository")?;
+
+    let mut parts = path.split('/');
+    let (owner, repo) = match (parts.next(), parts.next()) {
+        (Some(owner), Some(repo)) => (owner, repo),
+        _ => return Err("Not a GitHub repository".into()),
+    };
+
+    let url = format!("{}/repos/{}/{}", github(), owner, repo);
+    let body = get_cached(&url, config::get().cache.crate_ttl(), "repository")?;
+
+    let json: serde_json::Value = serde_json::from_str(&body)?;
+    Ok(RepoInfo {
+        archived: json["archived"].as_bool().unwrap_or(false),
+        stars: json["stargazers_count"].as_u64().unwrap_or(0),
+        open_issues: json["open_issues_count"].as_u64().unwrap_or(0),
+    })
+}
 
-            if !is_common {
-                warnings.push(format!(
-                    "Uncommon license: '{}' - verify before use",
-                    license
+// Download the rustdoc JSON docs.rs built for a version, gzipped. Only
+// builds from 2025 on include it.
+pub fn download_rustdoc_json(
+    name: &str,
+    version: &str,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let client = client()?;
+    let url = format!("{}/crate/{}/{}/json.gz", docs_rs(), name, version);
+
+    let response = client.get(&url).send()?;
+
+    if response.status() == StatusCode::NOT_FOUND {
+        return Err(format!("docs.rs has no rustdoc JSON for {} {}", name, version).into());
+    }
+    if !response.status().is_success() {
+        return Err(format!(
+            "Failed to download the rustdoc JSON of {} {}: {}",
+            name,
+            version,
+            response.status()
+        )
+        .into());
+    }
+
+    Ok(response.bytes()?.to_vec())
+}
+
+// Download the `.crate` archive (a gzipped tarball) for a published version
+pub fn download_crate(name: &str, version: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let client = client()?;
+    let url = format!("{}/crates/{}/{}/download", crates_io(), name, version);
+
+    let response = client.get(&url).send()?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Failed to download {} {}: {}",
+            name,
+            version,
+            response.status()
+        )
+        .into());
+    }
+
+    Ok(response.bytes()?.to_vec())
+}
+
+// Security check for crates - simple heuristic approach, tuned by the policy
+pub fn security_check(crate_data: &Crate, policy: &Policy) -> Vec<Finding> {
+    let mut warnings = Vec::new();
+    let name = crate_data.name.as_str();
+    let rules = &policy.rules;
+
+    // 0. Banned crates are reported regardless of the allowlist
+    if policy.is_banned(name) {
+        warnings.push(policy.finding("banned", Severity::High, "Crate is banned by policy"));
+    }
+
+    // 1. License check - more sophisticated
+    if policy.applies(name, &rules.license.rule) {
+        match &crate_data.license {
+            Some(license) if license.trim().is_empty() => {
+                warnings.push(policy.finding(
+                    "license",
+                    Severity::Medium,
+                    "Empty license specified",
                 ));
             }
+            Some(license) => {
+                // License exists and is not empty - check for common types
+                let license_lower = license.to_lowercase();
+
+                // Uncommon or proprietary license warning
+                let common_licenses = [
+                    "mit",
+                    "apache",
+                    "gpl",
+                    "lgpl",
+                    "bsd",
+                    "mpl",
+                    "unlicense",
+                    "isc",
+                    "zlib",
+                    "wtfpl",
+                    "cc0",
+                    "boost",
+                    "artistic",
+                    "mozilla",
+                    "zlib/libpng",
+                ];
+
+                let is_common = common_licenses
+                    .iter()
+                    .any(|common| license_lower.contains(common))
+                    || rules
+                        .license
+                        .extra_licenses
+                        .iter()
+                        .any(|extra| license_lower.contains(&extra.to_lowercase()));
 
-            // Warning for copyleft licenses that might affect projects
-            if license_lower.contains("gpl") && !license_lower.contains("lgpl") {
-                warnings.push(
-                    "GPL license may require derivative works to be open-sourced".to_string(),
-                );
+                if !is_common {
+                    warnings.push(policy.finding(
+                        "license",
+                        Severity::Low,
+                        format!("Uncommon license: '{}' - verify before use", license),
+                    ));
+                }
+            }
+            None => {
+                warnings.push(policy.finding("license", Severity::Medium, "No license specified"));
             }
         }
-    } else {
-        warnings.push("No license specified".to_string());
+    }
+
+    // Warning for copyleft licenses that might affect projects
+    if policy.applies(name, &rules.copyleft) {
+        let license_lower = crate_data.license.as_deref().unwrap_or("").to_lowercase();
+        if license_lower.contains("gpl") && !license_lower.contains("lgpl") {
+            warnings.push(policy.finding(
+                "copyleft",
+                Severity::Low,
+                "GPL license may require derivative works to be open-sourced",
+            ));
+        }
     }
 
     // 2. Recent crate with high downloads could be suspicious
-    if let Ok(created) = DateTime::parse_from_rfc3339(&crate_data.created_at) {
-        let created_utc = chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(
-            created.naive_utc(),
-            chrono::Utc,
-        );
-        let age = chrono::Utc::now().signed_duration_since(created_utc);
-
-        if age.num_days() < 30 && crate_data.downloads > 10000 {
-            warnings.push("New crate with unusually high download count".to_string());
+    if policy.applies(name, &rules.new_popular.rule) {
+        if let Ok(created) = DateTime::parse_from_rfc3339(&crate_data.created_at) {
+            let created_utc = chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(
+                created.naive_utc(),
+                chrono::Utc,
+            );
+            let age = chrono::Utc::now().signed_duration_since(created_utc);
+
+            if age.num_days() < rules.new_popular.max_age_days
+                && crate_data.downloads > rules.new_popular.min_downloads
+            {
+                warnings.push(policy.finding(
+                    "new-popular",
+                    Severity::Medium,
+                    "New crate with unusually high download count",
+                ));
+            }
         }
     }
 
@@ -221,60 +938,191 @@ pub fn security_check(crate_data: &Crate) -> Vec<String> {
         "anyhow",
         "thiserror",
     ];
+    let max_distance = rules.typosquat.max_distance;
 
-    for target in popular_crates {
-        if crate_data.name != target {
-            // Check for exact prefix/suffix
-            if crate_data.name.starts_with(target) || crate_data.name.ends_with(target) {
-                if crate_data.name.len() > target.len() && crate_data.name.len() <= target.len() + 3
+    if policy.applies(name, &rules.typosquat.rule) {
+        let targets = popular_crates
+            .iter()
+            .copied()
+            .chain(rules.typosquat.popular.iter().map(|p| p.as_str()));
+
+        for target in targets {
+            if name != target {
+                // Check for exact prefix/suffix
+                if (name.starts_with(target) || name.ends_with(target))
+                    && name.len() > target.len()
+                    && name.len() <= target.len() + 3
                 {
-                    warnings.push(format!("Name suspiciously similar to '{}'", target));
+                    warnings.push(policy.finding(
+                        "typosquat",
+                        Severity::High,
+                        format!("Name suspiciously similar to '{}'", target),
+                    ));
                     break;
                 }
-            }
 
-            // Check for Levenshtein distance for non-prefix/suffix cases
-            // Only warn if the crate name is similar in length to avoid false positives
-            let length_diff =
-                (crate_data.name.len() as isize - target.len() as isize).abs() as usize;
+                // Check for Levenshtein distance for non-prefix/suffix cases
+                // Only warn if the crate name is similar in length to avoid false positives
+                let length_diff = name.len().abs_diff(target.len());
 
-            if length_diff <= 2 && levenshtein_distance(&crate_data.name, target) <= 2 {
-                warnings.push(format!("Name similar to popular crate '{}'", target));
-                break;
+                if length_diff <= max_distance && levenshtein_distance(name, target) <= max_distance
+                {
+                    warnings.push(policy.finding(
+                        "typosquat",
+                        Severity::Medium,
+                        format!("Name similar to popular crate '{}'", target),
+                    ));
+                    break;
+                }
             }
         }
     }
 
     // 4. No repository link
-    if crate_data.repository.is_none()
-        || crate_data
+    if policy.applies(name, &rules.no_repository)
+        && crate_data
             .repository
             .as_deref()
             .unwrap_or("")
             .trim()
             .is_empty()
     {
-        warnings.push("No repository link".to_string());
+        warnings.push(policy.finding("no-repository", Severity::Low, "No repository link"));
     }
 
-    // 5. No documentation
-    if crate_data.documentation.is_none()
-        || crate_data
-            .documentation
-            .as_deref()
-            .unwrap_or("")
-            .trim()
-            .is_empty()
+    // 5. Version number check - very low versions might be pre-production
+    if policy.applies(name, &rules.early_version.rule)
+        && crate_data
+            .max_version
+            .starts_with(&rules.early_version.prefix)
     {
-        warnings.push("No documentation link".to_string());
+        warnings.push(policy.finding(
+            "early-version",
+            Severity::Low,
+            "Very early version - may not be stable",
+        ));
     }
 
-    // 6. Version number check - very low versions might be pre-production
-    if crate_data.max_version.starts_with("0.0.") || crate_data.max_version == "0.0.1" {
-        warnings.push("Very early version - may not be stable".to_string());
+    warnings
+}
+
+// Documentation check - whether docs.rs could build the version's docs.
+// Kept apart from `security_check` as the status has to be fetched.
+pub fn docs_check(name: &str, status: Option<&DocsStatus>, policy: &Policy) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    if let Some(status) = status {
+        if status.build == DocsBuild::Failed && policy.applies(name, &policy.rules.docs_failed) {
+            findings.push(policy.finding(
+                "docs-failed",
+                Severity::Low,
+                format!(
+                    "Documentation failed to build on docs.rs for v{}",
+                    status.version
+                ),
+            ));
+        }
     }
+    findings
+}
 
-    warnings
+/// The most recently published version. The API lists versions in semver
+/// order, so a backport to an older line isn't necessarily first.
+pub fn latest_release(versions: &[Version]) -> Option<&Version> {
+    versions.iter().max_by_key(|v| {
+        DateTime::parse_from_rfc3339(&v.created_at)
+            .map(|created| created.timestamp())
+            .unwrap_or(i64::MIN)
+    })
+}
+
+/// Whether the owners differ from an acknowledged set, ignoring order
+pub fn owners_changed(owners: &[User], acknowledged: &[String]) -> bool {
+    owners.len() != acknowledged.len() || owners.iter().any(|o| !acknowledged.contains(&o.login))
+}
+
+// Ownership check - a new publisher or a changed owner set right before a
+// release is a common sign of an account or crate takeover.
+// `acknowledged_owners` is the owner set the user last accepted.
+pub fn ownership_check(
+    name: &str,
+    owners: &[User],
+    versions: &[Version],
+    acknowledged_owners: Option<&[String]>,
+    policy: &Policy,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let rule = &policy.rules.ownership_change;
+
+    if !policy.applies(name, &rule.rule) {
+        return findings;
+    }
+
+    // 1. Latest release published by someone who never published before
+    if let Some(latest) = latest_release(versions) {
+        let is_recent = DateTime::parse_from_rfc3339(&latest.created_at)
+            .map(|created| {
+                chrono::Utc::now().signed_duration_since(created).num_days() < rule.recent_days
+            })
+            .unwrap_or(false);
+
+        let previous_publishers: Vec<&str> = versions
+            .iter()
+            .filter(|v| v.num != latest.num)
+            .filter_map(|v| v.published_by.as_ref())
+            .map(|u| u.login.as_str())
+            .collect();
+
+        if let Some(publisher) = &latest.published_by {
+            if is_recent
+                && !previous_publishers.is_empty()
+                && !previous_publishers.contains(&publisher.login.as_str())
+            {
+                findings.push(policy.finding(
+                    "ownership-change",
+                    Severity::High,
+                    format!(
+                        "v{} was published by '{}', who has not published this crate before",
+                        latest.num, publisher.login
+                    ),
+                ));
+            }
+        }
+    }
+
+    // 2. Owner set changed since the user last acknowledged it
+    if let Some(acknowledged) = acknowledged_owners {
+        let current: Vec<&str> = owners.iter().map(|o| o.login.as_str()).collect();
+        let added: Vec<&str> = current
+            .iter()
+            .filter(|login| !acknowledged.iter().any(|p| p == *login))
+            .copied()
+            .collect();
+        let removed: Vec<&str> = acknowledged
+            .iter()
+            .map(|p| p.as_str())
+            .filter(|p| !current.contains(p))
+            .collect();
+
+        if !added.is_empty() || !removed.is_empty() {
+            let mut changes = Vec::new();
+            if !added.is_empty() {
+                changes.push(format!("added {}", added.join(", ")));
+            }
+            if !removed.is_empty() {
+                changes.push(format!("removed {}", removed.join(", ")));
+            }
+            findings.push(policy.finding(
+                "ownership-change",
+                Severity::High,
+                format!(
+                    "Owners changed since last acknowledged: {}",
+                    changes.join("; ")
+                ),
+            ));
+        }
+    }
+
+    findings
 }
 
 // Simple Levenshtein distance implementation for detecting similar crate names
@@ -287,12 +1135,12 @@ fn levenshtein_distance(s1: &str, s2: &str) -> usize {
 
     let mut matrix = vec![vec![0; s2_len + 1]; s1_len + 1];
 
-    for i in 0..=s1_len {
-        matrix[i][0] = i;
+    for (i, row) in matrix.iter_mut().enumerate() {
+        row[0] = i;
     }
 
-    for j in 0..=s2_len {
-        matrix[0][j] = j;
+    for (j, cell) in matrix[0].iter_mut().enumerate() {
+        *cell = j;
     }
 
     for j in 1..=s2_len {
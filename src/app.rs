@@ -1,18 +1,123 @@
-use crate::api::{self, Crate, Repository};
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crate::api::{
+    self, Advisory, Category, Crate, CratesPage, Dependency, DocsStatus, DocsStatusResult, Finding,
+    Keyword, RepoInfo, Repository, SortOrder, User, Version,
+};
+use crate::browser::{self, Link};
+use crate::clipboard;
+use crate::columns::Column;
+use crate::config;
+use crate::diff::{self, VersionDiff};
+use crate::filter::SearchFilters;
+use crate::fuzzy;
+use crate::keymap::{Action, Context, KeyPress, Keymap, Lookup};
+use crate::live::{LiveSearch, Response};
+use crate::mouse::{HitMap, Target};
+use crate::msrv::{self, RustSupport};
+use crate::policy::Policy;
+use crate::query::Query;
+use crate::readme::{self, Readme};
+use crate::rustdoc::{self, ApiDocs};
+use crate::score::{self, Scorecard};
+use crate::session::Session;
+use crate::sets::ComparisonSets;
+use crate::store::{CrateRecord, Store};
+use crate::watch::{self, CheckResult, Watchlist};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::error;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
 
 pub type AppResult<T> = std::result::Result<T, Box<dyn error::Error>>;
 
-#[derive(PartialEq)]
+// How many rows from the bottom of the list the next page starts loading
+const LOAD_MORE_THRESHOLD: usize = 3;
+// Pages fetched in one go while looking for rows that pass the search filters
+const MAX_FILTERED_PAGES: usize = 5;
+// Keywords shown next to the category tree
+const POPULAR_KEYWORDS: usize = 50;
+// Pause in typing before a live search starts
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(300);
+// Shortest name prefix worth autocompleting
+const MIN_SUGGEST_PREFIX: usize = 2;
+// How often watched crates are checked for changes while the app is open
+const WATCH_INTERVAL: Duration = Duration::from_secs(30 * 60);
+// Lines the mouse wheel scrolls a view by
+const SCROLL_LINES: usize = 3;
+// More columns than this get too narrow to read
+const MAX_SIDE_BY_SIDE: usize = 4;
+// docs.rs is asked about this many of the newest versions when the history
+// opens; older ones are checked once selected
+const DOCS_STATUS_VERSIONS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Tab {
     Search,
     Recent,
     Trending,
     Compare,
+    Categories,
+    Watchlist,
     Help,
 }
 
+/// Where the crates in the Search and Recent lists came from, so further
+/// pages can be requested
+#[derive(Clone, PartialEq)]
+pub enum Listing {
+    Recent,
+    Search { query: Query, sort: SortOrder },
+    Category(String),
+    Keyword(String),
+}
+
+impl Listing {
+    // Further pages load as the selection nears the end of the list
+    fn fetch(&self, page: usize) -> AppResult<CratesPage> {
+        let page_size = config::get().general.page_size;
+        match self {
+            Listing::Recent => api::recent_crates(page, page_size),
+            Listing::Search { query, sort } => {
+                api::search_crates(&query.params, *sort, page, page_size)
+            }
+            Listing::Category(slug) => api::category_crates(slug, page, page_size),
+            Listing::Keyword(keyword) => api::keyword_crates(keyword, page, page_size),
+        }
+    }
+}
+
+/// Which side of the Categories tab has focus
+#[derive(PartialEq)]
+pub enum BrowsePane {
+    Categories,
+    Keywords,
+}
+
+/// A row in the category tree; children are fetched when first expanded
+pub struct CategoryNode {
+    pub category: Category,
+    pub depth: usize,
+    pub expanded: bool,
+}
+
+/// A keyword or category slug a crate is tagged with, selectable in the
+/// detail view to browse other crates with the same tag
+#[derive(Clone, PartialEq)]
+pub enum Tag {
+    Keyword(String),
+    Category(String),
+}
+
+/// What the name typed in the Compare tab's set prompt is for
+#[derive(Clone, PartialEq)]
+pub enum SetPrompt {
+    Save,
+    Rename(String),
+}
+
 pub enum LoadingState {
     NotLoading,
     Loading,
@@ -21,23 +126,62 @@ pub enum LoadingState {
 }
 
 pub struct SecurityInfo {
-    pub warnings: Vec<String>,
+    pub warnings: Vec<Finding>,
     pub safe: bool,
 }
 
+pub struct OwnershipInfo {
+    pub owners: Vec<User>,
+    pub versions: Vec<Version>,
+    pub latest_publisher: Option<User>,
+    /// The owners differ from the ones last acknowledged
+    pub owners_changed: bool,
+    pub findings: Vec<Finding>,
+}
+
 #[allow(dead_code)]
 pub struct ComparedCrate {
     pub details: Crate,
     pub security: SecurityInfo,
-    pub selected: bool,
+    pub score: Scorecard,
+    pub docs: Option<DocsStatus>,
+    pub rust: Option<RustSupport>,
+    pub reverse_deps: Option<u64>,
+    pub repo: Option<RepoInfo>,
+    /// Of the latest version
+    pub dependencies: Option<Vec<Dependency>>,
+    pub advisories: Option<Vec<Advisory>>,
+    /// When the newest version was published
+    pub last_release: Option<String>,
+    /// Features of the latest version
+    pub features: Option<BTreeMap<String, Vec<String>>>,
+    /// Daily downloads, fetched when the crate is first viewed side by side
+    pub download_history: Option<Vec<u64>>,
+    /// The owners differ from the ones last acknowledged
+    pub owners_changed: bool,
+    /// Marked to be viewed side by side
+    pub marked: bool,
 }
 
 pub struct App {
     pub running: bool,
     pub current_tab: Tab,
     pub crates: Vec<Crate>,
+    pub listing: Listing,
+    pub page: usize,
+    pub total_crates: u64,
     pub repos: Vec<Repository>,
     pub search_query: String,
+    /// Why the search box text could not be parsed, shown in the input
+    pub query_error: Option<String>,
+    pub live: LiveSearch,
+    /// When the search text last changed, until the debounced search starts
+    pub last_edit: Option<Instant>,
+    pub suggestions: Vec<String>,
+    pub suggestion_index: Option<usize>,
+    /// Quick filter over the loaded rows of the current tab
+    pub fuzzy_query: String,
+    pub fuzzy_mode: bool,
     pub selected_index: usize,
     pub loading_state: LoadingState,
     pub trend_period: String,
@@ -45,102 +189,380 @@ pub struct App {
     pub input_mode: bool,
     pub detail_scroll: usize,
     pub compared_crates: Vec<ComparedCrate>,
+    /// Crates of a restored or opened comparison still loading in the background
+    pub compare_pending: Vec<String>,
+    compare_missing: Vec<String>,
+    pub compare_load: Option<Receiver<CompareResult>>,
     pub compare_search_query: String,
     pub compare_input_mode: bool,
+    pub show_versions: bool,
+    pub versions: Vec<Version>,
+    pub version_index: usize,
+    pub diff_marks: Vec<usize>,
+    pub show_diff: bool,
+    pub version_diff: Option<VersionDiff>,
+    pub diff_file_index: usize,
+    pub diff_scroll: usize,
+    pub version_error: Option<String>,
+    /// docs.rs status by crate and version, as they come in; None when it
+    /// could not be fetched
+    pub version_docs: HashMap<(String, String), Option<DocsStatus>>,
+    /// Versions whose docs.rs status has been asked for
+    pub version_docs_requested: HashSet<(String, String)>,
+    pub version_docs_checks: Vec<(String, Receiver<DocsStatusResult>)>,
+    pub show_readme: bool,
+    /// The README last opened, kept so reopening it doesn't download the crate again
+    pub readme: Option<Readme>,
+    pub readme_error: Option<String>,
+    pub readme_scroll: usize,
+    pub show_side_by_side: bool,
+    /// Names of the compared crates shown side by side, two to four of
+    /// them. Names rather than rows, since the comparison re-sorts as
+    /// crates finish loading.
+    pub side_by_side: Vec<String>,
+    pub side_by_side_scroll: usize,
+    pub show_api: bool,
+    /// The API docs last opened, kept like the README
+    pub api_docs: Option<ApiDocs>,
+    /// API docs still loading or building in the background, with the
+    /// crate and version they are for
+    pub api_load: Option<(String, String, Receiver<rustdoc::LoadResult>)>,
+    /// The load in progress compiles the crate on this machine
+    pub api_building: bool,
+    pub api_error: Option<String>,
+    /// docs.rs had no docs and a local build is waiting to be confirmed
+    pub api_build_prompt: bool,
+    /// Selected row of the item tree or search results
+    pub api_index: usize,
+    /// Items of the tree shown open
+    pub api_expanded: HashSet<usize>,
+    pub api_query: String,
+    pub api_search_mode: bool,
+    pub api_scroll: usize,
+    pub ownership: Option<OwnershipInfo>,
+    /// docs.rs status of the latest version of the crate in the detail view
+    pub docs_status: Option<DocsStatus>,
+    /// What Rust the latest version of the crate in the detail view needs
+    pub rust_support: Option<RustSupport>,
+    pub store: Store,
+    pub policy: Policy,
+    pub scorecard: Option<Scorecard>,
+    /// Metric columns of the Compare table, in order
+    pub compare_columns: Vec<Column>,
+    /// Column the comparison is sorted by, best first
+    pub compare_sort: Option<Column>,
+    pub columns_mode: bool,
+    /// Selected entry of the column menu, which lists the shown columns first
+    pub column_index: usize,
+    pub search_sort: SortOrder,
+    pub search_filters: SearchFilters,
+    pub filter_mode: bool,
+    pub categories: Vec<CategoryNode>,
+    pub keywords: Vec<Keyword>,
+    pub browse_pane: BrowsePane,
+    pub category_index: usize,
+    pub keyword_index: usize,
+    pub browse_listing: bool,
+    pub detail_tags: Vec<Tag>,
+    pub detail_tag_index: Option<usize>,
+    pub watchlist: Watchlist,
+    /// Results of a watchlist check still running in the background
+    pub watch_check: Option<Receiver<CheckResult>>,
+    pub last_watch_check: Option<Instant>,
+    pub comparison_sets: ComparisonSets,
+    /// The saved set the comparison was loaded from or last saved as
+    pub current_set: Option<String>,
+    pub sets_mode: bool,
+    pub set_index: usize,
+    pub set_prompt: Option<SetPrompt>,
+    pub set_name_input: String,
+    pub set_error: Option<String>,
+    pub keymap: Keymap,
+    /// Keys of a sequence typed so far, e.g. the first `g` of `g g`
+    pub pending_keys: Vec<KeyPress>,
+    pub help_scroll: usize,
+    /// What the last frame drew where, for mouse clicks
+    pub hits: HitMap,
+    /// A one-off message for the status bar, cleared by the next key
+    pub notice: Option<String>,
 }
 
 impl App {
-    pub fn new() -> Self {
-        let mut app = Self {
+    /// Create the app, picking up where `session` left off if one is given
+    pub fn new(policy: Policy, session: Option<Session>) -> Self {
+        let mut app = Self::blank(policy);
+        let (store, store_warning) = Store::load();
+        let (comparison_sets, sets_warning) = ComparisonSets::load();
+        app.store = store;
+        app.comparison_sets = comparison_sets;
+        app.watchlist = Watchlist::load();
+        app.live = LiveSearch::spawn();
+        // A damaged data file was set aside; say so until the first key
+        app.notice = [store_warning, sets_warning]
+            .into_iter()
+            .flatten()
+            .reduce(|a, b| format!("{} | {}", a, b));
+
+        // Load initial data; the configured startup tab wins over the restored one
+        let mut session = session.unwrap_or_default();
+        if let Some(tab) = config::get().general.startup_tab {
+            if tab != session.tab {
+                session.tab = tab;
+                session.selected_index = 0;
+            }
+        }
+        app.restore(session);
+
+        app
+    }
+
+    // The app before anything has been loaded, with empty stores and a live
+    // search that never answers, so tests don't touch the data directory or
+    // the network
+    fn blank(policy: Policy) -> Self {
+        Self {
             running: true,
             current_tab: Tab::Search,
             crates: Vec::new(),
+            listing: Listing::Recent,
+            page: 0,
+            total_crates: 0,
             repos: Vec::new(),
             search_query: String::new(),
+            query_error: None,
+            live: LiveSearch::detached().0,
+            last_edit: None,
+            suggestions: Vec::new(),
+            suggestion_index: None,
+            fuzzy_query: String::new(),
+            fuzzy_mode: false,
             selected_index: 0,
             loading_state: LoadingState::NotLoading,
-            trend_period: "weekly".to_string(),
+            trend_period: config::get().general.trend_period.clone(),
             show_detail: false,
             input_mode: false,
             detail_scroll: 0,
             compared_crates: Vec::new(),
+            compare_pending: Vec::new(),
+            compare_missing: Vec::new(),
+            compare_load: None,
             compare_search_query: String::new(),
             compare_input_mode: false,
-        };
+            show_versions: false,
+            versions: Vec::new(),
+            version_index: 0,
+            diff_marks: Vec::new(),
+            show_diff: false,
+            version_diff: None,
+            diff_file_index: 0,
+            diff_scroll: 0,
+            version_error: None,
+            version_docs: HashMap::new(),
+            version_docs_requested: HashSet::new(),
+            version_docs_checks: Vec::new(),
+            show_readme: false,
+            readme: None,
+            readme_error: None,
+            readme_scroll: 0,
+            show_side_by_side: false,
+            side_by_side: Vec::new(),
+            side_by_side_scroll: 0,
+            show_api: false,
+            api_docs: None,
+            api_load: None,
+            api_building: false,
+            api_error: None,
+            api_build_prompt: false,
+            api_index: 0,
+            api_expanded: HashSet::new(),
+            api_query: String::new(),
+            api_search_mode: false,
+            api_scroll: 0,
+            ownership: None,
+            docs_status: None,
+            rust_support: None,
+            store: Store::default(),
+            policy,
+            scorecard: None,
+            compare_columns: Column::DEFAULTS.to_vec(),
+            compare_sort: None,
+            columns_mode: false,
+            column_index: 0,
+            search_sort: SortOrder::Downloads,
+            search_filters: SearchFilters::default(),
+            filter_mode: false,
+            categories: Vec::new(),
+            keywords: Vec::new(),
+            browse_pane: BrowsePane::Categories,
+            category_index: 0,
+            keyword_index: 0,
+            browse_listing: false,
+            detail_tags: Vec::new(),
+            detail_tag_index: None,
+            watchlist: Watchlist::default(),
+            watch_check: None,
+            last_watch_check: None,
+            comparison_sets: ComparisonSets::default(),
+            current_set: None,
+            sets_mode: false,
+            set_index: 0,
+            set_prompt: None,
+            set_name_input: String::new(),
+            set_error: None,
+            // The config was validated on load, so its keys are known to parse
+            keymap: Keymap::new(&config::get().keys).unwrap_or_default(),
+            pending_keys: Vec::new(),
+            help_scroll: 0,
+            hits: HitMap::default(),
+            notice: None,
+        }
+    }
+
+    fn restore(&mut self, session: Session) {
+        self.trend_period = session.trend_period;
+        self.search_query = session.search_query;
+        self.current_tab = session.tab;
+        if !session.compare_columns.is_empty() {
+            self.compare_columns = session.compare_columns;
+        }
+        self.compare_sort = session
+            .compare_sort
+            .filter(|column| self.compare_columns.contains(column));
 
-        // Load initial data
-        app.load_recent_crates();
+        if !session.compared.is_empty() {
+            self.load_comparison(&session.compared);
+        }
+        self.current_set = session.current_set;
 
-        app
+        // Only fetch what the restored tab shows; other tabs load when opened
+        match self.current_tab {
+            Tab::Search if !self.search_query.is_empty() => self.search_crates(),
+            Tab::Trending => self.load_trending_repos(),
+            Tab::Categories => self.load_browse(),
+            _ => self.load_recent_crates(),
+        }
+
+        // The lists may have changed since the last run. A comparison is
+        // still loading, so its rows are counted by name.
+        let rows = match self.current_tab {
+            Tab::Compare => self.compare_pending.len(),
+            _ => self.visible_rows().len(),
+        };
+        self.selected_index = session.selected_index.min(rows.saturating_sub(1));
+    }
+
+    /// The state to restore on the next launch
+    pub fn session(&self) -> Session {
+        Session {
+            tab: self.current_tab,
+            search_query: self.search_query.clone(),
+            selected_index: self.selected_row().unwrap_or(0),
+            trend_period: self.trend_period.clone(),
+            compared: self
+                .compared_crates
+                .iter()
+                .map(|c| c.details.name.clone())
+                .chain(self.compare_pending.iter().cloned())
+                .collect(),
+            current_set: self.current_set.clone(),
+            compare_columns: self.compare_columns.clone(),
+            compare_sort: self.compare_sort,
+        }
     }
 
     pub fn tick(&mut self) {
+        self.update_live_search();
+        self.update_comparison();
+        self.update_watchlist();
+        self.update_api_docs();
+        self.update_version_docs();
+
         // Update app state on tick
-        match self.loading_state {
-            LoadingState::Loading => match self.current_tab {
+        if let LoadingState::Loading = self.loading_state {
+            match self.current_tab {
                 Tab::Recent => self.load_recent_crates(),
                 Tab::Trending => self.load_trending_repos(),
-                Tab::Search => {
-                    if !self.search_query.is_empty() {
-                        self.search_crates();
-                    }
-                }
+                Tab::Search if !self.search_query.is_empty() => self.search_crates(),
+                Tab::Categories if !self.browse_listing => self.load_browse(),
                 _ => {}
-            },
-            _ => {}
+            }
         }
     }
 
-    pub fn add_to_comparison(&mut self) {
-        if self.current_tab == Tab::Recent || self.current_tab == Tab::Search {
-            if !self.crates.is_empty() && self.selected_index < self.crates.len() {
-                let current_crate = &self.crates[self.selected_index];
+    /// Whether the current tab shows the shared crate list
+    pub fn showing_crates(&self) -> bool {
+        match self.current_tab {
+            Tab::Search | Tab::Recent => true,
+            Tab::Categories => self.browse_listing,
+            _ => false,
+        }
+    }
 
-                // Check if already in comparison
-                if self
-                    .compared_crates
-                    .iter()
-                    .any(|c| c.details.name == current_crate.name)
-                {
-                    return; // Already added
-                }
+    /// Indices into the current tab's list of the rows on screen, narrowed
+    /// by the quick filter when one is active
+    pub fn visible_rows(&self) -> Vec<usize> {
+        let query = self.fuzzy_query.as_str();
 
-                // Fetch full details for the crate
-                match api::get_crate_details(&current_crate.name) {
-                    Ok(details) => {
-                        let security_warnings = api::security_check(&details);
-                        self.compared_crates.push(ComparedCrate {
-                            details,
-                            security: SecurityInfo {
-                                warnings: security_warnings.clone(),
-                                safe: security_warnings.is_empty(),
-                            },
-                            selected: false,
-                        });
-                    }
-                    Err(_) => {
-                        // If we can't get details, use the basic info we have
-                        let security_warnings = api::security_check(current_crate);
-                        self.compared_crates.push(ComparedCrate {
-                            details: current_crate.clone(),
-                            security: SecurityInfo {
-                                warnings: security_warnings.clone(),
-                                safe: security_warnings.is_empty(),
-                            },
-                            selected: false,
-                        });
-                    }
-                }
+        match self.current_tab {
+            _ if self.showing_crates() => (0..self.crates.len())
+                .filter(|&i| {
+                    query.is_empty() || fuzzy::match_crate(query, &self.crates[i]).is_some()
+                })
+                .collect(),
+            Tab::Trending => (0..self.repos.len())
+                .filter(|&i| query.is_empty() || fuzzy::match_repo(query, &self.repos[i]).is_some())
+                .collect(),
+            Tab::Watchlist => (0..self.watchlist.crates.len()).collect(),
+            Tab::Compare => (0..self.compared_crates.len())
+                .filter(|&i| {
+                    query.is_empty()
+                        || fuzzy::match_crate(query, &self.compared_crates[i].details).is_some()
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Index of the selected row in the current tab's list
+    pub fn selected_row(&self) -> Option<usize> {
+        self.visible_rows().get(self.selected_index).copied()
+    }
+
+    fn clear_fuzzy(&mut self) {
+        self.fuzzy_query.clear();
+        self.fuzzy_mode = false;
+    }
+
+    pub fn add_to_comparison(&mut self) {
+        let row = self.selected_row();
+        if let (true, Some(row)) = (self.showing_crates(), row) {
+            let current_crate = &self.crates[row];
+
+            // Check if already in comparison
+            if self
+                .compared_crates
+                .iter()
+                .any(|c| c.details.name == current_crate.name)
+            {
+                return; // Already added
             }
+
+            // Fetch full details for the crate, falling back to the basic info we have
+            let details = api::get_crate_details(&current_crate.name)
+                .unwrap_or_else(|_| current_crate.clone());
+            let compared = self.compare_entry(details);
+            self.compared_crates.push(compared);
+            self.sort_comparison();
         }
     }
 
     pub fn remove_from_comparison(&mut self) {
-        if self.current_tab == Tab::Compare && !self.compared_crates.is_empty() {
-            self.compared_crates.remove(self.selected_index);
-            if self.selected_index >= self.compared_crates.len() && !self.compared_crates.is_empty()
-            {
-                self.selected_index = self.compared_crates.len() - 1;
+        let row = self.selected_row();
+        if let (Tab::Compare, Some(row)) = (&self.current_tab, row) {
+            self.compared_crates.remove(row);
+            let visible = self.visible_rows().len();
+            if self.selected_index >= visible && visible > 0 {
+                self.selected_index = visible - 1;
             }
         }
     }
@@ -157,15 +579,9 @@ impl App {
                     return; // Already added
                 }
 
-                let security_warnings = api::security_check(&details);
-                self.compared_crates.push(ComparedCrate {
-                    details,
-                    security: SecurityInfo {
-                        warnings: security_warnings.clone(),
-                        safe: security_warnings.is_empty(),
-                    },
-                    selected: false,
-                });
+                let compared = self.compare_entry(details);
+                self.compared_crates.push(compared);
+                self.sort_comparison();
             }
             Err(_) => {
                 // Handle error - perhaps show a message to the user
@@ -173,21 +589,412 @@ impl App {
         }
     }
 
-    pub fn handle_key_event(&mut self, key: KeyEvent) {
-        // Handle quit event in any mode
-        if key.code == KeyCode::Char('q') && !self.input_mode && !self.compare_input_mode {
-            self.running = false;
+    fn compare_entry(&mut self, details: Crate) -> ComparedCrate {
+        let compared = compare_entry(&mut self.store, &self.policy, details);
+        let _ = self.store.save();
+        compared
+    }
+
+    /// Replace the comparison with the crates of a saved set. Crates that can
+    /// no longer be fetched are skipped and reported.
+    fn load_set(&mut self, name: &str) {
+        let Some(crates) = self.comparison_sets.sets.get(name).cloned() else {
+            return;
+        };
+
+        self.load_comparison(&crates);
+        self.current_set = Some(name.to_string());
+        self.selected_index = 0;
+    }
+
+    // Replace the comparison with the named crates. They load in the
+    // background; those that can't be fetched are reported in `set_error`.
+    fn load_comparison(&mut self, names: &[String]) {
+        self.compared_crates.clear();
+        self.compare_pending = names.to_vec();
+        self.compare_missing.clear();
+        self.set_error = None;
+        // Replacing the receiver stops a load that is still running
+        self.compare_load = Some(spawn_compare(
+            names.to_vec(),
+            self.store.clone(),
+            self.policy.clone(),
+        ));
+    }
+
+    // Add compared crates as the background load delivers them
+    fn update_comparison(&mut self) {
+        let Some(ref receiver) = self.compare_load else {
+            return;
+        };
+        let mut results = Vec::new();
+        let finished = loop {
+            match receiver.try_recv() {
+                Ok(result) => results.push(result),
+                Err(TryRecvError::Empty) => break false,
+                Err(TryRecvError::Disconnected) => break true,
+            }
+        };
+
+        for (name, result) in results {
+            self.compare_pending.retain(|pending| *pending != name);
+            match result {
+                Ok((compared, record)) => {
+                    if let Some(mut record) = record {
+                        // Keep owners acknowledged while the crate was loading
+                        if let Some(current) = self.store.crates.get(&compared.details.name) {
+                            record.acknowledged_owners = current.acknowledged_owners.clone();
+                        }
+                        self.store
+                            .crates
+                            .insert(compared.details.name.clone(), record);
+                    }
+                    // It may have been added by hand in the meantime
+                    if !self
+                        .compared_crates
+                        .iter()
+                        .any(|c| c.details.name == compared.details.name)
+                    {
+                        self.compared_crates.push(compared);
+                    }
+                }
+                Err(_) => self.compare_missing.push(name),
+            }
+        }
+        self.sort_comparison();
+
+        if finished {
+            self.compare_load = None;
+            self.compare_pending.clear();
+            let _ = self.store.save();
+            if !self.compare_missing.is_empty() {
+                self.set_error = Some(format!(
+                    "Could not load {}",
+                    self.compare_missing.join(", ")
+                ));
+            }
+        }
+    }
+
+    fn open_set_prompt(&mut self, prompt: SetPrompt) {
+        self.set_name_input = match prompt {
+            SetPrompt::Save => self.current_set.clone().unwrap_or_default(),
+            SetPrompt::Rename(ref old) => old.clone(),
+        };
+        self.set_prompt = Some(prompt);
+        self.set_error = None;
+    }
+
+    fn submit_set_prompt(&mut self) {
+        let Some(prompt) = self.set_prompt.take() else {
+            return;
+        };
+        let name = self.set_name_input.trim().to_string();
+
+        let result = match prompt {
+            SetPrompt::Save => {
+                let crates = self
+                    .compared_crates
+                    .iter()
+                    .map(|c| c.details.name.clone())
+                    .collect();
+                self.comparison_sets.store(&name, crates)
+            }
+            SetPrompt::Rename(ref old) => self.comparison_sets.rename(old, &name),
+        };
+        let result = result.and_then(|_| self.comparison_sets.save());
+
+        match result {
+            Ok(()) => {
+                let renamed_current = matches!(prompt, SetPrompt::Rename(ref old) if self.current_set.as_ref() == Some(old));
+                if prompt == SetPrompt::Save || renamed_current {
+                    self.current_set = Some(name);
+                }
+                self.set_error = None;
+            }
+            Err(e) => {
+                self.set_error = Some(e.to_string());
+                // Keep the prompt open so the name can be fixed
+                self.set_prompt = Some(prompt);
+            }
+        }
+    }
+
+    /// Name of the set highlighted in the saved sets list
+    fn selected_set(&self) -> Option<String> {
+        self.comparison_sets
+            .sets
+            .keys()
+            .nth(self.set_index)
+            .cloned()
+    }
+
+    fn handle_set_prompt(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => self.submit_set_prompt(),
+            KeyCode::Esc => {
+                self.set_prompt = None;
+                self.set_error = None;
+            }
+            KeyCode::Char(c) => self.set_name_input.push(c),
+            KeyCode::Backspace => {
+                self.set_name_input.pop();
+            }
+            _ => {}
+        }
+    }
+
+    // The saved sets list: open loads a set, and it can be renamed or removed
+    fn handle_sets_mode(&mut self, key: KeyEvent) {
+        let len = self.comparison_sets.sets.len();
+        let Some(action) = self.key_action(Context::Sets, key) else {
+            return;
+        };
+        match action {
+            Action::Back | Action::OpenSets => {
+                self.sets_mode = false;
+                self.set_error = None;
+            }
+            Action::Down if len > 0 => {
+                self.set_index = (self.set_index + 1) % len;
+            }
+            Action::Up if len > 0 => {
+                self.set_index = (self.set_index + len - 1) % len;
+            }
+            Action::Open => {
+                if let Some(name) = self.selected_set() {
+                    self.sets_mode = false;
+                    self.load_set(&name);
+                }
+            }
+            Action::Rename => {
+                if let Some(name) = self.selected_set() {
+                    self.open_set_prompt(SetPrompt::Rename(name));
+                }
+            }
+            Action::Remove => {
+                if let Some(name) = self.selected_set() {
+                    self.comparison_sets.delete(&name);
+                    if self.current_set.as_ref() == Some(&name) {
+                        self.current_set = None;
+                    }
+                    self.set_index = self.set_index.min(len.saturating_sub(2));
+                    if let Err(e) = self.comparison_sets.save() {
+                        self.set_error = Some(e.to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Keep the comparison ordered by the sort column, best first and unknown
+    // values last
+    fn sort_comparison(&mut self) {
+        if let Some(column) = self.compare_sort {
+            self.compared_crates
+                .sort_by_key(|c| std::cmp::Reverse(column.rank(c)));
+        }
+    }
+
+    // Sort by a column, or stop sorting if it already is the sort column
+    fn sort_compare_by(&mut self, column: Column) {
+        self.compare_sort = (self.compare_sort != Some(column)).then_some(column);
+        self.sort_comparison();
+        self.selected_index = 0;
+    }
+
+    // The column menu: shown columns in table order, then the hidden ones
+    pub fn column_menu(&self) -> Vec<(Column, bool)> {
+        let shown = self.compare_columns.iter().map(|c| (*c, true));
+        let hidden = Column::ALL
+            .into_iter()
+            .filter(|c| !self.compare_columns.contains(c))
+            .map(|c| (c, false));
+        shown.chain(hidden).collect()
+    }
+
+    // Expand shows or hides, move down/up moves a shown column right or
+    // left and sort sorts by it
+    fn handle_columns_mode(&mut self, key: KeyEvent) {
+        let menu = self.column_menu();
+        let Some(&(column, shown)) = menu.get(self.column_index) else {
+            self.columns_mode = false;
+            return;
+        };
+        let position = self.compare_columns.iter().position(|c| *c == column);
+        let Some(action) = self.key_action(Context::Columns, key) else {
             return;
+        };
+
+        match action {
+            Action::Back | Action::Columns => self.columns_mode = false,
+            Action::Down => {
+                self.column_index = (self.column_index + 1) % menu.len();
+            }
+            Action::Up => {
+                self.column_index = (self.column_index + menu.len() - 1) % menu.len();
+            }
+            Action::Expand => match position {
+                // Keep at least one metric next to the names
+                Some(index) if self.compare_columns.len() > 1 => {
+                    self.compare_columns.remove(index);
+                    if self.compare_sort == Some(column) {
+                        self.compare_sort = None;
+                    }
+                    self.column_index = self.compare_columns.len();
+                }
+                Some(_) => {}
+                None => {
+                    self.compare_columns.push(column);
+                    self.column_index = self.compare_columns.len() - 1;
+                }
+            },
+            Action::MoveDown => {
+                if let Some(index) = position.filter(|i| i + 1 < self.compare_columns.len()) {
+                    self.compare_columns.swap(index, index + 1);
+                    self.column_index = index + 1;
+                }
+            }
+            Action::MoveUp => {
+                if let Some(index) = position.filter(|i| *i > 0) {
+                    self.compare_columns.swap(index, index - 1);
+                    self.column_index = index - 1;
+                }
+            }
+            Action::Sort if shown => self.sort_compare_by(column),
+            _ => {}
+        }
+    }
+
+    fn open_detail(&mut self) {
+        self.stop_live_search();
+        self.show_detail = true;
+        self.detail_scroll = 0;
+        self.ownership = None;
+        self.docs_status = None;
+        self.rust_support = None;
+        self.scorecard = None;
+        self.detail_tags = Vec::new();
+        self.detail_tag_index = None;
+
+        if self.current_tab == Tab::Compare {
+            // Compared crates already carry full details, findings and a scorecard
+            if let Some(compared) = self
+                .selected_row()
+                .and_then(|row| self.compared_crates.get(row))
+            {
+                self.detail_tags = crate_tags(&compared.details);
+            }
+            return;
+        }
+
+        if !self.showing_crates() {
+            return;
+        }
+        let mut details = match self.selected_row().and_then(|row| self.crates.get(row)) {
+            Some(crate_data) => crate_data.clone(),
+            None => return,
+        };
+
+        // Listings leave out keywords and categories; the crate endpoint has them
+        if details.keywords.is_none() && details.categories.is_none() {
+            if let Ok(full) = api::get_crate_details(&details.name) {
+                details = full;
+            }
+        }
+        self.detail_tags = crate_tags(&details);
+
+        self.ownership = inspect_ownership(&mut self.store, &self.policy, &details.name);
+        // Saving is best effort; a read-only data directory shouldn't break browsing
+        let _ = self.store.save();
+
+        self.docs_status = api::docs_status(&details.name, &details.max_version).ok();
+        self.rust_support = self
+            .ownership
+            .as_ref()
+            .and_then(|o| msrv::support(&o.versions, &details.max_version));
+
+        let mut findings = api::security_check(&details, &self.policy);
+        if let Some(ref ownership) = self.ownership {
+            findings.extend(ownership.findings.iter().cloned());
         }
+        findings.extend(api::docs_check(
+            &details.name,
+            self.docs_status.as_ref(),
+            &self.policy,
+        ));
+        findings.extend(msrv::check(
+            &details.name,
+            self.rust_support.as_ref(),
+            &self.policy,
+        ));
+
+        self.scorecard = Some(score::scorecard(
+            &details,
+            &findings,
+            self.ownership.as_ref().map(|o| o.owners.as_slice()),
+            self.ownership
+                .as_ref()
+                .map(|o| o.versions.as_slice())
+                .unwrap_or(&[]),
+        ));
+    }
+
+    pub fn handle_key_event(&mut self, key: KeyEvent) {
+        self.notice = None;
 
+        // Ctrl+C quits from anywhere, including text input
         if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
             self.running = false;
             return;
         }
 
+        // Handle version diff and history views, which sit on top of the detail view
+        if self.show_diff {
+            if let Some(action) = self.key_action(Context::Diff, key) {
+                self.handle_diff_action(action);
+            }
+            return;
+        }
+
+        if self.show_versions {
+            if let Some(action) = self.key_action(Context::Versions, key) {
+                self.handle_versions_action(action);
+            }
+            return;
+        }
+
+        if self.show_readme {
+            if let Some(action) = self.key_action(Context::Readme, key) {
+                self.handle_readme_action(action);
+            }
+            return;
+        }
+
+        if self.show_side_by_side {
+            if let Some(action) = self.key_action(Context::SideBySide, key) {
+                self.handle_side_by_side_action(action);
+            }
+            return;
+        }
+
+        if self.show_api {
+            if self.api_build_prompt {
+                self.handle_api_build_prompt(key);
+            } else if self.api_search_mode {
+                self.handle_api_search(key);
+            } else if let Some(action) = self.key_action(Context::Api, key) {
+                self.handle_api_action(action);
+            }
+            return;
+        }
+
         // Handle detail view mode
         if self.show_detail {
-            self.handle_detail_mode(key);
+            if let Some(action) = self.key_action(Context::Detail, key) {
+                self.handle_detail_action(action);
+            }
             return;
         }
 
@@ -197,6 +1004,38 @@ impl App {
             return;
         }
 
+        // The filter, set and column menus take single keys, so quitting still works there
+        if (self.filter_mode || self.sets_mode || self.columns_mode) && self.is_quit_key(key) {
+            self.running = false;
+            return;
+        }
+
+        if self.filter_mode {
+            self.handle_filter_mode(key);
+            return;
+        }
+
+        if self.fuzzy_mode {
+            self.handle_fuzzy_mode(key);
+            return;
+        }
+
+        // The name prompt can be opened from the sets list, so it goes first
+        if self.set_prompt.is_some() {
+            self.handle_set_prompt(key);
+            return;
+        }
+
+        if self.sets_mode {
+            self.handle_sets_mode(key);
+            return;
+        }
+
+        if self.columns_mode {
+            self.handle_columns_mode(key);
+            return;
+        }
+
         // Handle compare input mode separately
         if self.compare_input_mode {
             match key.code {
@@ -222,130 +1061,1431 @@ impl App {
             return;
         }
 
-        match key.code {
-            KeyCode::Tab => {
+        if let Some(action) = self.key_action(Context::List, key) {
+            self.handle_list_action(action);
+        }
+    }
+
+    // Resolve a key through the keymap, collecting the keys of a sequence
+    // until it is complete. A key that breaks a sequence is tried on its own.
+    fn key_action(&mut self, context: Context, key: KeyEvent) -> Option<Action> {
+        self.pending_keys.push(KeyPress::from(key));
+        match self.keymap.lookup(context, &self.pending_keys) {
+            Lookup::Action(action) => {
+                self.pending_keys.clear();
+                Some(action)
+            }
+            Lookup::Pending => None,
+            Lookup::None => {
+                let retry = self.pending_keys.len() > 1;
+                self.pending_keys.clear();
+                if retry {
+                    self.key_action(context, key)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    fn is_quit_key(&self, key: KeyEvent) -> bool {
+        matches!(
+            self.keymap.lookup(Context::List, &[KeyPress::from(key)]),
+            Lookup::Action(Action::Quit)
+        )
+    }
+
+    fn handle_list_action(&mut self, action: Action) {
+        let browsing_tree = self.current_tab == Tab::Categories && !self.browse_listing;
+
+        match action {
+            Action::Quit => {
+                self.running = false;
+            }
+            Action::NextTab => {
                 self.next_tab();
             }
-            KeyCode::BackTab => {
+            Action::PrevTab => {
                 self.prev_tab();
             }
-            KeyCode::Down | KeyCode::Char('j') => {
+            Action::Down if self.current_tab == Tab::Help => {
+                self.help_scroll = self.help_scroll.saturating_add(1);
+            }
+            Action::Up if self.current_tab == Tab::Help => {
+                self.help_scroll = self.help_scroll.saturating_sub(1);
+            }
+            Action::Down => {
                 self.next_item();
             }
-            KeyCode::Up | KeyCode::Char('k') => {
+            Action::Up => {
                 self.prev_item();
             }
-            KeyCode::Enter => {
-                self.show_detail = true;
-                self.detail_scroll = 0;
+            Action::Top => self.jump_to(false),
+            Action::Bottom => self.jump_to(true),
+            Action::Open if browsing_tree => {
+                self.browse_selected();
             }
-            KeyCode::Char('1') => {
-                self.current_tab = Tab::Search;
+            Action::Open => {
+                self.open_detail();
             }
-            KeyCode::Char('2') => {
-                self.current_tab = Tab::Recent;
-                self.load_recent_crates();
+            Action::Back if !self.fuzzy_query.is_empty() => {
+                self.clear_fuzzy();
+                self.selected_index = 0;
             }
-            KeyCode::Char('3') => {
-                self.current_tab = Tab::Trending;
-                self.load_trending_repos();
+            Action::Back if self.current_tab == Tab::Categories => {
+                self.browse_listing = false;
             }
-            KeyCode::Char('4') => {
-                self.current_tab = Tab::Help;
+            Action::Expand if browsing_tree && self.browse_pane == BrowsePane::Categories => {
+                self.toggle_category();
             }
-            KeyCode::Char('5') => {
-                self.current_tab = Tab::Compare;
+            Action::SwitchPane if browsing_tree => {
+                self.browse_pane = match self.browse_pane {
+                    BrowsePane::Categories => BrowsePane::Keywords,
+                    BrowsePane::Keywords => BrowsePane::Categories,
+                };
             }
-            KeyCode::Char('/') => {
-                if matches!(self.current_tab, Tab::Search) {
-                    self.input_mode = true;
-                    self.search_query.clear(); // Clear previous query when starting new search
-                }
+            Action::GoSearch => self.go_to_tab(Tab::Search),
+            Action::GoRecent => self.go_to_tab(Tab::Recent),
+            Action::GoTrending => self.go_to_tab(Tab::Trending),
+            Action::GoHelp => self.go_to_tab(Tab::Help),
+            Action::GoCompare => self.go_to_tab(Tab::Compare),
+            Action::GoWatchlist => self.go_to_tab(Tab::Watchlist),
+            Action::GoCategories => self.go_to_tab(Tab::Categories),
+            Action::Search if self.current_tab == Tab::Search => {
+                self.input_mode = true;
+                self.search_query.clear(); // Clear previous query when starting new search
             }
-            KeyCode::Char('a') => {
-                if matches!(self.current_tab, Tab::Search)
-                    || matches!(self.current_tab, Tab::Recent)
-                {
+            Action::Add => {
+                if self.showing_crates() {
                     self.add_to_comparison();
                 } else if matches!(self.current_tab, Tab::Compare) {
                     self.compare_input_mode = true;
                 }
             }
-            KeyCode::Char('d') => {
-                if matches!(self.current_tab, Tab::Compare) {
-                    self.remove_from_comparison();
+            Action::Remove => match self.current_tab {
+                Tab::Compare => self.remove_from_comparison(),
+                Tab::Watchlist => {
+                    if let Some(name) = self.selected_watched() {
+                        self.toggle_watch(&name);
+                    }
                 }
-            }
+                _ => {}
+            },
+            Action::Watch => {
+                if let Some(name) = self.detail_crate().map(|c| c.name.clone()) {
+                    self.toggle_watch(&name);
+                }
+            }
+            Action::CheckWatchlist if self.current_tab == Tab::Watchlist => {
+                self.start_watch_check();
+            }
+            Action::MarkRead if self.current_tab == Tab::Watchlist => {
+                let _ = self.watchlist.update(Watchlist::mark_all_read);
+            }
+            Action::Sort => match self.current_tab {
+                // Step through the shown columns, then back to unsorted
+                Tab::Compare => {
+                    let next = match self.compare_sort {
+                        None => self.compare_columns.first(),
+                        Some(column) => self
+                            .compare_columns
+                            .iter()
+                            .skip_while(|c| **c != column)
+                            .nth(1),
+                    };
+                    self.compare_sort = next.copied();
+                    self.sort_comparison();
+                    self.selected_index = 0;
+                }
+                Tab::Search => {
+                    self.search_sort = self.search_sort.next();
+                    self.reload_search();
+                }
+                _ => {}
+            },
+            Action::SaveSet
+                if self.current_tab == Tab::Compare && !self.compared_crates.is_empty() =>
+            {
+                self.open_set_prompt(SetPrompt::Save);
+            }
+            Action::Mark if self.current_tab == Tab::Compare => self.toggle_compare_mark(),
+            Action::SideBySide if self.current_tab == Tab::Compare => self.open_side_by_side(),
+            Action::Columns if self.current_tab == Tab::Compare => {
+                self.columns_mode = true;
+                self.column_index = 0;
+            }
+            Action::OpenSets if self.current_tab == Tab::Compare => {
+                self.sets_mode = true;
+                self.set_error = None;
+                self.set_index = self
+                    .current_set
+                    .as_ref()
+                    .and_then(|name| self.comparison_sets.sets.keys().position(|s| s == name))
+                    .unwrap_or(0);
+            }
+            Action::Filters if matches!(self.current_tab, Tab::Search) => {
+                self.filter_mode = true;
+            }
+            Action::QuickFilter
+                if self.showing_crates()
+                    || matches!(self.current_tab, Tab::Trending | Tab::Compare) =>
+            {
+                self.fuzzy_mode = true;
+            }
             _ => {}
         }
     }
 
-    fn handle_detail_mode(&mut self, key: KeyEvent) {
+    // Switch straight to a tab, loading what it shows
+    fn go_to_tab(&mut self, tab: Tab) {
+        self.stop_live_search();
+        self.clear_fuzzy();
+        self.current_tab = tab;
+        match tab {
+            Tab::Recent => self.load_recent_crates(),
+            Tab::Trending => self.load_trending_repos(),
+            Tab::Watchlist => self.selected_index = 0,
+            Tab::Categories => {
+                self.browse_listing = false;
+                if self.categories.is_empty() {
+                    self.load_browse();
+                }
+            }
+            Tab::Search | Tab::Compare | Tab::Help => {}
+        }
+    }
+
+    pub fn handle_mouse_event(&mut self, event: MouseEvent) {
+        // Typing and menus own the screen until they are closed
+        if self.input_mode
+            || self.api_search_mode
+            || self.compare_input_mode
+            || self.filter_mode
+            || self.fuzzy_mode
+            || self.sets_mode
+            || self.columns_mode
+            || self.set_prompt.is_some()
+        {
+            return;
+        }
+
+        match event.kind {
+            MouseEventKind::ScrollDown => self.scroll(true),
+            MouseEventKind::ScrollUp => self.scroll(false),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let Some(target) = self.hits.at(event.column, event.row).cloned() else {
+                    return;
+                };
+                let double = self.hits.click(&target);
+                self.click(target, double);
+            }
+            _ => {}
+        }
+    }
+
+    // The wheel scrolls views and moves the selection of lists, stopping at
+    // either end rather than wrapping around like the keys do
+    fn scroll(&mut self, down: bool) {
+        let step = |value: usize| {
+            if down {
+                value.saturating_add(SCROLL_LINES)
+            } else {
+                value.saturating_sub(SCROLL_LINES)
+            }
+        };
+
+        if self.show_diff {
+            self.diff_scroll = step(self.diff_scroll);
+            return;
+        }
+        if self.show_versions {
+            let last = self.versions.len().saturating_sub(1);
+            self.version_index = if down {
+                (self.version_index + 1).min(last)
+            } else {
+                self.version_index.saturating_sub(1)
+            };
+            return;
+        }
+        if self.show_readme {
+            self.readme_scroll = step(self.readme_scroll);
+            return;
+        }
+        if self.show_side_by_side {
+            self.side_by_side_scroll = step(self.side_by_side_scroll);
+            return;
+        }
+        if self.show_api {
+            let last = self.api_rows().len().saturating_sub(1);
+            self.select_api_row(if down {
+                (self.api_index + 1).min(last)
+            } else {
+                self.api_index.saturating_sub(1)
+            });
+            return;
+        }
+        if self.show_detail {
+            self.detail_scroll = step(self.detail_scroll);
+            return;
+        }
+        if self.current_tab == Tab::Help {
+            self.help_scroll = step(self.help_scroll);
+            return;
+        }
+
+        let (index, len) = if self.current_tab == Tab::Categories && !self.browse_listing {
+            match self.browse_pane {
+                BrowsePane::Categories => (self.category_index, self.categories.len()),
+                BrowsePane::Keywords => (self.keyword_index, self.keywords.len()),
+            }
+        } else {
+            (self.selected_index, self.visible_rows().len())
+        };
+        if down && index + 1 < len {
+            self.next_item();
+        } else if !down && index > 0 {
+            self.prev_item();
+        }
+    }
+
+    fn click(&mut self, target: Target, double: bool) {
+        match target {
+            Target::Tab(tab) => {
+                self.show_diff = false;
+                self.show_versions = false;
+                self.show_readme = false;
+                self.show_side_by_side = false;
+                self.show_api = false;
+                self.show_detail = false;
+                if tab != self.current_tab {
+                    self.go_to_tab(tab);
+                }
+            }
+            Target::Row(row) => {
+                self.selected_index = row;
+                if double {
+                    self.handle_list_action(Action::Open);
+                }
+            }
+            Target::Category(row) => {
+                self.browse_pane = BrowsePane::Categories;
+                self.category_index = row;
+                if double {
+                    self.browse_selected();
+                }
+            }
+            Target::Keyword(row) => {
+                self.browse_pane = BrowsePane::Keywords;
+                self.keyword_index = row;
+                if double {
+                    self.browse_selected();
+                }
+            }
+            Target::Version(row) => {
+                self.version_index = row;
+                if double {
+                    self.toggle_diff_mark();
+                }
+            }
+            Target::ApiItem(row) => {
+                self.select_api_row(row);
+                if double {
+                    self.handle_api_action(Action::Open);
+                }
+            }
+            Target::DiffFile(index) => {
+                self.diff_file_index = index;
+                self.diff_scroll = 0;
+            }
+            Target::Column(column) => self.sort_compare_by(column),
+            Target::Link(url) => self.open_url(&url),
+        }
+    }
+
+    // Jump to the first or last row of the list, or of the focused Categories pane
+    fn jump_to(&mut self, last: bool) {
+        if self.current_tab == Tab::Categories && !self.browse_listing {
+            let (index, len) = match self.browse_pane {
+                BrowsePane::Categories => (&mut self.category_index, self.categories.len()),
+                BrowsePane::Keywords => (&mut self.keyword_index, self.keywords.len()),
+            };
+            *index = if last { len.saturating_sub(1) } else { 0 };
+            return;
+        }
+
+        self.selected_index = if last {
+            self.visible_rows().len().saturating_sub(1)
+        } else {
+            0
+        };
+        // Like moving down, nearing the end of a listing fetches the next page
+        if last && self.showing_crates() && self.fuzzy_query.is_empty() {
+            self.load_more_crates();
+        }
+    }
+
+    // Each key toggles or cycles one filter and re-runs the search right away
+    fn handle_filter_mode(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('F') => {
+                self.filter_mode = false;
+                return;
+            }
+            KeyCode::Char('s') => self.search_sort = self.search_sort.next(),
+            KeyCode::Char('l') => self.search_filters.cycle_license(),
+            KeyCode::Char('m') => self.search_filters.cycle_min_downloads(),
+            KeyCode::Char('u') => self.search_filters.cycle_updated_within(),
+            KeyCode::Char('r') => {
+                self.search_filters.has_repository = !self.search_filters.has_repository
+            }
+            KeyCode::Char('z') => {
+                self.search_filters.hide_early_versions = !self.search_filters.hide_early_versions
+            }
+            KeyCode::Char('c') => self.search_filters = SearchFilters::default(),
+            _ => return,
+        }
+        self.reload_search();
+    }
+
+    // Narrow the list as the user types; Enter keeps the filter, Esc drops it
+    fn handle_fuzzy_mode(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                self.fuzzy_mode = false;
+            }
+            KeyCode::Esc => {
+                self.clear_fuzzy();
+            }
+            KeyCode::Char(c) => {
+                self.fuzzy_query.push(c);
+            }
+            KeyCode::Backspace => {
+                self.fuzzy_query.pop();
+            }
+            KeyCode::Down => {
+                self.next_item();
+                return;
+            }
+            KeyCode::Up => {
+                self.prev_item();
+                return;
+            }
+            _ => return,
+        }
+        self.selected_index = 0;
+    }
+
+    // Re-run the current search with the active sort order and filters
+    fn reload_search(&mut self) {
+        if let Listing::Search { query, .. } = &self.listing {
+            let listing = Listing::Search {
+                query: query.clone(),
+                sort: self.search_sort,
+            };
+            self.load_listing(listing);
+            self.selected_index = 0;
+        }
+    }
+
+    fn handle_detail_action(&mut self, action: Action) {
+        match action {
+            Action::OpenLink(link) => match self.link_url(link) {
+                Some(url) => self.open_url(&url),
+                None => self.notice = Some(format!("No {} link", link.label())),
+            },
+            Action::CopyLink(link) => match self.link_url(link) {
+                Some(url) => self.copy_text(&url),
+                None => self.notice = Some(format!("No {} link", link.label())),
+            },
+            Action::CopySnippet => {
+                if let Some(snippet) = self
+                    .detail_crate()
+                    .map(|c| format!("{} = \"{}\"", c.name, c.max_version))
+                {
+                    self.copy_text(&snippet);
+                }
+            }
+            Action::Quit => {
+                self.running = false;
+            }
+            Action::Back => {
+                self.show_detail = false;
+            }
+            Action::Down => {
+                self.detail_scroll = self.detail_scroll.saturating_add(1);
+            }
+            Action::Up => {
+                self.detail_scroll = self.detail_scroll.saturating_sub(1);
+            }
+            Action::PageDown => {
+                self.detail_scroll = self.detail_scroll.saturating_add(10);
+            }
+            Action::PageUp => {
+                self.detail_scroll = self.detail_scroll.saturating_sub(10);
+            }
+            Action::Versions => {
+                self.open_version_history();
+            }
+            Action::Readme => {
+                self.open_readme();
+            }
+            Action::Api => {
+                self.open_api_docs();
+            }
+            Action::Watch => {
+                if let Some(name) = self.detail_crate().map(|c| c.name.clone()) {
+                    self.toggle_watch(&name);
+                }
+            }
+            Action::AcknowledgeOwners => {
+                self.acknowledge_owners();
+            }
+            Action::NextTag if !self.detail_tags.is_empty() => {
+                self.detail_tag_index = Some(match self.detail_tag_index {
+                    Some(i) => (i + 1) % self.detail_tags.len(),
+                    None => 0,
+                });
+            }
+            Action::PrevTag if !self.detail_tags.is_empty() => {
+                self.detail_tag_index = Some(match self.detail_tag_index {
+                    Some(i) if i > 0 => i - 1,
+                    _ => self.detail_tags.len() - 1,
+                });
+            }
+            Action::Open => {
+                if let Some(tag) = self
+                    .detail_tag_index
+                    .and_then(|i| self.detail_tags.get(i))
+                    .cloned()
+                {
+                    self.browse_tag(tag);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_readme_action(&mut self, action: Action) {
+        match action {
+            Action::Quit => {
+                self.running = false;
+            }
+            Action::Back => {
+                self.show_readme = false;
+            }
+            Action::Down => {
+                self.readme_scroll = self.readme_scroll.saturating_add(1);
+            }
+            Action::Up => {
+                self.readme_scroll = self.readme_scroll.saturating_sub(1);
+            }
+            Action::PageDown => {
+                self.readme_scroll = self.readme_scroll.saturating_add(10);
+            }
+            Action::PageUp => {
+                self.readme_scroll = self.readme_scroll.saturating_sub(10);
+            }
+            Action::Top => {
+                self.readme_scroll = 0;
+            }
+            // Drawing clamps the scroll to the last page
+            Action::Bottom => {
+                self.readme_scroll = usize::MAX;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_side_by_side_action(&mut self, action: Action) {
+        match action {
+            Action::Quit => {
+                self.running = false;
+            }
+            Action::Back => {
+                self.show_side_by_side = false;
+            }
+            Action::Down => {
+                self.side_by_side_scroll = self.side_by_side_scroll.saturating_add(1);
+            }
+            Action::Up => {
+                self.side_by_side_scroll = self.side_by_side_scroll.saturating_sub(1);
+            }
+            Action::PageDown => {
+                self.side_by_side_scroll = self.side_by_side_scroll.saturating_add(10);
+            }
+            Action::PageUp => {
+                self.side_by_side_scroll = self.side_by_side_scroll.saturating_sub(10);
+            }
+            Action::Top => {
+                self.side_by_side_scroll = 0;
+            }
+            // Drawing clamps the scroll to the last page
+            Action::Bottom => {
+                self.side_by_side_scroll = usize::MAX;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_api_action(&mut self, action: Action) {
+        let len = self.api_rows().len();
+        match action {
+            Action::Quit => {
+                self.running = false;
+            }
+            Action::Back if !self.api_query.is_empty() => {
+                self.api_query.clear();
+                self.select_api_row(0);
+            }
+            Action::Back => {
+                self.show_api = false;
+            }
+            Action::Down if self.api_index + 1 < len => {
+                self.select_api_row(self.api_index + 1);
+            }
+            Action::Up if self.api_index > 0 => {
+                self.select_api_row(self.api_index - 1);
+            }
+            Action::Top => self.select_api_row(0),
+            Action::Bottom => self.select_api_row(len.saturating_sub(1)),
+            Action::Expand => self.toggle_api_item(),
+            // A search result opens in its place in the tree
+            Action::Open if !self.api_query.is_empty() => self.reveal_api_item(),
+            Action::Open => self.toggle_api_item(),
+            Action::Search if self.api_docs.is_some() => {
+                self.api_search_mode = true;
+            }
+            Action::ScrollDown => {
+                self.api_scroll = self.api_scroll.saturating_add(1);
+            }
+            Action::ScrollUp => {
+                self.api_scroll = self.api_scroll.saturating_sub(1);
+            }
+            Action::PageDown => {
+                self.api_scroll = self.api_scroll.saturating_add(10);
+            }
+            Action::PageUp => {
+                self.api_scroll = self.api_scroll.saturating_sub(10);
+            }
+            _ => {}
+        }
+    }
+
+    // Typing narrows the items to those matching by name; Enter keeps the
+    // results to browse, Esc drops the search
+    fn handle_api_search(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                self.api_search_mode = false;
+                return;
+            }
+            KeyCode::Esc => {
+                self.api_search_mode = false;
+                self.api_query.clear();
+            }
+            KeyCode::Char(c) => {
+                self.api_query.push(c);
+            }
+            KeyCode::Backspace => {
+                self.api_query.pop();
+            }
+            KeyCode::Down => {
+                let last = self.api_rows().len().saturating_sub(1);
+                self.select_api_row((self.api_index + 1).min(last));
+                return;
+            }
+            KeyCode::Up => {
+                self.select_api_row(self.api_index.saturating_sub(1));
+                return;
+            }
+            _ => return,
+        }
+        self.select_api_row(0);
+    }
+
+    /// Rows of the API docs pane as (item, depth)
+    pub fn api_rows(&self) -> Vec<(usize, usize)> {
+        match self.api_docs {
+            Some(ref docs) => docs.rows(&self.api_expanded, &self.api_query),
+            None => Vec::new(),
+        }
+    }
+
+    /// The item on the selected row of the API docs pane
+    pub fn selected_api_item(&self) -> Option<usize> {
+        self.api_rows().get(self.api_index).map(|&(item, _)| item)
+    }
+
+    fn select_api_row(&mut self, row: usize) {
+        if row != self.api_index {
+            self.api_scroll = 0;
+        }
+        self.api_index = row;
+    }
+
+    fn toggle_api_item(&mut self) {
+        let Some(item) = self.selected_api_item() else {
+            return;
+        };
+        let has_children = self
+            .api_docs
+            .as_ref()
+            .is_some_and(|docs| !docs.items[item].children.is_empty());
+        if !self.api_expanded.remove(&item) && has_children {
+            self.api_expanded.insert(item);
+        }
+    }
+
+    // Leave the search with the selected result open in the tree
+    fn reveal_api_item(&mut self) {
+        let (Some(item), Some(docs)) = (self.selected_api_item(), self.api_docs.as_ref()) else {
+            return;
+        };
+        self.api_expanded.extend(docs.ancestors(item));
+        self.api_query.clear();
+        let row = self
+            .api_rows()
+            .iter()
+            .position(|&(i, _)| i == item)
+            .unwrap_or(0);
+        self.api_index = row;
+        self.api_scroll = 0;
+    }
+
+    fn handle_versions_action(&mut self, action: Action) {
+        let len = self.versions.len();
+        match action {
+            Action::Quit => {
+                self.running = false;
+            }
+            Action::Back => {
+                self.show_versions = false;
+            }
+            Action::Down if len > 0 => {
+                self.version_index = (self.version_index + 1) % len;
+            }
+            Action::Up if len > 0 => {
+                self.version_index = if self.version_index > 0 {
+                    self.version_index - 1
+                } else {
+                    len - 1
+                };
+            }
+            Action::Top => {
+                self.version_index = 0;
+            }
+            Action::Bottom => {
+                self.version_index = len.saturating_sub(1);
+            }
+            Action::Mark => {
+                self.toggle_diff_mark();
+            }
+            Action::Diff => {
+                self.diff_selected_versions();
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_diff_action(&mut self, action: Action) {
+        let file_count = self
+            .version_diff
+            .as_ref()
+            .map(|d| d.files.len())
+            .unwrap_or(0);
+
+        match action {
+            Action::Quit => {
+                self.running = false;
+            }
+            Action::Back => {
+                self.show_diff = false;
+            }
+            Action::Down if file_count > 0 => {
+                self.diff_file_index = (self.diff_file_index + 1) % file_count;
+                self.diff_scroll = 0;
+            }
+            Action::Up if file_count > 0 => {
+                self.diff_file_index = if self.diff_file_index > 0 {
+                    self.diff_file_index - 1
+                } else {
+                    file_count - 1
+                };
+                self.diff_scroll = 0;
+            }
+            Action::ScrollDown => {
+                self.diff_scroll = self.diff_scroll.saturating_add(1);
+            }
+            Action::ScrollUp => {
+                self.diff_scroll = self.diff_scroll.saturating_sub(1);
+            }
+            Action::PageDown => {
+                self.diff_scroll = self.diff_scroll.saturating_add(10);
+            }
+            Action::PageUp => {
+                self.diff_scroll = self.diff_scroll.saturating_sub(10);
+            }
+            _ => {}
+        }
+    }
+
+    // The URL behind a link of the crate or repository in the detail view
+    fn link_url(&self, link: Link) -> Option<String> {
+        if let Some(crate_data) = self.detail_crate() {
+            return match link {
+                Link::Docs => Some(api::docs_url(crate_data)),
+                Link::Repository => crate_data.repository.clone(),
+                Link::CratesIo => Some(api::crate_page_url(&crate_data.name)),
+                Link::Homepage => crate_data.homepage.clone(),
+            };
+        }
+
+        // Trending repositories only have their GitHub page
+        match link {
+            Link::Repository if self.current_tab == Tab::Trending => self
+                .selected_row()
+                .and_then(|row| self.repos.get(row))
+                .map(|repo| repo.html_url.clone()),
+            _ => None,
+        }
+    }
+
+    fn open_url(&mut self, url: &str) {
+        self.notice = Some(match browser::open(url) {
+            Ok(()) => format!("Opened {}", url),
+            Err(e) => e.to_string(),
+        });
+    }
+
+    fn copy_text(&mut self, text: &str) {
+        self.notice = Some(match clipboard::copy(text) {
+            Ok(()) => format!("Copied {}", text),
+            Err(e) => e.to_string(),
+        });
+    }
+
+    /// The crate whose detail view is currently open, if any
+    pub fn detail_crate(&self) -> Option<&Crate> {
+        match self.current_tab {
+            Tab::Search | Tab::Recent | Tab::Categories if self.showing_crates() => {
+                self.selected_row().and_then(|row| self.crates.get(row))
+            }
+            Tab::Compare => self
+                .selected_row()
+                .and_then(|row| self.compared_crates.get(row))
+                .map(|c| &c.details),
+            _ => None,
+        }
+    }
+
+    /// Accept the owners of the crate in the detail view, so a change of
+    /// owners is no longer reported, and redo its checks
+    fn acknowledge_owners(&mut self) {
+        let name = match self.detail_crate() {
+            Some(crate_data) => crate_data.name.clone(),
+            None => return,
+        };
+        if !self.store.acknowledge(&name) {
+            self.notice = Some(format!("No owners recorded for {} yet", name));
+            return;
+        }
+        if let Err(e) = self.store.save() {
+            self.notice = Some(format!("Could not save the acknowledged owners: {}", e));
+            return;
+        }
+
+        if self.current_tab == Tab::Compare {
+            if let Some(row) = self.selected_row() {
+                let details = self.compared_crates[row].details.clone();
+                let mut compared = self.compare_entry(details);
+                let previous = &self.compared_crates[row];
+                compared.marked = previous.marked;
+                compared.download_history = previous.download_history.clone();
+                self.compared_crates[row] = compared;
+            }
+        } else {
+            let (scroll, tag) = (self.detail_scroll, self.detail_tag_index);
+            self.open_detail();
+            self.detail_scroll = scroll;
+            self.detail_tag_index = tag;
+        }
+        self.notice = Some(format!("Accepted the current owners of {}", name));
+    }
+
+    fn open_version_history(&mut self) {
+        let name = match self.detail_crate() {
+            Some(crate_data) => crate_data.name.clone(),
+            None => return,
+        };
+
+        self.show_versions = true;
+        self.version_index = 0;
+        self.diff_marks.clear();
+        self.version_error = None;
+
+        match api::crate_versions(&name) {
+            Ok(versions) => self.versions = versions,
+            Err(e) => {
+                self.versions.clear();
+                self.version_error = Some(e.to_string());
+            }
+        }
+
+        // Each version costs docs.rs requests, so only the newest are
+        // checked up front, newest first like the list
+        self.version_docs_requested.clear();
+        let numbers: Vec<String> = self
+            .versions
+            .iter()
+            .take(DOCS_STATUS_VERSIONS)
+            .map(|v| v.num.clone())
+            .collect();
+        self.request_version_docs(&name, numbers);
+    }
+
+    // Fetch the docs.rs status of versions not already known or asked for
+    fn request_version_docs(&mut self, name: &str, versions: Vec<String>) {
+        let versions: Vec<String> = versions
+            .into_iter()
+            .filter(|version| {
+                let key = (name.to_string(), version.clone());
+                !self.version_docs.contains_key(&key) && self.version_docs_requested.insert(key)
+            })
+            .collect();
+        if !versions.is_empty() {
+            let receiver = api::spawn_docs_statuses(name.to_string(), versions);
+            self.version_docs_checks.push((name.to_string(), receiver));
+        }
+    }
+
+    /// The docs.rs status of a version in the history: None while unknown,
+    /// Some(None) if it could not be fetched
+    pub fn version_docs_status(&self, version: &str) -> Option<Option<&DocsStatus>> {
+        let name = self.detail_crate()?.name.clone();
+        self.version_docs
+            .get(&(name, version.to_string()))
+            .map(Option::as_ref)
+    }
+
+    /// Whether the docs.rs status of a version has been asked for but not
+    /// arrived yet
+    pub fn version_docs_pending(&self, version: &str) -> bool {
+        self.detail_crate().is_some_and(|c| {
+            let key = (c.name.clone(), version.to_string());
+            self.version_docs_requested.contains(&key) && !self.version_docs.contains_key(&key)
+        })
+    }
+
+    fn update_version_docs(&mut self) {
+        // Leaving the history stops the remaining requests
+        if !self.show_versions {
+            self.version_docs_checks.clear();
+            return;
+        }
+
+        let selected = self.versions.get(self.version_index).map(|v| v.num.clone());
+        if let (Some(version), Some(name)) = (selected, self.detail_crate().map(|c| c.name.clone()))
+        {
+            self.request_version_docs(&name, vec![version]);
+        }
+
+        let mut checks = std::mem::take(&mut self.version_docs_checks);
+        checks.retain(|(name, receiver)| loop {
+            match receiver.try_recv() {
+                Ok((version, status)) => {
+                    self.version_docs
+                        .insert((name.clone(), version), status.ok());
+                }
+                Err(TryRecvError::Empty) => break true,
+                Err(TryRecvError::Disconnected) => break false,
+            }
+        });
+        self.version_docs_checks = checks;
+    }
+
+    // Mark or unmark the selected compared crate for the side-by-side view
+    fn toggle_compare_mark(&mut self) {
+        let marked = self.compared_crates.iter().filter(|c| c.marked).count();
+        let Some(compared) = self
+            .selected_row()
+            .and_then(|row| self.compared_crates.get_mut(row))
+        else {
+            return;
+        };
+        if !compared.marked && marked >= MAX_SIDE_BY_SIDE {
+            self.notice = Some(format!("Only {} crates fit side by side", MAX_SIDE_BY_SIDE));
+            return;
+        }
+        compared.marked = !compared.marked;
+    }
+
+    // Show the marked crates side by side, or the whole comparison if it is
+    // small enough and nothing is marked
+    fn open_side_by_side(&mut self) {
+        let marked: Vec<usize> = (0..self.compared_crates.len())
+            .filter(|row| self.compared_crates[*row].marked)
+            .collect();
+        let rows = if marked.is_empty() && self.compared_crates.len() <= MAX_SIDE_BY_SIDE {
+            (0..self.compared_crates.len()).collect()
+        } else {
+            marked
+        };
+        if rows.len() < 2 {
+            self.notice = Some(format!(
+                "Mark two to {} crates with '{}' to view them side by side",
+                MAX_SIDE_BY_SIDE,
+                self.keymap.hint(Context::List, Action::Mark)
+            ));
+            return;
+        }
+
+        for row in &rows {
+            let compared = &mut self.compared_crates[*row];
+            if compared.download_history.is_none() {
+                compared.download_history = api::download_history(&compared.details.name).ok();
+            }
+        }
+        self.side_by_side = rows
+            .iter()
+            .map(|row| self.compared_crates[*row].details.name.clone())
+            .collect();
+        self.side_by_side_scroll = 0;
+        self.show_side_by_side = true;
+    }
+
+    fn open_readme(&mut self) {
+        let (name, version) = match self.detail_crate() {
+            Some(crate_data) => (crate_data.name.clone(), crate_data.max_version.clone()),
+            None => return,
+        };
+
+        self.show_readme = true;
+        self.readme_scroll = 0;
+        self.readme_error = None;
+        if self
+            .readme
+            .as_ref()
+            .is_some_and(|r| r.name == name && r.version == version)
+        {
+            return;
+        }
+
+        match readme::fetch(&name, &version) {
+            Ok(readme) => self.readme = Some(readme),
+            Err(e) => {
+                self.readme = None;
+                self.readme_error = Some(e.to_string());
+            }
+        }
+    }
+
+    fn open_api_docs(&mut self) {
+        let (name, version) = match self.detail_crate() {
+            Some(crate_data) => (crate_data.name.clone(), crate_data.max_version.clone()),
+            None => return,
+        };
+
+        self.show_api = true;
+        self.api_search_mode = false;
+        let loaded = self
+            .api_docs
+            .as_ref()
+            .is_some_and(|d| d.name == name && d.version == version);
+        let pending = self
+            .api_load
+            .as_ref()
+            .is_some_and(|(n, v, _)| *n == name && *v == version);
+        if loaded || pending {
+            return;
+        }
+
+        // Downloading the docs can take a while, so it runs in the background.
+        // A load still running for another crate is dropped.
+        self.api_docs = None;
+        self.api_error = None;
+        self.api_build_prompt = false;
+        self.api_index = 0;
+        self.api_expanded.clear();
+        self.api_query.clear();
+        self.api_scroll = 0;
+        self.api_building = false;
+        let receiver = rustdoc::spawn_load(name.clone(), version.clone());
+        self.api_load = Some((name, version, receiver));
+    }
+
+    // Building runs code from the crate, so it takes an explicit yes
+    fn handle_api_build_prompt(&mut self, key: KeyEvent) {
         match key.code {
-            KeyCode::Esc | KeyCode::Char('q') => {
-                self.show_detail = false;
+            KeyCode::Char('y') => {
+                self.api_build_prompt = false;
+                let Some(crate_data) = self.detail_crate() else {
+                    return;
+                };
+                let (name, version) = (crate_data.name.clone(), crate_data.max_version.clone());
+                self.api_error = None;
+                self.api_building = true;
+                let receiver = rustdoc::spawn_build(name.clone(), version.clone());
+                self.api_load = Some((name, version, receiver));
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                self.detail_scroll = self.detail_scroll.saturating_add(1);
+            KeyCode::Char('n') | KeyCode::Esc => self.api_build_prompt = false,
+            _ if self.is_quit_key(key) => self.running = false,
+            _ => {}
+        }
+    }
+
+    fn update_api_docs(&mut self) {
+        let Some((ref name, ref version, ref receiver)) = self.api_load else {
+            return;
+        };
+        // Only the crate whose API pane is open gets its result shown
+        let current = self
+            .detail_crate()
+            .is_some_and(|c| c.name == *name && c.max_version == *version);
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err("Loading the API docs stopped".to_string()),
+        };
+
+        self.api_load = None;
+        if !current {
+            return;
+        }
+        match result {
+            Ok(docs) => self.api_docs = Some(docs),
+            Err(e) => {
+                self.api_build_prompt = !self.api_building && config::get().api_docs.local_build;
+                self.api_error = Some(e);
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                self.detail_scroll = self.detail_scroll.saturating_sub(1);
+        }
+    }
+
+    fn toggle_diff_mark(&mut self) {
+        if self.version_index >= self.versions.len() {
+            return;
+        }
+
+        if let Some(pos) = self
+            .diff_marks
+            .iter()
+            .position(|&i| i == self.version_index)
+        {
+            self.diff_marks.remove(pos);
+        } else {
+            // Only two versions can be compared at once, so drop the oldest mark
+            if self.diff_marks.len() == 2 {
+                self.diff_marks.remove(0);
             }
-            KeyCode::PageDown => {
-                self.detail_scroll = self.detail_scroll.saturating_add(10);
+            self.diff_marks.push(self.version_index);
+        }
+    }
+
+    // Diff the two marked versions, the marked version against the selected one,
+    // or the selected version against the release before it
+    fn diff_selected_versions(&mut self) {
+        let (a, b) = match self.diff_marks.as_slice() {
+            [a, b] => (*a, *b),
+            [a] if *a != self.version_index => (*a, self.version_index),
+            _ => (self.version_index, self.version_index + 1),
+        };
+
+        if a >= self.versions.len() || b >= self.versions.len() {
+            self.version_error = Some("Select two versions to diff".to_string());
+            return;
+        }
+
+        // Versions are listed newest first, so the larger index is the older release
+        let (old, new) = if a > b { (a, b) } else { (b, a) };
+        let name = match self.detail_crate() {
+            Some(crate_data) => crate_data.name.clone(),
+            None => return,
+        };
+
+        match diff::diff_versions(&name, &self.versions[old].num, &self.versions[new].num) {
+            Ok(version_diff) => {
+                self.version_diff = Some(version_diff);
+                self.diff_file_index = 0;
+                self.diff_scroll = 0;
+                self.show_diff = true;
+                self.version_error = None;
             }
-            KeyCode::PageUp => {
-                self.detail_scroll = self.detail_scroll.saturating_sub(10);
+            Err(e) => {
+                self.version_error = Some(e.to_string());
             }
-            _ => {}
         }
     }
 
     fn handle_input_mode(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Enter => {
-                self.input_mode = false;
+                if self.suggestion_index.is_some() {
+                    self.accept_suggestion();
+                }
+                // A submitted search replaces whatever live search is in flight
+                self.stop_live_search();
                 if !self.search_query.is_empty() {
                     self.search_crates();
                     self.selected_index = 0; // Reset selection to the top result
                 }
+                // Stay in the input so a syntax error can be fixed in place
+                self.input_mode = self.query_error.is_some();
             }
             KeyCode::Esc => {
                 self.input_mode = false;
+                self.query_error = None;
+                self.stop_live_search();
             }
             KeyCode::Char(c) => {
                 self.search_query.push(c);
+                self.search_edited();
             }
             KeyCode::Backspace => {
                 self.search_query.pop();
+                self.search_edited();
+            }
+            KeyCode::Down if !self.suggestions.is_empty() => {
+                self.suggestion_index = Some(match self.suggestion_index {
+                    Some(i) => (i + 1) % self.suggestions.len(),
+                    None => 0,
+                });
+            }
+            KeyCode::Up if !self.suggestions.is_empty() => {
+                self.suggestion_index = Some(match self.suggestion_index {
+                    Some(i) if i > 0 => i - 1,
+                    _ => self.suggestions.len() - 1,
+                });
             }
-            KeyCode::Tab => {
-                // Auto-complete functionality could be added here
+            KeyCode::Tab if !self.suggestions.is_empty() => {
+                self.accept_suggestion();
             }
             _ => {}
         }
     }
 
+    /// Name of the selected crate in the Watchlist tab
+    pub fn selected_watched(&self) -> Option<String> {
+        self.watchlist
+            .crates
+            .keys()
+            .nth(self.selected_index)
+            .cloned()
+    }
+
+    fn toggle_watch(&mut self, name: &str) {
+        let watch = !self.watchlist.contains(name);
+        // Record a baseline right away so later changes can be reported
+        let baseline = watch.then(|| watch::snapshot(name).map_err(|e| e.to_string()));
+        // Saving is best effort, like the ownership store
+        let _ = self.watchlist.update(|watchlist| {
+            if watchlist.contains(name) != watch {
+                watchlist.toggle(name);
+            }
+            if let Some(result) = baseline {
+                watchlist.apply((name.to_string(), result));
+            }
+        });
+
+        if self.current_tab == Tab::Watchlist {
+            let len = self.watchlist.crates.len();
+            self.selected_index = self.selected_index.min(len.saturating_sub(1));
+        }
+    }
+
+    fn start_watch_check(&mut self) {
+        if self.watch_check.is_some() || self.watchlist.crates.is_empty() {
+            return;
+        }
+        let names = self.watchlist.crates.keys().cloned().collect();
+        self.watch_check = Some(watch::spawn_check(names));
+        self.last_watch_check = Some(Instant::now());
+    }
+
+    // Check watched crates on startup and every WATCH_INTERVAL, applying
+    // results as the background check reports them
+    fn update_watchlist(&mut self) {
+        let due = self
+            .last_watch_check
+            .is_none_or(|checked| checked.elapsed() >= WATCH_INTERVAL);
+        if due {
+            self.start_watch_check();
+        }
+
+        let Some(ref receiver) = self.watch_check else {
+            return;
+        };
+        let mut results = Vec::new();
+        let finished = loop {
+            match receiver.try_recv() {
+                Ok(result) => results.push(result),
+                Err(TryRecvError::Empty) => break false,
+                Err(TryRecvError::Disconnected) => break true,
+            }
+        };
+
+        if !results.is_empty() {
+            let _ = self.watchlist.update(|watchlist| {
+                for result in results {
+                    watchlist.apply(result);
+                }
+            });
+        }
+        if finished {
+            self.watch_check = None;
+        }
+    }
+
+    fn search_edited(&mut self) {
+        self.query_error = None;
+        self.suggestion_index = None;
+        self.last_edit = Some(Instant::now());
+    }
+
+    // Replace the word being typed with the highlighted (or first) suggestion
+    fn accept_suggestion(&mut self) {
+        let index = self.suggestion_index.unwrap_or(0);
+        let Some(name) = self.suggestions.get(index).cloned() else {
+            return;
+        };
+
+        let start = self
+            .search_query
+            .rfind(char::is_whitespace)
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        self.search_query.truncate(start);
+        self.search_query.push_str(&name);
+
+        self.suggestions.clear();
+        self.suggestion_index = None;
+        self.last_edit = Some(Instant::now());
+    }
+
+    // Forget the search being typed: a late response must not replace what
+    // the user has moved on to
+    fn stop_live_search(&mut self) {
+        self.live.cancel();
+        self.last_edit = None;
+        self.suggestions.clear();
+        self.suggestion_index = None;
+    }
+
+    // Start a live search once typing pauses, and apply responses as they arrive
+    fn update_live_search(&mut self) {
+        if let Some(edited) = self.last_edit {
+            if edited.elapsed() >= SEARCH_DEBOUNCE {
+                self.last_edit = None;
+                self.start_live_search();
+            }
+        }
+
+        while let Some(response) = self.live.poll() {
+            match response {
+                Response::Results {
+                    query, sort, page, ..
+                } if self.showing_live_results(&query) => match page {
+                    Ok(mut page) => {
+                        let listing = Listing::Search { query, sort };
+                        self.clear_fuzzy();
+                        self.filter_crates(&listing, &mut page.crates);
+                        self.crates = page.crates;
+                        self.total_crates = page.total;
+                        self.page = 1;
+                        self.listing = listing;
+                        self.selected_index = 0;
+                        self.loading_state = LoadingState::Loaded;
+
+                        // Strict filters can reject a whole page; keep looking
+                        if self.crates.is_empty() {
+                            self.load_more_crates();
+                        }
+                    }
+                    Err(e) => {
+                        self.loading_state = LoadingState::Error(e);
+                    }
+                },
+                Response::Results { .. } => {}
+                Response::Suggestions { names, .. } => {
+                    // Only offer completions while the user is still typing
+                    if self.input_mode {
+                        self.suggestions = names;
+                        self.suggestion_index = None;
+                    }
+                }
+            }
+        }
+    }
+
+    // Results only belong on screen if the search list is showing and the
+    // query is still the one typed
+    fn showing_live_results(&self, query: &Query) -> bool {
+        self.current_tab == Tab::Search
+            && !self.show_detail
+            && Query::parse(&self.search_query).is_ok_and(|current| current == *query)
+    }
+
+    fn start_live_search(&mut self) {
+        if !self.input_mode || self.current_tab != Tab::Search {
+            return;
+        }
+
+        // Complete the word under the cursor unless it's a qualifier
+        let prefix = if self.search_query.ends_with(char::is_whitespace) {
+            None
+        } else {
+            self.search_query
+                .split_whitespace()
+                .last()
+                .filter(|word| !word.contains(':') && word.chars().count() >= MIN_SUGGEST_PREFIX)
+                .map(str::to_string)
+        };
+        if prefix.is_none() {
+            self.suggestions.clear();
+        }
+
+        let query = if self.search_query.trim().is_empty() {
+            None
+        } else {
+            match Query::parse(&self.search_query) {
+                Ok(query) => Some((query, self.search_sort)),
+                Err(e) => {
+                    self.query_error = Some(e.to_string());
+                    None
+                }
+            }
+        };
+
+        if query.is_some() || prefix.is_some() {
+            self.live.request(query, prefix);
+        }
+    }
+
     fn next_tab(&mut self) {
+        self.stop_live_search();
         self.current_tab = match self.current_tab {
             Tab::Search => Tab::Recent,
             Tab::Recent => Tab::Trending,
             Tab::Trending => Tab::Compare,
-            Tab::Compare => Tab::Help,
+            Tab::Compare => Tab::Categories,
+            Tab::Categories => Tab::Watchlist,
+            Tab::Watchlist => Tab::Help,
             Tab::Help => Tab::Search,
         };
         self.selected_index = 0;
         self.show_detail = false;
+        self.clear_fuzzy();
 
         // Just set loading state but don't actually load
         match self.current_tab {
-            Tab::Recent => {
-                if self.crates.is_empty() {
-                    self.loading_state = LoadingState::Loading;
-                }
+            Tab::Recent if self.crates.is_empty() => {
+                self.loading_state = LoadingState::Loading;
+            }
+            Tab::Trending if self.repos.is_empty() => {
+                self.loading_state = LoadingState::Loading;
             }
-            Tab::Trending => {
-                if self.repos.is_empty() {
+            // Other tabs may have replaced the shared crate list meanwhile
+            Tab::Categories => {
+                self.browse_listing = false;
+                if self.categories.is_empty() {
                     self.loading_state = LoadingState::Loading;
                 }
             }
@@ -354,25 +2494,32 @@ impl App {
     }
 
     fn prev_tab(&mut self) {
+        self.stop_live_search();
         self.current_tab = match self.current_tab {
             Tab::Search => Tab::Help,
             Tab::Recent => Tab::Search,
             Tab::Trending => Tab::Recent,
             Tab::Compare => Tab::Trending,
-            Tab::Help => Tab::Compare,
+            Tab::Categories => Tab::Compare,
+            Tab::Watchlist => Tab::Categories,
+            Tab::Help => Tab::Watchlist,
         };
         self.selected_index = 0;
         self.show_detail = false;
+        self.clear_fuzzy();
 
         // Just set loading state but don't actually load
         match self.current_tab {
-            Tab::Recent => {
-                if self.crates.is_empty() {
-                    self.loading_state = LoadingState::Loading;
-                }
+            Tab::Recent if self.crates.is_empty() => {
+                self.loading_state = LoadingState::Loading;
+            }
+            Tab::Trending if self.repos.is_empty() => {
+                self.loading_state = LoadingState::Loading;
             }
-            Tab::Trending => {
-                if self.repos.is_empty() {
+            // Other tabs may have replaced the shared crate list meanwhile
+            Tab::Categories => {
+                self.browse_listing = false;
+                if self.categories.is_empty() {
                     self.loading_state = LoadingState::Loading;
                 }
             }
@@ -381,12 +2528,21 @@ impl App {
     }
 
     fn next_item(&mut self) {
-        let max = match self.current_tab {
-            Tab::Recent | Tab::Search => self.crates.len(),
-            Tab::Trending => self.repos.len(),
-            Tab::Compare => self.compared_crates.len(),
-            Tab::Help => 0,
-        };
+        // Infinite scroll: fetch the next page before reaching the last row.
+        // The quick filter only narrows what's loaded, so it never fetches.
+        if self.showing_crates()
+            && self.fuzzy_query.is_empty()
+            && self.selected_index + LOAD_MORE_THRESHOLD >= self.crates.len()
+        {
+            self.load_more_crates();
+        }
+
+        if self.current_tab == Tab::Categories && !self.browse_listing {
+            self.move_browse_selection(true);
+            return;
+        }
+
+        let max = self.visible_rows().len();
 
         if max > 0 {
             self.selected_index = (self.selected_index + 1) % max;
@@ -394,12 +2550,12 @@ impl App {
     }
 
     fn prev_item(&mut self) {
-        let max = match self.current_tab {
-            Tab::Recent | Tab::Search => self.crates.len(),
-            Tab::Trending => self.repos.len(),
-            Tab::Compare => self.compared_crates.len(),
-            Tab::Help => 0,
-        };
+        if self.current_tab == Tab::Categories && !self.browse_listing {
+            self.move_browse_selection(false);
+            return;
+        }
+
+        let max = self.visible_rows().len();
 
         if max > 0 {
             self.selected_index = if self.selected_index > 0 {
@@ -410,15 +2566,146 @@ impl App {
         }
     }
 
+    // Move within whichever pane of the Categories tab has focus
+    fn move_browse_selection(&mut self, forward: bool) {
+        let (index, len) = match self.browse_pane {
+            BrowsePane::Categories => (&mut self.category_index, self.categories.len()),
+            BrowsePane::Keywords => (&mut self.keyword_index, self.keywords.len()),
+        };
+
+        if len > 0 {
+            *index = if forward {
+                (*index + 1) % len
+            } else if *index > 0 {
+                *index - 1
+            } else {
+                len - 1
+            };
+        }
+    }
+
+    fn load_browse(&mut self) {
+        self.loading_state = LoadingState::Loading;
+
+        match api::categories() {
+            Ok(categories) => {
+                self.categories = categories
+                    .into_iter()
+                    .map(|category| CategoryNode {
+                        category,
+                        depth: 0,
+                        expanded: false,
+                    })
+                    .collect();
+                self.category_index = 0;
+                // The keyword list is a nice-to-have next to the tree
+                self.keywords = api::popular_keywords(POPULAR_KEYWORDS).unwrap_or_default();
+                self.keyword_index = 0;
+                self.loading_state = LoadingState::Loaded;
+            }
+            Err(e) => {
+                self.loading_state = LoadingState::Error(e.to_string());
+            }
+        }
+    }
+
+    // Expand or collapse the selected category, fetching its children the first time
+    fn toggle_category(&mut self) {
+        let index = self.category_index;
+        let Some(node) = self.categories.get(index) else {
+            return;
+        };
+        let depth = node.depth;
+
+        if node.expanded {
+            // Drop every descendant row below this one
+            let end = self.categories[index + 1..]
+                .iter()
+                .position(|n| n.depth <= depth)
+                .map(|p| index + 1 + p)
+                .unwrap_or(self.categories.len());
+            self.categories.drain(index + 1..end);
+            self.categories[index].expanded = false;
+            return;
+        }
+
+        let children = match api::subcategories(&node.category.slug) {
+            Ok(children) => children,
+            Err(e) => {
+                self.loading_state = LoadingState::Error(e.to_string());
+                return;
+            }
+        };
+
+        let rows = children.into_iter().map(|category| CategoryNode {
+            category,
+            depth: depth + 1,
+            expanded: false,
+        });
+        self.categories.splice(index + 1..index + 1, rows);
+        self.categories[index].expanded = true;
+    }
+
+    // Show the top crates for the selected category or keyword
+    fn browse_selected(&mut self) {
+        let listing = match self.browse_pane {
+            BrowsePane::Categories => self
+                .categories
+                .get(self.category_index)
+                .map(|n| Listing::Category(n.category.slug.clone())),
+            BrowsePane::Keywords => self
+                .keywords
+                .get(self.keyword_index)
+                .map(|k| Listing::Keyword(k.keyword.clone())),
+        };
+
+        if let Some(listing) = listing {
+            self.open_browse_listing(listing);
+        }
+    }
+
+    // Jump from a tag in the detail view to the crates sharing it
+    fn browse_tag(&mut self, tag: Tag) {
+        let listing = match tag {
+            Tag::Keyword(keyword) => Listing::Keyword(keyword),
+            Tag::Category(slug) => Listing::Category(slug),
+        };
+
+        self.stop_live_search();
+        self.current_tab = Tab::Categories;
+        self.show_detail = false;
+        self.open_browse_listing(listing);
+    }
+
+    fn open_browse_listing(&mut self, listing: Listing) {
+        self.load_listing(listing);
+        self.browse_listing = true;
+        self.selected_index = 0;
+    }
+
     fn load_recent_crates(&mut self) {
+        self.load_listing(Listing::Recent);
+    }
+
+    // Load the first page of a listing, replacing the current crates
+    fn load_listing(&mut self, listing: Listing) {
         self.loading_state = LoadingState::Loading;
+        self.clear_fuzzy();
 
         // Fetch data
-        let app_result = api::recent_crates(20);
-        match app_result {
-            Ok(crates) => {
-                self.crates = crates;
+        match listing.fetch(1) {
+            Ok(mut page) => {
+                self.filter_crates(&listing, &mut page.crates);
+                self.crates = page.crates;
+                self.total_crates = page.total;
+                self.page = 1;
+                self.listing = listing;
                 self.loading_state = LoadingState::Loaded;
+
+                // Strict filters can reject a whole page; keep looking
+                if self.crates.is_empty() {
+                    self.load_more_crates();
+                }
             }
             Err(e) => {
                 self.loading_state = LoadingState::Error(e.to_string());
@@ -426,11 +2713,54 @@ impl App {
         }
     }
 
+    // Append the next page of the current listing, if there is one. With
+    // search filters active, several pages may be fetched before a match.
+    fn load_more_crates(&mut self) {
+        for _ in 0..MAX_FILTERED_PAGES {
+            if !self.has_more_pages() {
+                return;
+            }
+
+            // Errors are ignored so the loaded rows stay visible; moving the
+            // selection again retries the request
+            let mut page = match self.listing.fetch(self.page + 1) {
+                Ok(page) => page,
+                Err(_) => return,
+            };
+
+            if page.crates.is_empty() {
+                // The listing shrank since the first page was fetched
+                self.total_crates = (self.page * config::get().general.page_size) as u64;
+                return;
+            }
+
+            self.page += 1;
+            self.filter_crates(&self.listing, &mut page.crates);
+            if !page.crates.is_empty() {
+                self.crates.extend(page.crates);
+                return;
+            }
+        }
+    }
+
+    fn has_more_pages(&self) -> bool {
+        ((self.page * config::get().general.page_size) as u64) < self.total_crates
+    }
+
+    // Drop search results that don't pass the filters
+    fn filter_crates(&self, listing: &Listing, crates: &mut Vec<Crate>) {
+        if let Listing::Search { query, .. } = listing {
+            crates.retain(|c| {
+                self.search_filters.matches(c) && query.predicates.iter().all(|p| p.matches(c))
+            });
+        }
+    }
+
     fn load_trending_repos(&mut self) {
         self.loading_state = LoadingState::Loading;
 
         // Fetch data
-        let app_result = api::trending_repos(&self.trend_period, 20);
+        let app_result = api::trending_repos(&self.trend_period, config::get().general.page_size);
         match app_result {
             Ok(repos) => {
                 self.repos = repos;
@@ -447,29 +2777,360 @@ impl App {
             return;
         }
 
-        self.loading_state = LoadingState::Loading;
-
-        match api::search_crates(&self.search_query, 20) {
-            Ok(crates) => {
-                self.crates = crates;
-                self.loading_state = LoadingState::Loaded;
+        match Query::parse(&self.search_query) {
+            Ok(query) => {
+                self.query_error = None;
+                self.load_listing(Listing::Search {
+                    query,
+                    sort: self.search_sort,
+                });
             }
             Err(e) => {
-                self.loading_state = LoadingState::Error(e.to_string());
+                self.query_error = Some(e.to_string());
+                self.loading_state = LoadingState::NotLoading;
             }
         }
     }
+
     pub fn search_crates_silently(&mut self, query: &str) {
-        self.loading_state = LoadingState::Loading;
+        if let Ok(query) = Query::parse(query) {
+            self.load_listing(Listing::Search {
+                query,
+                sort: self.search_sort,
+            });
+        }
+    }
 
-        match api::search_crates(query, 20) {
-            Ok(crates) => {
-                self.crates = crates;
-                self.loading_state = LoadingState::Loaded;
+    /// Whether results of the current listing are being narrowed down locally
+    pub fn listing_filtered(&self) -> bool {
+        match self.listing {
+            Listing::Search { ref query, .. } => {
+                self.search_filters.is_active() || !query.predicates.is_empty()
             }
-            Err(e) => {
-                self.loading_state = LoadingState::Error(e.to_string());
+            _ => false,
+        }
+    }
+}
+
+// Keywords first, then categories, as they appear in the detail view
+fn crate_tags(crate_data: &Crate) -> Vec<Tag> {
+    let keywords = crate_data
+        .keywords
+        .iter()
+        .flatten()
+        .cloned()
+        .map(Tag::Keyword);
+    let categories = crate_data
+        .categories
+        .iter()
+        .flatten()
+        .cloned()
+        .map(Tag::Category);
+    keywords.chain(categories).collect()
+}
+
+/// A crate of a comparison loaded in the background, with its record from
+/// the loader's copy of the store
+pub type CompareResult = (String, Result<(ComparedCrate, Option<CrateRecord>), String>);
+
+/// Load compared crates on a background thread, one at a time. The thread
+/// records ownership in its own copy of the store; the UI thread keeps the
+/// records it sends back.
+pub fn spawn_compare(
+    names: Vec<String>,
+    mut store: Store,
+    policy: Policy,
+) -> Receiver<CompareResult> {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        for name in names {
+            let result = api::get_crate_details(&name)
+                .map(|details| {
+                    let compared = compare_entry(&mut store, &policy, details);
+                    let record = store.crates.get(&compared.details.name).cloned();
+                    (compared, record)
+                })
+                .map_err(|e| e.to_string());
+            if sender.send((name, result)).is_err() {
+                break;
             }
         }
+    });
+
+    receiver
+}
+
+/// Security findings and a scorecard for a crate being compared
+pub fn compare_entry(store: &mut Store, policy: &Policy, details: Crate) -> ComparedCrate {
+    let mut security_warnings = api::security_check(&details, policy);
+    let ownership = inspect_ownership(store, policy, &details.name);
+    if let Some(ref ownership) = ownership {
+        security_warnings.extend(ownership.findings.iter().cloned());
+    }
+    let docs = api::docs_status(&details.name, &details.max_version).ok();
+    security_warnings.extend(api::docs_check(&details.name, docs.as_ref(), policy));
+    let rust = ownership
+        .as_ref()
+        .and_then(|o| msrv::support(&o.versions, &details.max_version));
+    security_warnings.extend(msrv::check(&details.name, rust.as_ref(), policy));
+
+    // The scorecard fetches these too, so they come from the cache
+    let reverse_deps = api::reverse_dependency_count(&details.name).ok();
+    let repo = details
+        .repository
+        .as_deref()
+        .and_then(|repo| api::repository_info(repo).ok());
+    let advisories = api::advisories(&details.name, &details.max_version).ok();
+    let dependencies = api::dependencies(&details.name, &details.max_version).ok();
+    let last_release = ownership
+        .as_ref()
+        .and_then(|o| api::latest_release(&o.versions))
+        .map(|v| v.created_at.clone());
+    let features = ownership.as_ref().and_then(|o| {
+        o.versions
+            .iter()
+            .find(|v| v.num == details.max_version)
+            .map(|v| v.features.clone())
+    });
+
+    let score = score::scorecard(
+        &details,
+        &security_warnings,
+        ownership.as_ref().map(|o| o.owners.as_slice()),
+        ownership
+            .as_ref()
+            .map(|o| o.versions.as_slice())
+            .unwrap_or(&[]),
+    );
+
+    ComparedCrate {
+        details,
+        security: SecurityInfo {
+            safe: security_warnings.is_empty(),
+            warnings: security_warnings,
+        },
+        score,
+        docs,
+        rust,
+        reverse_deps,
+        repo,
+        dependencies,
+        advisories,
+        last_release,
+        features,
+        download_history: None,
+        owners_changed: ownership.as_ref().is_some_and(|o| o.owners_changed),
+        marked: false,
+    }
+}
+
+/// Fetch owners and per-version publishers, compare the owners with the ones
+/// the user last acknowledged and record the new state. Saving the store is
+/// up to the caller.
+pub fn inspect_ownership(store: &mut Store, policy: &Policy, name: &str) -> Option<OwnershipInfo> {
+    let owners = api::crate_owners(name).ok()?;
+    let versions = api::crate_versions(name).unwrap_or_default();
+
+    let acknowledged = store.record(name, &owners, &versions).baseline().to_vec();
+
+    let findings = api::ownership_check(name, &owners, &versions, Some(&acknowledged), policy);
+    let owners_changed = api::owners_changed(&owners, &acknowledged);
+
+    Some(OwnershipInfo {
+        latest_publisher: api::latest_release(&versions).and_then(|v| v.published_by.clone()),
+        owners,
+        versions,
+        owners_changed,
+        findings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::CratesPage;
+
+    fn search_result(name: &str) -> Crate {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "description": null,
+            "downloads": 1,
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "documentation": null,
+            "repository": null,
+            "homepage": null,
+            "max_version": "1.0.0",
+            "license": null,
+            "keywords": null,
+            "categories": null,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn late_live_result_after_esc_is_dropped() {
+        let mut app = App::blank(Policy::default());
+        let (live, responses) = LiveSearch::detached();
+        app.live = live;
+
+        app.input_mode = true;
+        for c in "serde".chars() {
+            app.handle_key_event(KeyEvent::from(KeyCode::Char(c)));
+        }
+        // Typing pauses and the live search starts
+        app.last_edit = Some(Instant::now() - SEARCH_DEBOUNCE);
+        app.update_live_search();
+        assert!(app.live.pending);
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Esc));
+        assert!(!app.live.pending);
+        assert!(app.last_edit.is_none());
+
+        // The response to the request above, the first one made
+        responses
+            .send(Response::Results {
+                generation: 1,
+                query: Query::parse("serde").unwrap(),
+                sort: app.search_sort,
+                page: Ok(CratesPage {
+                    crates: vec![search_result("serde")],
+                    total: 1,
+                }),
+            })
+            .unwrap();
+        app.update_live_search();
+        assert!(app.crates.is_empty());
+    }
+
+    #[test]
+    fn live_result_for_another_query_is_dropped() {
+        let mut app = App::blank(Policy::default());
+        let (live, responses) = LiveSearch::detached();
+        app.live = live;
+
+        app.input_mode = true;
+        app.search_query = "tokio".to_string();
+        app.last_edit = Some(Instant::now() - SEARCH_DEBOUNCE);
+        app.update_live_search();
+
+        // Still the current request, but the input has changed since
+        app.search_query = "tokio-util".to_string();
+        responses
+            .send(Response::Results {
+                generation: 1,
+                query: Query::parse("tokio").unwrap(),
+                sort: app.search_sort,
+                page: Ok(CratesPage {
+                    crates: vec![search_result("tokio")],
+                    total: 1,
+                }),
+            })
+            .unwrap();
+        app.update_live_search();
+        assert!(app.crates.is_empty());
+
+        app.search_query = "tokio".to_string();
+        responses
+            .send(Response::Results {
+                generation: 1,
+                query: Query::parse("tokio").unwrap(),
+                sort: app.search_sort,
+                page: Ok(CratesPage {
+                    crates: vec![search_result("tokio")],
+                    total: 1,
+                }),
+            })
+            .unwrap();
+        app.update_live_search();
+        assert_eq!(app.crates.len(), 1);
+    }
+
+    #[test]
+    fn api_docs_for_another_crate_are_dropped() {
+        let mut app = App::blank(Policy::default());
+        app.crates = vec![search_result("tokio")];
+        app.show_detail = true;
+        app.show_api = true;
+
+        // A load started for serde is still running when tokio's pane opens
+        let (sender, receiver) = mpsc::channel();
+        app.api_load = Some(("serde".to_string(), "1.0.0".to_string(), receiver));
+        sender
+            .send(Ok(ApiDocs {
+                name: "serde".to_string(),
+                version: "1.0.0".to_string(),
+                items: Vec::new(),
+            }))
+            .unwrap();
+        app.update_api_docs();
+        assert!(app.api_load.is_none());
+        assert!(app.api_docs.is_none());
+
+        let (sender, receiver) = mpsc::channel();
+        app.api_load = Some(("tokio".to_string(), "1.0.0".to_string(), receiver));
+        sender
+            .send(Ok(ApiDocs {
+                name: "tokio".to_string(),
+                version: "1.0.0".to_string(),
+                items: Vec::new(),
+            }))
+            .unwrap();
+        app.update_api_docs();
+        assert_eq!(app.api_docs.map(|d| d.name).as_deref(), Some("tokio"));
+    }
+
+    #[test]
+    fn a_key_that_breaks_a_sequence_is_tried_on_its_own() {
+        let mut app = App::blank(Policy::default());
+        let key = |c| KeyEvent::from(KeyCode::Char(c));
+
+        assert_eq!(app.key_action(Context::List, key('g')), None);
+        assert_eq!(app.key_action(Context::List, key('g')), Some(Action::Top));
+        assert_eq!(app.key_action(Context::List, key('g')), None);
+        assert_eq!(app.key_action(Context::List, key('j')), Some(Action::Down));
+        assert!(app.pending_keys.is_empty());
+
+        // A key bound to nothing is dropped along with the sequence
+        app.key_action(Context::List, key('g'));
+        assert_eq!(app.key_action(Context::List, key('z')), None);
+        assert!(app.pending_keys.is_empty());
+    }
+
+    #[test]
+    fn column_menu_keys_follow_the_keymap() {
+        let mut app = App::blank(Policy::default());
+        let keys = [("move-down".to_string(), vec!["n".to_string()])];
+        app.keymap = Keymap::new(&keys.into_iter().collect()).unwrap();
+        app.columns_mode = true;
+        let first = app.compare_columns[0];
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('J')));
+        assert_eq!(app.compare_columns[0], first);
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('n')));
+        assert_eq!(app.compare_columns[1], first);
+        assert_eq!(app.column_index, 1);
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Esc));
+        assert!(!app.columns_mode);
+    }
+
+    #[test]
+    fn saved_sets_keys_follow_the_keymap() {
+        let mut app = App::blank(Policy::default());
+        let keys = [("rename".to_string(), vec!["n".to_string()])];
+        app.keymap = Keymap::new(&keys.into_iter().collect()).unwrap();
+        app.comparison_sets.sets = [("a", vec![]), ("b", vec![])]
+            .into_iter()
+            .map(|(name, crates)| (name.to_string(), crates))
+            .collect();
+        app.sets_mode = true;
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('j')));
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('r')));
+        assert!(app.set_prompt.is_none());
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('n')));
+        assert!(app.set_prompt == Some(SetPrompt::Rename("b".to_string())));
     }
 }
@@ -0,0 +1,182 @@
+use crate::app::AppResult;
+use crate::config;
+use crate::mouse::HitMap;
+use crate::text;
+use crossterm::cursor::MoveTo;
+use crossterm::style::{
+    Attribute, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor,
+};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Modifier;
+use reqwest::Url;
+use std::env;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+/// The pages of a crate that can be opened or copied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Link {
+    Docs,
+    Repository,
+    CratesIo,
+    Homepage,
+}
+
+impl Link {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Link::Docs => "documentation",
+            Link::Repository => "repository",
+            Link::CratesIo => "crates.io page",
+            Link::Homepage => "homepage",
+        }
+    }
+}
+
+/// Open a URL with the configured opener, or the system's default handler,
+/// without waiting for it. Only web pages are opened: the links come from
+/// crate authors, and other schemes can run programs or open local files.
+pub fn open(url: &str) -> AppResult<()> {
+    let url = web_url(url)?;
+    let mut command = match config::get().system.opener {
+        Some(ref opener) => command_line(opener, url.as_str())?,
+        None => default_opener(url.as_str()),
+    };
+
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Could not open {}: {}", url, e))?;
+    Ok(())
+}
+
+fn web_url(url: &str) -> AppResult<Url> {
+    let parsed = Url::parse(url).map_err(|e| format!("Not opening {}: {}", url, e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!("Not opening {}: only http and https links are opened", url).into());
+    }
+    Ok(parsed)
+}
+
+fn default_opener(url: &str) -> Command {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        // Not `cmd /C start`, which would interpret the URL as shell syntax
+        let mut command = Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    command.arg(url);
+    command
+}
+
+/// A command from the config, split on whitespace. `{}` is replaced by the
+/// URL; without it the URL goes last.
+pub fn command_line(template: &str, url: &str) -> AppResult<Command> {
+    let mut words = template.split_whitespace();
+    let program = words.next().ok_or("The command is empty")?;
+    let mut command = Command::new(program);
+    let mut placed = false;
+    for word in words {
+        if word.contains("{}") {
+            placed = true;
+        }
+        command.arg(word.replace("{}", url));
+    }
+    if !placed && !url.is_empty() {
+        command.arg(url);
+    }
+    Ok(command)
+}
+
+/// Whether to wrap links in OSC 8 escapes so the terminal makes them
+/// clickable, as configured or guessed from terminals known to support it
+pub fn hyperlinks_enabled() -> bool {
+    if let Some(enabled) = config::get().system.hyperlinks {
+        return enabled;
+    }
+
+    let term_program = env::var("TERM_PROGRAM").unwrap_or_default();
+    let term = env::var("TERM").unwrap_or_default();
+    matches!(
+        term_program.as_str(),
+        "iTerm.app" | "WezTerm" | "vscode" | "ghostty" | "Hyper"
+    ) || env::var("VTE_VERSION")
+        .ok()
+        .and_then(|v| v.parse::<u32>().ok())
+        .is_some_and(|v| v >= 5000)
+        || env::var("KITTY_WINDOW_ID").is_ok()
+        || env::var("WT_SESSION").is_ok()
+        || term.contains("kitty")
+        || term.contains("foot")
+        || term.contains("alacritty")
+}
+
+/// Redraw the links of the last frame as OSC 8 hyperlinks. The text and its
+/// style are unchanged, so this goes straight to the terminal behind the
+/// back of the UI's own diffing. A wrapped URL is several pieces, each
+/// pointing at the whole address.
+pub fn write_hyperlinks(out: &mut impl Write, hits: &HitMap, buffer: &Buffer) -> io::Result<()> {
+    for (area, url) in hits.links() {
+        let cell = buffer.get(area.x, area.y);
+        crossterm::queue!(
+            out,
+            MoveTo(area.x, area.y),
+            SetForegroundColor(cell.fg.into()),
+            SetBackgroundColor(cell.bg.into())
+        )?;
+        if cell.modifier.contains(Modifier::BOLD) {
+            crossterm::queue!(out, SetAttribute(Attribute::Bold))?;
+        }
+        if cell.modifier.contains(Modifier::UNDERLINED) {
+            crossterm::queue!(out, SetAttribute(Attribute::Underlined))?;
+        }
+        write!(
+            out,
+            "\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\",
+            url,
+            drawn_text(buffer, area)
+        )?;
+        crossterm::queue!(out, SetAttribute(Attribute::Reset), ResetColor)?;
+    }
+    out.flush()
+}
+
+// The text drawn in one row of cells, skipping the cells covered by wide
+// characters
+fn drawn_text(buffer: &Buffer, area: Rect) -> String {
+    let mut text = String::new();
+    let mut x = area.left();
+    while x < area.right() {
+        let symbol = &buffer.get(x, area.y).symbol;
+        text.push_str(symbol);
+        x += text::display_width(symbol).max(1) as u16;
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_web_urls_are_opened() {
+        assert!(web_url("https://docs.rs/serde").is_ok());
+        assert!(web_url("http://example.com").is_ok());
+        for url in [
+            "file:///etc/passwd",
+            "javascript:alert(1)",
+            "smb://host/share",
+            "calc.exe",
+            "",
+        ] {
+            assert!(web_url(url).is_err(), "{}", url);
+        }
+    }
+}
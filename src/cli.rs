@@ -0,0 +1,455 @@
+use crate::api;
+use crate::app::{self, AppResult};
+use crate::config::{self, Config};
+use crate::filter::format_count;
+use crate::msrv;
+use crate::policy::{Level, Policy};
+use crate::sets::ComparisonSets;
+use crate::store::Store;
+use crate::watch::{self, Watchlist};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+const USAGE: &str = "Usage: crates [--fresh]
+       crates COMMAND [ARGS]
+
+Run without a command to start the interactive explorer. It reopens the tab,
+search, selection and comparison of the last session unless --fresh is given.
+
+Commands:
+  audit [--policy FILE] [CRATE...]
+      Check crates against the security policy. Without crate names, audits
+      the crates.io dependencies of ./Cargo.toml. Exits with status 1 if any
+      rule configured with level = \"error\" fires, otherwise with status 3
+      if a crate could not be fetched.
+  compare --set NAME | compare CRATE...
+      Print a comparison of a saved set (as saved from the Compare tab, by
+      name or slug, e.g. http-clients) or of the given crates
+  compare --list
+      List saved comparison sets
+  watch --check
+      Check every watched crate for new releases, yanks, advisories and
+      license or owner changes, printing what changed since the last check.
+      Suitable for cron. Exits with status 1 if a check failed.
+  watch add|remove CRATE...
+      Start or stop watching crates
+  watch list
+      Show watched crates and unread changes
+  config show|edit|path
+      Print the effective settings, open the config file in $VISUAL or
+      $EDITOR (creating it with the defaults first), or print its location
+  help
+      Show this message";
+
+/// Run a headless command, returning the process exit code
+pub fn run(args: &[String]) -> AppResult<i32> {
+    match args.first().map(String::as_str) {
+        Some("audit") => audit(&args[1..]),
+        Some("compare") => compare(&args[1..]),
+        Some("config") => config(&args[1..]),
+        Some("watch") => watch(&args[1..]),
+        Some("help") | Some("-h") | Some("--help") => {
+            println!("{}", USAGE);
+            Ok(0)
+        }
+        Some(other) => Err(format!("Unknown command '{}'\n\n{}", other, USAGE).into()),
+        None => Ok(0),
+    }
+}
+
+fn audit(args: &[String]) -> AppResult<i32> {
+    let mut policy_path = None;
+    let mut names = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--policy" => {
+                policy_path = Some(iter.next().ok_or("--policy requires a file path")?);
+            }
+            flag if flag.starts_with('-') => {
+                return Err(format!("Unknown option '{}' for audit", flag).into());
+            }
+            name => names.push(name.to_string()),
+        }
+    }
+
+    let policy = match policy_path {
+        Some(path) => Policy::load_file(Path::new(path))?,
+        None => Policy::load()?,
+    };
+
+    if names.is_empty() {
+        names = manifest_dependencies(Path::new("Cargo.toml"))?;
+    }
+
+    let mut store = warn(Store::load());
+    let mut errors = 0;
+    let mut warnings = 0;
+    let mut failed = 0;
+
+    for name in &names {
+        let details = match api::get_crate_details(name) {
+            Ok(details) => details,
+            Err(e) => {
+                println!("{}\n  FAILED could not fetch crate: {}", name, e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        let mut findings = api::security_check(&details, &policy);
+        let ownership = app::inspect_ownership(&mut store, &policy, name);
+        let rust = ownership
+            .as_ref()
+            .and_then(|o| msrv::support(&o.versions, &details.max_version));
+        if let Some(ownership) = ownership {
+            findings.extend(ownership.findings);
+        }
+        let docs = api::docs_status(&details.name, &details.max_version).ok();
+        findings.extend(api::docs_check(&details.name, docs.as_ref(), &policy));
+        findings.extend(msrv::check(&details.name, rust.as_ref(), &policy));
+        findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+
+        println!("{} {}", details.name, details.max_version);
+        if findings.is_empty() {
+            println!("  ok");
+        }
+        for finding in &findings {
+            let level = match finding.level {
+                Level::Error => {
+                    errors += 1;
+                    "ERROR"
+                }
+                Level::Warn => {
+                    warnings += 1;
+                    "warn "
+                }
+            };
+            println!("  {} {}", level, finding);
+        }
+    }
+
+    // Saving is best effort; the audit result doesn't depend on it
+    let _ = store.save();
+
+    println!(
+        "\n{} crates audited: {} errors, {} warnings",
+        names.len() - failed,
+        errors,
+        warnings
+    );
+    if failed > 0 {
+        println!("{} crates could not be fetched", failed);
+    }
+
+    // A crate that couldn't be checked isn't a policy violation, but the
+    // audit is incomplete
+    Ok(if errors > 0 {
+        1
+    } else if failed > 0 {
+        3
+    } else {
+        0
+    })
+}
+
+fn compare(args: &[String]) -> AppResult<i32> {
+    let sets = warn(ComparisonSets::load());
+
+    let names = match args.first().map(String::as_str) {
+        Some("--list") => {
+            if sets.sets.is_empty() {
+                println!("No saved comparison sets. Save one from the Compare tab with 'S'.");
+            }
+            for (name, crates) in &sets.sets {
+                println!("{}: {}", name, crates.join(", "));
+            }
+            return Ok(0);
+        }
+        Some("--set") => {
+            let name = args.get(1).ok_or("--set requires a set name")?;
+            let (name, crates) = sets
+                .find(name)
+                .ok_or_else(|| format!("No comparison set called '{}'", name))?;
+            println!("{}\n", name);
+            crates.clone()
+        }
+        Some(flag) if flag.starts_with('-') => {
+            return Err(format!("Unknown option '{}' for compare", flag).into());
+        }
+        Some(_) => args.to_vec(),
+        None => return Err(format!("compare needs --set NAME or crate names\n\n{}", USAGE).into()),
+    };
+
+    let policy = Policy::load()?;
+    let mut store = warn(Store::load());
+    let mut failed = 0;
+
+    println!(
+        "{:<24} {:<12} {:>10} {:<24} {:<10} {:>5}  {:<10}",
+        "CRATE", "VERSION", "DOWNLOADS", "LICENSE", "SECURITY", "SCORE", "UPDATED"
+    );
+    for name in &names {
+        let details = match api::get_crate_details(name) {
+            Ok(details) => details,
+            Err(e) => {
+                println!("{:<24} ERROR could not fetch crate: {}", name, e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        let compared = app::compare_entry(&mut store, &policy, details);
+        let crate_data = &compared.details;
+        let security = match compared.security.warnings.len() {
+            0 => "ok".to_string(),
+            n => format!("{} warn", n),
+        };
+        println!(
+            "{:<24} {:<12} {:>10} {:<24} {:<10} {:>5}  {}",
+            crate_data.name,
+            crate_data.max_version,
+            format_count(crate_data.downloads),
+            crate_data.license.as_deref().unwrap_or("Unknown"),
+            security,
+            compared.score.total(),
+            crate_data
+                .updated_at
+                .get(..10)
+                .unwrap_or(&crate_data.updated_at)
+        );
+    }
+    let _ = store.save();
+
+    Ok(if failed > 0 { 1 } else { 0 })
+}
+
+fn config(args: &[String]) -> AppResult<i32> {
+    let path = config::config_path().ok_or("Could not determine config directory")?;
+
+    match args.first().map(String::as_str) {
+        Some("path") => {
+            println!("{}", path.display());
+            Ok(0)
+        }
+        Some("show") | None => {
+            let config = Config::load()?;
+            if path.exists() {
+                println!("# {}", path.display());
+            } else {
+                println!("# {} does not exist; showing the defaults", path.display());
+            }
+            print!("{}", toml::to_string_pretty(&config)?);
+            Ok(0)
+        }
+        Some("edit") => {
+            if !path.exists() {
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                fs::write(&path, toml::to_string_pretty(&Config::default())?)?;
+            }
+
+            let editor = std::env::var("VISUAL")
+                .or_else(|_| std::env::var("EDITOR"))
+                .unwrap_or_else(|_| "vi".to_string());
+            // The editor may come with arguments, e.g. `code --wait`
+            let mut words = editor.split_whitespace();
+            let program = words.next().ok_or("$EDITOR is empty")?;
+            let status = Command::new(program)
+                .args(words)
+                .arg(&path)
+                .status()
+                .map_err(|e| format!("Could not run editor '{}': {}", editor, e))?;
+            if !status.success() {
+                return Err(format!("Editor '{}' exited with {}", editor, status).into());
+            }
+
+            // Report mistakes now rather than on the next launch
+            match Config::load() {
+                Ok(_) => Ok(0),
+                Err(e) => {
+                    println!("{}", e);
+                    Ok(1)
+                }
+            }
+        }
+        Some(other) => Err(format!("Unknown config option '{}'\n\n{}", other, USAGE).into()),
+    }
+}
+
+fn watch(args: &[String]) -> AppResult<i32> {
+    let mut watchlist = Watchlist::load();
+
+    match args.first().map(String::as_str) {
+        Some("--check") | Some("check") => {
+            if watchlist.crates.is_empty() {
+                println!("No crates watched. Add some with `crates watch add CRATE`.");
+                return Ok(0);
+            }
+
+            let names: Vec<String> = watchlist.crates.keys().cloned().collect();
+            let mut failed = 0;
+            let mut results = Vec::new();
+            for name in names {
+                let result = watch::snapshot(&name).map_err(|e| e.to_string());
+                if let Err(ref e) = result {
+                    println!("{}\n  ERROR {}", name, e);
+                    failed += 1;
+                }
+                results.push((name, result));
+            }
+
+            // Fetching takes a while; record the results in one quick update
+            let checked = results.len();
+            let events = watchlist.update(|watchlist| {
+                results
+                    .into_iter()
+                    .flat_map(|result| watchlist.apply(result))
+                    .collect::<Vec<_>>()
+            })?;
+            for event in &events {
+                println!("{} [{}] {}", event.name, event.kind.label(), event.message);
+            }
+
+            println!(
+                "\n{} crates checked: {} changes, {} failed",
+                checked,
+                events.len(),
+                failed
+            );
+            Ok(if failed > 0 { 1 } else { 0 })
+        }
+        Some(action @ ("add" | "remove")) => {
+            let names = &args[1..];
+            if names.is_empty() {
+                return Err(format!("watch {} requires at least one crate name", action).into());
+            }
+            for name in names {
+                let watched = watchlist.contains(name);
+                if watched == (action == "add") {
+                    continue;
+                }
+                if action == "add" {
+                    // The baseline snapshot also checks that the crate exists
+                    let snapshot = watch::snapshot(name)?;
+                    watchlist.update(|watchlist| {
+                        if !watchlist.contains(name) {
+                            watchlist.toggle(name);
+                        }
+                        watchlist.apply((name.clone(), Ok(snapshot)));
+                    })?;
+                    println!("Watching {}", name);
+                } else {
+                    watchlist.update(|watchlist| {
+                        if watchlist.contains(name) {
+                            watchlist.toggle(name);
+                        }
+                    })?;
+                    println!("Stopped watching {}", name);
+                }
+            }
+            Ok(0)
+        }
+        Some("list") | None => {
+            for (name, watched) in &watchlist.crates {
+                let version = watched
+                    .snapshot
+                    .as_ref()
+                    .map(|s| s.latest_version.as_str())
+                    .unwrap_or("-");
+                println!(
+                    "{} {} ({} unread)",
+                    name,
+                    version,
+                    watchlist.unread_for(name)
+                );
+            }
+            Ok(0)
+        }
+        Some(other) => Err(format!("Unknown watch option '{}'\n\n{}", other, USAGE).into()),
+    }
+}
+
+// Crate names from the dependency tables of a manifest, following renames
+// Report a damaged data file that was set aside on load
+fn warn<T>((value, warning): (T, Option<String>)) -> T {
+    if let Some(warning) = warning {
+        eprintln!("warning: {}", warning);
+    }
+    value
+}
+
+fn manifest_dependencies(path: &Path) -> AppResult<Vec<String>> {
+    let manifest = read_manifest(path).map_err(|e| format!("No crates given and {}", e))?;
+    let workspace = workspace_dependencies(path);
+
+    // `[target.'cfg(..)'.dependencies]` and friends hold the same sections
+    let mut tables = vec![&manifest];
+    if let Some(targets) = manifest.get("target").and_then(|t| t.as_table()) {
+        tables.extend(targets.values().filter_map(|t| t.as_table()));
+    }
+
+    let mut names = Vec::new();
+    for table in tables {
+        for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+            let Some(deps) = table.get(section).and_then(|d| d.as_table()) else {
+                continue;
+            };
+            for (key, value) in deps {
+                let value = if value.get("workspace").and_then(|w| w.as_bool()) == Some(true) {
+                    workspace
+                        .as_ref()
+                        .and_then(|deps| deps.get(key))
+                        .ok_or_else(|| {
+                            format!(
+                                "{} inherits {} from the workspace, which does not define it",
+                                path.display(),
+                                key
+                            )
+                        })?
+                } else {
+                    value
+                };
+                // Only crates.io dependencies can be audited
+                if ["path", "git", "registry"]
+                    .iter()
+                    .any(|source| value.get(source).is_some())
+                {
+                    continue;
+                }
+                let name = value.get("package").and_then(|p| p.as_str()).unwrap_or(key);
+                if !names.iter().any(|n| n == name) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+    }
+
+    Ok(names)
+}
+
+fn read_manifest(path: &Path) -> AppResult<toml::Table> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+    Ok(text
+        .parse()
+        .map_err(|e| format!("invalid manifest {}: {}", path.display(), e))?)
+}
+
+// `[workspace.dependencies]` of the workspace the manifest belongs to: the
+// nearest manifest at or above it with a `[workspace]` table
+fn workspace_dependencies(path: &Path) -> Option<toml::Table> {
+    let path = fs::canonicalize(path).ok()?;
+    let root = path
+        .parent()?
+        .ancestors()
+        .filter_map(|dir| read_manifest(&dir.join("Cargo.toml")).ok())
+        .find(|manifest| manifest.contains_key("workspace"))?;
+    root.get("workspace")?
+        .get("dependencies")?
+        .as_table()
+        .cloned()
+}
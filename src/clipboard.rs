@@ -0,0 +1,77 @@
+use crate::app::AppResult;
+use crate::browser;
+use crate::config;
+use base64::Engine;
+use std::env;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+/// Put text on the clipboard: through the configured command, else the
+/// first clipboard tool that works, else an OSC 52 escape asking the
+/// terminal to do it (which also works over ssh)
+pub fn copy(text: &str) -> AppResult<()> {
+    if let Some(ref template) = config::get().system.clipboard {
+        let command = browser::command_line(template, "")?;
+        return pipe(command, text);
+    }
+
+    for candidate in candidates() {
+        let mut words = candidate.iter();
+        let Some(program) = words.next() else {
+            continue;
+        };
+        let mut command = Command::new(program);
+        command.args(words);
+        if pipe(command, text).is_ok() {
+            return Ok(());
+        }
+    }
+
+    osc52(text)
+}
+
+fn candidates() -> Vec<&'static [&'static str]> {
+    if cfg!(target_os = "macos") {
+        return vec![&["pbcopy"]];
+    }
+    if cfg!(windows) {
+        return vec![&["clip"]];
+    }
+
+    let mut candidates: Vec<&'static [&'static str]> = Vec::new();
+    if env::var("WAYLAND_DISPLAY").is_ok() {
+        candidates.push(&["wl-copy"]);
+    }
+    if env::var("DISPLAY").is_ok() {
+        candidates.push(&["xclip", "-selection", "clipboard"]);
+        candidates.push(&["xsel", "--clipboard", "--input"]);
+    }
+    candidates
+}
+
+// Run a command with the text on its stdin, waiting for it to take it
+fn pipe(mut command: Command, text: &str) -> AppResult<()> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Could not run the clipboard command: {}", e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(format!("The clipboard command failed ({})", status).into());
+    }
+    Ok(())
+}
+
+fn osc52(text: &str) -> AppResult<()> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", encoded)?;
+    stdout.flush()?;
+    Ok(())
+}
@@ -0,0 +1,229 @@
+use crate::app::ComparedCrate;
+use crate::filter::{format_count, LicenseFamily};
+use crate::msrv::RustVersion;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+
+/// A metric column of the Compare table. The crate name always comes first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Column {
+    Downloads,
+    RecentDownloads,
+    ReverseDeps,
+    Stars,
+    OpenIssues,
+    LastRelease,
+    Msrv,
+    Dependencies,
+    License,
+    LicenseFamily,
+    Advisories,
+    Security,
+    Score,
+    Version,
+}
+
+impl Column {
+    pub const ALL: [Column; 14] = [
+        Column::Downloads,
+        Column::RecentDownloads,
+        Column::ReverseDeps,
+        Column::Stars,
+        Column::OpenIssues,
+        Column::LastRelease,
+        Column::Msrv,
+        Column::Dependencies,
+        Column::License,
+        Column::LicenseFamily,
+        Column::Advisories,
+        Column::Security,
+        Column::Score,
+        Column::Version,
+    ];
+
+    /// The columns shown until the user picks their own
+    pub const DEFAULTS: [Column; 6] = [
+        Column::Downloads,
+        Column::License,
+        Column::Security,
+        Column::Score,
+        Column::LastRelease,
+        Column::Version,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Column::Downloads => "Downloads",
+            Column::RecentDownloads => "Recent",
+            Column::ReverseDeps => "Dependents",
+            Column::Stars => "Stars",
+            Column::OpenIssues => "Issues",
+            Column::LastRelease => "Released",
+            Column::Msrv => "MSRV",
+            Column::Dependencies => "Deps",
+            Column::License => "License",
+            Column::LicenseFamily => "License Family",
+            Column::Advisories => "Advisories",
+            Column::Security => "Security",
+            Column::Score => "Score",
+            Column::Version => "Version",
+        }
+    }
+
+    /// What the column shows, for the column menu
+    pub fn description(&self) -> &'static str {
+        match self {
+            Column::Downloads => "All-time downloads",
+            Column::RecentDownloads => "Downloads in the last 90 days",
+            Column::ReverseDeps => "Crates depending on it",
+            Column::Stars => "GitHub stars",
+            Column::OpenIssues => "Open GitHub issues and pull requests",
+            Column::LastRelease => "Date of the newest release",
+            Column::Msrv => "rust-version of the latest version",
+            Column::Dependencies => "Normal and build dependencies of the latest version",
+            Column::License => "License expression",
+            Column::LicenseFamily => "Permissive, copyleft, ...",
+            Column::Advisories => "Known advisories for the latest version",
+            Column::Security => "Findings of the security check",
+            Column::Score => "Composite trust score",
+            Column::Version => "Latest version",
+        }
+    }
+
+    pub fn width(&self) -> u16 {
+        match self {
+            Column::Downloads | Column::Dependencies | Column::Stars => 9,
+            Column::RecentDownloads | Column::OpenIssues | Column::Score => 8,
+            Column::ReverseDeps | Column::LastRelease => 10,
+            Column::Msrv => 6,
+            Column::License => 18,
+            Column::LicenseFamily => 14,
+            Column::Advisories => 10,
+            Column::Security => 10,
+            Column::Version => 12,
+        }
+    }
+
+    pub fn cell(&self, compared: &ComparedCrate) -> String {
+        let details = &compared.details;
+        let count = |n: Option<u64>| n.map(format_count).unwrap_or_else(|| "-".to_string());
+        match self {
+            Column::Downloads => format_count(details.downloads),
+            Column::RecentDownloads => count(details.recent_downloads),
+            Column::ReverseDeps => count(compared.reverse_deps),
+            Column::Stars => count(compared.repo.map(|r| r.stars)),
+            Column::OpenIssues => count(compared.repo.map(|r| r.open_issues)),
+            Column::LastRelease => compared
+                .last_release
+                .as_deref()
+                .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+                .map(|date| date.format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|| "Unknown".to_string()),
+            Column::Msrv => compared
+                .rust
+                .as_ref()
+                .and_then(|r| r.rust_version)
+                .map(|v| v.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            Column::Dependencies => count(dependency_count(compared)),
+            Column::License => match details.license.as_deref() {
+                Some(license) if !license.is_empty() => license.to_string(),
+                _ => "Unknown".to_string(),
+            },
+            Column::LicenseFamily => license_family(compared)
+                .map(|family| family.label().to_string())
+                .unwrap_or_else(|| "Unknown".to_string()),
+            Column::Advisories => count(compared.advisories.as_ref().map(|a| a.len() as u64)),
+            Column::Security => match compared.security.warnings.len() {
+                0 => "✓ Safe".to_string(),
+                n => format!("⚠ {}", n),
+            },
+            Column::Score => compared.score.total().to_string(),
+            Column::Version => {
+                // Flag versions that need a newer Rust than the project builds with
+                match compared.rust.as_ref().and_then(|r| r.raise.as_ref()) {
+                    Some(_) => format!("{} ⚠", details.max_version),
+                    None => details.max_version.clone(),
+                }
+            }
+        }
+    }
+
+    /// Where the crate ranks in this column, higher being better, or `None`
+    /// when the value is unknown. Sorting puts the best crates first.
+    pub fn rank(&self, compared: &ComparedCrate) -> Option<i64> {
+        let details = &compared.details;
+        let count = |n: u64| n as i64;
+        match self {
+            Column::Downloads => Some(count(details.downloads)),
+            Column::RecentDownloads => details.recent_downloads.map(count),
+            Column::ReverseDeps => compared.reverse_deps.map(count),
+            Column::Stars => compared.repo.map(|r| count(r.stars)),
+            Column::OpenIssues => compared.repo.map(|r| -count(r.open_issues)),
+            Column::LastRelease => compared
+                .last_release
+                .as_deref()
+                .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+                .map(|date| date.timestamp()),
+            Column::Msrv => compared
+                .rust
+                .as_ref()
+                .and_then(|r| r.rust_version)
+                .map(|v| -release_rank(v)),
+            Column::Dependencies => dependency_count(compared).map(|n| -count(n)),
+            Column::License | Column::LicenseFamily => {
+                license_family(compared).map(|family| -match family {
+                    LicenseFamily::PublicDomain | LicenseFamily::Permissive => 0,
+                    LicenseFamily::WeakCopyleft => 1,
+                    LicenseFamily::Copyleft => 2,
+                    LicenseFamily::Other => 3,
+                })
+            }
+            Column::Advisories => compared.advisories.as_ref().map(|a| -(a.len() as i64)),
+            Column::Security => Some(-(compared.security.warnings.len() as i64)),
+            Column::Score => Some(compared.score.total() as i64),
+            Column::Version => RustVersion::parse(&details.max_version).map(release_rank),
+        }
+    }
+
+    /// Whether the best value is worth pointing out; a newer version number
+    /// isn't better in itself
+    pub fn highlights_best(&self) -> bool {
+        *self != Column::Version
+    }
+}
+
+// Semver versions rank like Rust releases
+fn release_rank(version: RustVersion) -> i64 {
+    ((version.major as i64) << 40) + ((version.minor as i64) << 20) + version.patch as i64
+}
+
+fn dependency_count(compared: &ComparedCrate) -> Option<u64> {
+    let dependencies = compared.dependencies.as_ref()?;
+    Some(dependencies.iter().filter(|d| d.is_used()).count() as u64)
+}
+
+fn license_family(compared: &ComparedCrate) -> Option<LicenseFamily> {
+    compared
+        .details
+        .license
+        .as_deref()
+        .filter(|license| !license.is_empty())
+        .map(LicenseFamily::of)
+}
+
+/// The best rank in a column among the compared crates, if it tells them
+/// apart: with fewer than two known values, or all of them equal, nothing
+/// stands out
+pub fn best<'a>(
+    column: Column,
+    crates: impl IntoIterator<Item = &'a ComparedCrate>,
+) -> Option<i64> {
+    if !column.highlights_best() {
+        return None;
+    }
+    let ranks: Vec<i64> = crates.into_iter().filter_map(|c| column.rank(c)).collect();
+    let best = *ranks.iter().max()?;
+    ranks.iter().any(|rank| *rank != best).then_some(best)
+}
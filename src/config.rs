@@ -0,0 +1,255 @@
+use crate::app::{AppResult, Tab};
+use crate::keymap::Keymap;
+use reqwest::{Proxy, Url};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+const CONFIG_FILE: &str = "config.toml";
+// crates.io refuses larger pages
+const MAX_PAGE_SIZE: usize = 100;
+const TREND_PERIODS: [&str; 3] = ["daily", "weekly", "monthly"];
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct General {
+    /// How often the UI redraws and polls background work
+    pub tick_rate_ms: u64,
+    /// Crates fetched per request
+    pub page_size: usize,
+    /// Trending period for sessions started from scratch: daily, weekly or monthly
+    pub trend_period: String,
+    /// Tab to open on launch; unset reopens the tab of the last session
+    pub startup_tab: Option<Tab>,
+    /// dark, light, high-contrast, no-color, or the name of a file in
+    /// `~/.config/crates/themes/` without `.toml`
+    pub theme: String,
+}
+
+impl Default for General {
+    fn default() -> Self {
+        Self {
+            tick_rate_ms: 250,
+            page_size: 20,
+            trend_period: "weekly".to_string(),
+            startup_tab: None,
+            theme: "dark".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Network {
+    pub user_agent: String,
+    pub timeout_secs: u64,
+    /// Proxy for every request, e.g. `http://proxy:3128`. Without it the
+    /// usual `HTTPS_PROXY`/`HTTP_PROXY` environment variables apply.
+    pub proxy: Option<String>,
+}
+
+impl Default for Network {
+    fn default() -> Self {
+        Self {
+            user_agent: "crates cli app".to_string(),
+            timeout_secs: 30,
+            proxy: None,
+        }
+    }
+}
+
+/// Base URLs of the services the app talks to, e.g. for a registry mirror
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Endpoints {
+    pub crates_io: String,
+    pub github: String,
+    pub osv: String,
+    pub docs_rs: String,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self {
+            crates_io: "https://crates.io/api/v1".to_string(),
+            github: "https://api.github.com".to_string(),
+            osv: "https://api.osv.dev/v1".to_string(),
+            docs_rs: "https://docs.rs".to_string(),
+        }
+    }
+}
+
+/// How long fetched responses are reused; 0 always fetches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Cache {
+    /// Crate details, versions, owners, dependencies, downloads,
+    /// repositories, advisories and docs.rs status
+    pub crate_secs: u64,
+    /// Search results, listings, categories and keywords
+    pub listing_secs: u64,
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Self {
+            crate_secs: 300,
+            listing_secs: 60,
+        }
+    }
+}
+
+impl Cache {
+    pub fn crate_ttl(&self) -> Duration {
+        Duration::from_secs(self.crate_secs)
+    }
+
+    pub fn listing_ttl(&self) -> Duration {
+        Duration::from_secs(self.listing_secs)
+    }
+}
+
+/// How the app hands things to the rest of the desktop
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct System {
+    /// Command to open URLs with, e.g. `firefox --new-tab`. `{}` stands for
+    /// the URL, which otherwise goes last. Unset uses the system's opener.
+    pub opener: Option<String>,
+    /// Command that reads text to copy on stdin, e.g. `wl-copy`. Unset tries
+    /// the usual clipboard tools, then asks the terminal through OSC 52.
+    pub clipboard: Option<String>,
+    /// Make links clickable in the terminal with OSC 8; unset guesses from
+    /// the terminal
+    pub hyperlinks: Option<bool>,
+}
+
+/// Where the API docs come from
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ApiDocs {
+    /// Offer to build the docs with `cargo +nightly rustdoc` when docs.rs
+    /// has none. Building runs the crate's build script and proc macros on
+    /// this machine, so it is off unless set, and asked about every time.
+    pub local_build: bool,
+}
+
+/// User settings, loaded from `~/.config/crates/config.toml`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub general: General,
+    pub network: Network,
+    pub endpoints: Endpoints,
+    pub cache: Cache,
+    pub system: System,
+    #[serde(rename = "api-docs")]
+    pub api_docs: ApiDocs,
+    /// Action name -> keys, replacing the default keys of that action
+    pub keys: BTreeMap<String, Vec<String>>,
+}
+
+impl Config {
+    /// Load and validate the config file. A missing file means the defaults.
+    pub fn load() -> AppResult<Self> {
+        let Some(path) = config_path() else {
+            return Ok(Config::default());
+        };
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+
+        let text = fs::read_to_string(&path)
+            .map_err(|e| format!("Could not read config file {}: {}", path.display(), e))?;
+        Self::parse(&text)
+            .map_err(|e| format!("Invalid config file {}: {}", path.display(), e).into())
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let config: Config = toml::from_str(text).map_err(|e| e.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let general = &self.general;
+        if !(10..=5000).contains(&general.tick_rate_ms) {
+            return Err(format!(
+                "general.tick-rate-ms must be between 10 and 5000, got {}",
+                general.tick_rate_ms
+            ));
+        }
+        if !(1..=MAX_PAGE_SIZE).contains(&general.page_size) {
+            return Err(format!(
+                "general.page-size must be between 1 and {}, got {}",
+                MAX_PAGE_SIZE, general.page_size
+            ));
+        }
+        if !TREND_PERIODS.contains(&general.trend_period.as_str()) {
+            return Err(format!(
+                "general.trend-period must be one of {}, got \"{}\"",
+                TREND_PERIODS.join(", "),
+                general.trend_period
+            ));
+        }
+
+        if self.network.user_agent.trim().is_empty() {
+            return Err("network.user-agent must not be empty".to_string());
+        }
+        if self.network.timeout_secs == 0 {
+            return Err("network.timeout-secs must be at least 1".to_string());
+        }
+        if let Some(ref proxy) = self.network.proxy {
+            Proxy::all(proxy)
+                .map_err(|e| format!("network.proxy \"{}\" is invalid: {}", proxy, e))?;
+        }
+
+        for (key, url) in [
+            ("crates-io", &self.endpoints.crates_io),
+            ("github", &self.endpoints.github),
+            ("osv", &self.endpoints.osv),
+            ("docs-rs", &self.endpoints.docs_rs),
+        ] {
+            let parsed = Url::parse(url)
+                .map_err(|e| format!("endpoints.{} \"{}\" is invalid: {}", key, url, e))?;
+            if !matches!(parsed.scheme(), "http" | "https") {
+                return Err(format!(
+                    "endpoints.{} \"{}\" must be an http or https URL",
+                    key, url
+                ));
+            }
+        }
+
+        for (key, command) in [
+            ("opener", &self.system.opener),
+            ("clipboard", &self.system.clipboard),
+        ] {
+            if command.as_ref().is_some_and(|c| c.trim().is_empty()) {
+                return Err(format!("system.{} must not be empty", key));
+            }
+        }
+
+        Keymap::new(&self.keys)?;
+
+        Ok(())
+    }
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("crates").join(CONFIG_FILE))
+}
+
+/// Make a loaded config the one `get` returns. Only the first call has an effect.
+pub fn init(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+/// The active config, or the defaults if none was loaded
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
@@ -0,0 +1,409 @@
+use crate::api;
+use crate::app::AppResult;
+use flate2::read::GzDecoder;
+use similar::{ChangeTag, TextDiff};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::hash::{DefaultHasher, Hasher};
+use std::io::{self, Read};
+use std::path::{Component, Path};
+
+// Lines of unchanged context shown around each hunk
+const CONTEXT_LINES: usize = 3;
+// Larger files are not read into memory or shown in a terminal
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+// Stops a compressed archive from unpacking into more memory than this
+const MAX_UNPACKED_SIZE: u64 = 256 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FileStatus {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// Changes reviewers care about most when bumping a dependency
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Concern {
+    BuildScript,
+    Dependencies,
+    Unsafe,
+}
+
+impl Concern {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Concern::BuildScript => "build script changed",
+            Concern::Dependencies => "Cargo.toml dependencies changed",
+            Concern::Unsafe => "unsafe code changed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiffLineKind {
+    Hunk,
+    Context,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub text: String,
+    /// Set when this line is the reason for one of the file's concerns
+    pub flagged: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct FileDiff {
+    pub path: String,
+    pub status: FileStatus,
+    pub lines: Vec<DiffLine>,
+    pub concerns: Vec<Concern>,
+}
+
+#[derive(Debug, Clone)]
+pub struct VersionDiff {
+    pub name: String,
+    pub old_version: String,
+    pub new_version: String,
+    pub files: Vec<FileDiff>,
+}
+
+impl VersionDiff {
+    /// All concerns raised by any file, without duplicates
+    pub fn concerns(&self) -> Vec<Concern> {
+        let mut concerns = Vec::new();
+        for concern in self.files.iter().flat_map(|f| f.concerns.iter()) {
+            if !concerns.contains(concern) {
+                concerns.push(*concern);
+            }
+        }
+        concerns
+    }
+}
+
+/// Download two published versions of a crate and diff their contents
+pub fn diff_versions(name: &str, old_version: &str, new_version: &str) -> AppResult<VersionDiff> {
+    let old_files = unpack_crate(&api::download_crate(name, old_version)?, |_| true)?;
+    let new_files = unpack_crate(&api::download_crate(name, new_version)?, |_| true)?;
+
+    let mut files = Vec::new();
+
+    for (path, old_text) in &old_files {
+        match new_files.get(path) {
+            Some(new_text) if new_text != old_text => {
+                files.push(diff_file(path, FileStatus::Modified, old_text, new_text));
+            }
+            Some(_) => {}
+            None => files.push(diff_file(path, FileStatus::Removed, old_text, "")),
+        }
+    }
+
+    for (path, new_text) in &new_files {
+        if !old_files.contains_key(path) {
+            files.push(diff_file(path, FileStatus::Added, "", new_text));
+        }
+    }
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(VersionDiff {
+        name: name.to_string(),
+        old_version: old_version.to_string(),
+        new_version: new_version.to_string(),
+        files,
+    })
+}
+
+/// Extract the files of a `.crate` archive that `keep` accepts into a map of
+/// relative path -> file contents. Files too large to read in a terminal
+/// are summarised rather than loaded.
+pub fn unpack_crate(
+    bytes: &[u8],
+    keep: impl Fn(&str) -> bool,
+) -> AppResult<BTreeMap<String, String>> {
+    let mut files = BTreeMap::new();
+    each_file(bytes, keep, |path, size, entry| {
+        if size > MAX_FILE_SIZE {
+            // Hashed so a change to the file still shows up in a diff
+            let mut hasher = DefaultHasher::new();
+            let mut chunk = [0; 64 * 1024];
+            loop {
+                match entry.read(&mut chunk)? {
+                    0 => break,
+                    n => hasher.write(&chunk[..n]),
+                }
+            }
+            let text = format!(
+                "Large file ({} bytes, hash {:016x}) not shown\n",
+                size,
+                hasher.finish()
+            );
+            files.insert(path, text);
+            return Ok(());
+        }
+
+        let mut raw = Vec::new();
+        entry.read_to_end(&mut raw)?;
+
+        let text = match String::from_utf8(raw) {
+            Ok(text) => text,
+            Err(e) => format!("Binary file ({} bytes)\n", e.as_bytes().len()),
+        };
+
+        files.insert(path, text);
+        Ok(())
+    })?;
+    Ok(files)
+}
+
+/// Write the files of a `.crate` archive under `dir`, which becomes the
+/// package root
+pub fn extract_crate(bytes: &[u8], dir: &Path) -> AppResult<()> {
+    each_file(
+        bytes,
+        |_| true,
+        |path, _, entry| {
+            let out = dir.join(path);
+            if let Some(parent) = out.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            io::copy(entry, &mut File::create(&out)?)?;
+            Ok(())
+        },
+    )
+}
+
+// Visit the regular files of a `.crate` archive that `keep` accepts, with
+// their path in the package and size. The leading `{name}-{version}/`
+// directory is stripped so paths from different versions line up. Paths
+// that could point outside the package are skipped, and an archive that
+// unpacks to more than the limit is refused.
+fn each_file(
+    bytes: &[u8],
+    keep: impl Fn(&str) -> bool,
+    mut visit: impl FnMut(String, u64, &mut dyn Read) -> AppResult<()>,
+) -> AppResult<()> {
+    let mut archive = tar::Archive::new(GzDecoder::new(bytes));
+    let mut total = 0;
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let path = entry.path()?;
+        if !path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            continue;
+        }
+        let path = path.to_string_lossy().to_string();
+        let path = match path.split_once('/') {
+            Some((_, rest)) => rest.to_string(),
+            None => path,
+        };
+        if !keep(&path) {
+            continue;
+        }
+
+        let size = entry.size();
+        total += size;
+        if total > MAX_UNPACKED_SIZE {
+            return Err(format!(
+                "The package unpacks to more than {} MB",
+                MAX_UNPACKED_SIZE / 1024 / 1024
+            )
+            .into());
+        }
+
+        visit(path, size, &mut entry)?;
+    }
+
+    Ok(())
+}
+
+fn diff_file(path: &str, status: FileStatus, old_text: &str, new_text: &str) -> FileDiff {
+    let is_manifest = path == "Cargo.toml";
+    let is_rust = path.ends_with(".rs");
+    let old_lines: Vec<&str> = old_text.lines().collect();
+    let new_lines: Vec<&str> = new_text.lines().collect();
+
+    let mut concerns = Vec::new();
+    if path == "build.rs" || path.ends_with("/build.rs") {
+        concerns.push(Concern::BuildScript);
+    }
+
+    let diff = TextDiff::from_lines(old_text, new_text);
+    let mut lines = Vec::new();
+
+    for group in diff.grouped_ops(CONTEXT_LINES) {
+        let (first, last) = match (group.first(), group.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => continue,
+        };
+        let old_range = first.old_range().start..last.old_range().end;
+        let new_range = first.new_range().start..last.new_range().end;
+
+        lines.push(DiffLine {
+            kind: DiffLineKind::Hunk,
+            text: format!(
+                "@@ -{},{} +{},{} @@",
+                old_range.start + 1,
+                old_range.len(),
+                new_range.start + 1,
+                new_range.len()
+            ),
+            flagged: false,
+        });
+
+        for op in &group {
+            for change in diff.iter_changes(op) {
+                let text = change.value().trim_end_matches(['\n', '\r']).to_string();
+                let kind = match change.tag() {
+                    ChangeTag::Equal => DiffLineKind::Context,
+                    ChangeTag::Delete => DiffLineKind::Removed,
+                    ChangeTag::Insert => DiffLineKind::Added,
+                };
+
+                let mut flagged = false;
+                if kind != DiffLineKind::Context {
+                    if is_rust && contains_unsafe(&text) {
+                        flagged = true;
+                        add_concern(&mut concerns, Concern::Unsafe);
+                    }
+
+                    if is_manifest {
+                        let section = match change.tag() {
+                            ChangeTag::Delete => change
+                                .old_index()
+                                .and_then(|i| toml_section_at(&old_lines, i)),
+                            _ => change
+                                .new_index()
+                                .and_then(|i| toml_section_at(&new_lines, i)),
+                        };
+                        if section.map(is_dependency_section).unwrap_or(false) {
+                            flagged = true;
+                            add_concern(&mut concerns, Concern::Dependencies);
+                        }
+                    }
+                }
+
+                lines.push(DiffLine {
+                    kind,
+                    text,
+                    flagged,
+                });
+            }
+        }
+    }
+
+    FileDiff {
+        path: path.to_string(),
+        status,
+        lines,
+        concerns,
+    }
+}
+
+fn add_concern(concerns: &mut Vec<Concern>, concern: Concern) {
+    if !concerns.contains(&concern) {
+        concerns.push(concern);
+    }
+}
+
+// Match `unsafe` as a whole word so identifiers like `unsafe_op` don't count
+fn contains_unsafe(line: &str) -> bool {
+    line.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .any(|word| word == "unsafe")
+}
+
+// Find the TOML table header in effect at a given line
+fn toml_section_at<'a>(lines: &[&'a str], index: usize) -> Option<&'a str> {
+    lines[..(index + 1).min(lines.len())]
+        .iter()
+        .rev()
+        .map(|l| l.trim())
+        .find(|l| l.starts_with('['))
+}
+
+fn is_dependency_section(header: &str) -> bool {
+    let name = header.trim_matches(|c| c == '[' || c == ']');
+    name.split('.').any(|part| {
+        matches!(
+            part,
+            "dependencies" | "dev-dependencies" | "build-dependencies"
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    fn archive(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::fast()));
+        for (path, data) in files {
+            let mut header = tar::Header::new_gnu();
+            // Set by hand, as `set_path` refuses the paths under test
+            header.as_gnu_mut().unwrap().name[..path.len()].copy_from_slice(path.as_bytes());
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append(&header, *data).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    #[test]
+    fn unpack_skips_escaping_paths_and_summarises_large_files() {
+        let large = vec![b'a'; MAX_FILE_SIZE as usize + 1];
+        let bytes = archive(&[
+            ("demo-1.0.0/src/lib.rs", b"pub fn demo() {}\n"),
+            ("demo-1.0.0/../../outside", b"x"),
+            ("/etc/passwd", b"x"),
+            ("demo-1.0.0/README.md", b"# Demo\n"),
+            ("demo-1.0.0/data.bin", &large),
+        ]);
+
+        let files = unpack_crate(&bytes, |_| true).unwrap();
+        assert_eq!(
+            files.keys().collect::<Vec<_>>(),
+            ["README.md", "data.bin", "src/lib.rs"]
+        );
+        assert!(files["data.bin"].starts_with("Large file"));
+
+        let files = unpack_crate(&bytes, |path| path.ends_with(".md")).unwrap();
+        assert_eq!(files.keys().collect::<Vec<_>>(), ["README.md"]);
+    }
+
+    #[test]
+    fn extract_writes_the_package_and_nothing_outside_it() {
+        let root = std::env::temp_dir().join(format!("crates-extract-{}", std::process::id()));
+        let package = root.join("package");
+        let bytes = archive(&[
+            ("demo-1.0.0/src/lib.rs", b"pub fn demo() {}\n"),
+            ("demo-1.0.0/../outside", b"x"),
+            ("demo-1.0.0/data.bin", &[0xff, 0x00, 0xfe]),
+        ]);
+
+        extract_crate(&bytes, &package).unwrap();
+        assert_eq!(
+            fs::read_to_string(package.join("src/lib.rs")).unwrap(),
+            "pub fn demo() {}\n"
+        );
+        assert_eq!(
+            fs::read(package.join("data.bin")).unwrap(),
+            [0xff, 0x00, 0xfe]
+        );
+        assert!(!root.join("outside").exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -36,23 +36,17 @@ impl EventHandler {
                     .unwrap_or_else(|| Duration::from_secs(0));
 
                 if event::poll(timeout).unwrap() {
-                    match event::read().unwrap() {
-                        CrosstermEvent::Key(e) => {
-                            if sender.send(Event::Key(e)).is_err() {
-                                break;
-                            }
-                        }
-                        CrosstermEvent::Mouse(e) => {
-                            if sender.send(Event::Mouse(e)).is_err() {
-                                break;
-                            }
-                        }
-                        CrosstermEvent::Resize(w, h) => {
-                            if sender.send(Event::Resize(w, h)).is_err() {
-                                break;
-                            }
+                    let event = match event::read().unwrap() {
+                        CrosstermEvent::Key(e) => Some(Event::Key(e)),
+                        CrosstermEvent::Mouse(e) => Some(Event::Mouse(e)),
+                        CrosstermEvent::Resize(w, h) => Some(Event::Resize(w, h)),
+                        _ => None,
+                    };
+
+                    if let Some(event) = event {
+                        if sender.send(event).is_err() {
+                            break;
                         }
-                        _ => {}
                     }
                 }
 
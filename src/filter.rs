@@ -0,0 +1,410 @@
+use crate::api::Crate;
+use chrono::DateTime;
+
+// Presets the filter bar cycles through
+const MIN_DOWNLOAD_PRESETS: [u64; 4] = [1_000, 10_000, 100_000, 1_000_000];
+const UPDATED_WITHIN_PRESETS: [i64; 3] = [30, 90, 365];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LicenseFamily {
+    Permissive,
+    WeakCopyleft,
+    Copyleft,
+    PublicDomain,
+    Other,
+}
+
+impl LicenseFamily {
+    const CYCLE: [LicenseFamily; 4] = [
+        LicenseFamily::Permissive,
+        LicenseFamily::WeakCopyleft,
+        LicenseFamily::Copyleft,
+        LicenseFamily::PublicDomain,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LicenseFamily::Permissive => "permissive",
+            LicenseFamily::WeakCopyleft => "weak copyleft",
+            LicenseFamily::Copyleft => "copyleft",
+            LicenseFamily::PublicDomain => "public domain",
+            LicenseFamily::Other => "other",
+        }
+    }
+
+    /// Classify an SPDX expression. For `OR` expressions the most permissive
+    /// option wins since users may pick it; otherwise the most restrictive does.
+    pub fn of(license: &str) -> LicenseFamily {
+        // Old crates write `MIT/Apache-2.0` for `MIT OR Apache-2.0`
+        let spaced = license
+            .to_lowercase()
+            .replace('/', " or ")
+            .replace('(', " ( ")
+            .replace(')', " ) ");
+        let tokens: Vec<&str> = spaced.split_whitespace().collect();
+        any_of(&tokens, &mut 0)
+    }
+
+    fn classify(id: &str) -> LicenseFamily {
+        if id.contains("lgpl") || id.contains("mpl") || id.contains("epl") {
+            LicenseFamily::WeakCopyleft
+        } else if id.contains("gpl") {
+            LicenseFamily::Copyleft
+        } else if id.contains("unlicense") || id.contains("cc0") || id == "0bsd" {
+            LicenseFamily::PublicDomain
+        } else if ["mit", "apache", "bsd", "isc", "zlib", "bsl", "boost"]
+            .iter()
+            .any(|p| id.contains(p))
+        {
+            LicenseFamily::Permissive
+        } else {
+            LicenseFamily::Other
+        }
+    }
+
+    // From most to least permissive
+    fn rank(&self) -> u8 {
+        match self {
+            LicenseFamily::PublicDomain => 0,
+            LicenseFamily::Permissive => 1,
+            LicenseFamily::WeakCopyleft => 2,
+            LicenseFamily::Copyleft => 3,
+            LicenseFamily::Other => 4,
+        }
+    }
+}
+
+// The parts of an SPDX expression, lowest precedence first: `OR` joins
+// `AND` groups, which join single licenses or parenthesised expressions.
+// `pos` is the next token to read.
+fn any_of(tokens: &[&str], pos: &mut usize) -> LicenseFamily {
+    let mut family = all_of(tokens, pos);
+    while tokens.get(*pos) == Some(&"or") {
+        *pos += 1;
+        family = std::cmp::min_by_key(family, all_of(tokens, pos), LicenseFamily::rank);
+    }
+    family
+}
+
+fn all_of(tokens: &[&str], pos: &mut usize) -> LicenseFamily {
+    let mut family = license(tokens, pos);
+    while tokens.get(*pos) == Some(&"and") {
+        *pos += 1;
+        family = std::cmp::max_by_key(family, license(tokens, pos), LicenseFamily::rank);
+    }
+    family
+}
+
+fn license(tokens: &[&str], pos: &mut usize) -> LicenseFamily {
+    let Some(token) = tokens.get(*pos) else {
+        return LicenseFamily::Other;
+    };
+    *pos += 1;
+    if *token == "(" {
+        let family = any_of(tokens, pos);
+        if tokens.get(*pos) == Some(&")") {
+            *pos += 1;
+        }
+        return family;
+    }
+    // An exception only ever grants more, so the license decides
+    if tokens.get(*pos) == Some(&"with") {
+        *pos += 2;
+    }
+    LicenseFamily::classify(token)
+}
+
+/// Client-side filters applied to search results
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchFilters {
+    pub license: Option<LicenseFamily>,
+    pub min_downloads: Option<u64>,
+    pub updated_within_days: Option<i64>,
+    pub has_repository: bool,
+    pub hide_early_versions: bool,
+}
+
+impl SearchFilters {
+    pub fn is_active(&self) -> bool {
+        *self != SearchFilters::default()
+    }
+
+    pub fn matches(&self, crate_data: &Crate) -> bool {
+        if let Some(family) = self.license {
+            match crate_data.license.as_deref() {
+                Some(license) if LicenseFamily::of(license) == family => {}
+                _ => return false,
+            }
+        }
+
+        if let Some(min) = self.min_downloads {
+            if crate_data.downloads < min {
+                return false;
+            }
+        }
+
+        if let Some(days) = self.updated_within_days {
+            let recent = DateTime::parse_from_rfc3339(&crate_data.updated_at)
+                .map(|t| chrono::Utc::now().signed_duration_since(t).num_days() <= days)
+                .unwrap_or(false);
+            if !recent {
+                return false;
+            }
+        }
+
+        if self.has_repository
+            && crate_data
+                .repository
+                .as_deref()
+                .unwrap_or("")
+                .trim()
+                .is_empty()
+        {
+            return false;
+        }
+
+        !(self.hide_early_versions && crate_data.max_version.starts_with("0.0."))
+    }
+
+    /// Short labels for the active filters, shown as chips in the filter bar
+    pub fn chips(&self) -> Vec<String> {
+        let mut chips = Vec::new();
+        if let Some(family) = self.license {
+            chips.push(format!("license: {}", family.label()));
+        }
+        if let Some(min) = self.min_downloads {
+            chips.push(format!("downloads ≥ {}", format_count(min)));
+        }
+        if let Some(days) = self.updated_within_days {
+            chips.push(format!("updated ≤ {}d", days));
+        }
+        if self.has_repository {
+            chips.push("has repository".to_string());
+        }
+        if self.hide_early_versions {
+            chips.push("no 0.0.x".to_string());
+        }
+        chips
+    }
+
+    pub fn cycle_license(&mut self) {
+        self.license = cycle(&LicenseFamily::CYCLE, self.license);
+    }
+
+    pub fn cycle_min_downloads(&mut self) {
+        self.min_downloads = cycle(&MIN_DOWNLOAD_PRESETS, self.min_downloads);
+    }
+
+    pub fn cycle_updated_within(&mut self) {
+        self.updated_within_days = cycle(&UPDATED_WITHIN_PRESETS, self.updated_within_days);
+    }
+}
+
+// Step to the next preset, going back to "off" after the last one
+fn cycle<T: Copy + PartialEq>(presets: &[T], current: Option<T>) -> Option<T> {
+    match current {
+        None => presets.first().copied(),
+        Some(value) => presets
+            .iter()
+            .position(|p| *p == value)
+            .and_then(|i| presets.get(i + 1))
+            .copied(),
+    }
+}
+
+/// Compact human-readable count, e.g. 1.2K or 3M
+pub fn format_count(n: u64) -> String {
+    if n < 1_000 {
+        return n.to_string();
+    }
+    let mut value = n as f64 / 1_000.0;
+    for suffix in ["K", "M"] {
+        // Decide after rounding, so 999_999 is 1M rather than 1000K
+        if (value * 10.0).round() < 10_000.0 {
+            return trim_decimal(value, suffix);
+        }
+        value /= 1_000.0;
+    }
+    trim_decimal(value, "B")
+}
+
+fn trim_decimal(value: f64, suffix: &str) -> String {
+    let formatted = format!("{:.1}", value);
+    format!("{}{}", formatted.trim_end_matches(".0"), suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search_result(
+        license: Option<&str>,
+        downloads: u64,
+        days_old: i64,
+        repository: Option<&str>,
+        max_version: &str,
+    ) -> Crate {
+        let updated_at = chrono::Utc::now() - chrono::Duration::days(days_old);
+        serde_json::from_value(serde_json::json!({
+            "name": "demo",
+            "description": null,
+            "downloads": downloads,
+            "created_at": "2020-01-01T00:00:00Z",
+            "updated_at": updated_at.to_rfc3339(),
+            "documentation": null,
+            "repository": repository,
+            "homepage": null,
+            "max_version": max_version,
+            "license": license,
+            "keywords": null,
+            "categories": null,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn license_families() {
+        use LicenseFamily::*;
+        for (license, family) in [
+            ("MIT", Permissive),
+            ("MIT OR Apache-2.0", Permissive),
+            ("MIT/Apache-2.0", Permissive),
+            ("Apache-2.0 WITH LLVM-exception", Permissive),
+            ("GPL-3.0-only", Copyleft),
+            ("GPL-3.0-only WITH Classpath-exception-2.0", Copyleft),
+            ("GPL-2.0-or-later OR MIT", Permissive),
+            ("LGPL-2.1-or-later", WeakCopyleft),
+            ("MPL-2.0", WeakCopyleft),
+            ("MIT AND GPL-3.0-only", Copyleft),
+            ("(MIT OR Apache-2.0) AND GPL-3.0-only", Copyleft),
+            ("MIT AND (LGPL-2.1 OR GPL-3.0)", WeakCopyleft),
+            ("Unlicense OR MIT", PublicDomain),
+            ("CC0-1.0", PublicDomain),
+            ("0BSD", PublicDomain),
+            ("Proprietary", Other),
+            ("", Other),
+            ("MIT OR", Permissive),
+            ("(MIT", Permissive),
+        ] {
+            assert_eq!(LicenseFamily::of(license), family, "{:?}", license);
+        }
+    }
+
+    #[test]
+    fn filters_match() {
+        let filters = |f: fn(&mut SearchFilters)| {
+            let mut filters = SearchFilters::default();
+            f(&mut filters);
+            filters
+        };
+        let typical = search_result(
+            Some("MIT OR Apache-2.0"),
+            50_000,
+            10,
+            Some("https://x"),
+            "1.2.0",
+        );
+
+        for (filters, result, expected) in [
+            (
+                SearchFilters::default(),
+                search_result(None, 0, 999, None, "0.0.1"),
+                true,
+            ),
+            (
+                filters(|f| f.license = Some(LicenseFamily::Permissive)),
+                typical.clone(),
+                true,
+            ),
+            (
+                filters(|f| f.license = Some(LicenseFamily::Copyleft)),
+                typical.clone(),
+                false,
+            ),
+            (
+                filters(|f| f.license = Some(LicenseFamily::Permissive)),
+                search_result(None, 1, 1, None, "1.0.0"),
+                false,
+            ),
+            (
+                filters(|f| f.min_downloads = Some(50_000)),
+                typical.clone(),
+                true,
+            ),
+            (
+                filters(|f| f.min_downloads = Some(50_001)),
+                typical.clone(),
+                false,
+            ),
+            (
+                filters(|f| f.updated_within_days = Some(30)),
+                typical.clone(),
+                true,
+            ),
+            (
+                filters(|f| f.updated_within_days = Some(5)),
+                typical.clone(),
+                false,
+            ),
+            (filters(|f| f.has_repository = true), typical.clone(), true),
+            (
+                filters(|f| f.has_repository = true),
+                search_result(None, 1, 1, Some(" "), "1.0.0"),
+                false,
+            ),
+            (
+                filters(|f| f.hide_early_versions = true),
+                typical.clone(),
+                true,
+            ),
+            (
+                filters(|f| f.hide_early_versions = true),
+                search_result(None, 1, 1, None, "0.0.3"),
+                false,
+            ),
+            (
+                filters(|f| f.hide_early_versions = true),
+                search_result(None, 1, 1, None, "0.1.0"),
+                true,
+            ),
+        ] {
+            assert_eq!(
+                filters.matches(&result),
+                expected,
+                "{:?} {:?}",
+                filters,
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn presets_cycle_back_to_off() {
+        let mut filters = SearchFilters::default();
+        let mut seen = Vec::new();
+        for _ in 0..5 {
+            filters.cycle_updated_within();
+            seen.push(filters.updated_within_days);
+        }
+        assert_eq!(seen, [Some(30), Some(90), Some(365), None, Some(30)]);
+        assert!(filters.is_active());
+    }
+
+    #[test]
+    fn counts() {
+        for (n, text) in [
+            (0, "0"),
+            (999, "999"),
+            (1_000, "1K"),
+            (1_250, "1.2K"),
+            (999_949, "999.9K"),
+            (999_950, "1M"),
+            (999_999, "1M"),
+            (1_500_000, "1.5M"),
+            (999_999_999, "1B"),
+            (12_300_000_000, "12.3B"),
+        ] {
+            assert_eq!(format_count(n), text, "{}", n);
+        }
+    }
+}
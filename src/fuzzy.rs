@@ -0,0 +1,174 @@
+use crate::api::{Crate, Repository};
+use crate::text;
+
+// Quick-filter matching for the lists already on screen. Whitespace is ignored
+// on both sides, so match positions count only the visible characters of a
+// field; that keeps them valid after the UI wraps text onto several lines.
+
+/// Where a query matched best
+pub struct FuzzyMatch {
+    /// Index of the matching field in the slice passed to `best_match`
+    pub field: usize,
+    pub score: i64,
+    /// Matched characters, counted over the non-whitespace characters of the field
+    pub positions: Vec<usize>,
+}
+
+/// Match the query as a case-insensitive subsequence of each field, keeping
+/// the best-scoring field. Runs of consecutive characters and matches at
+/// word starts score higher.
+pub fn best_match(query: &str, fields: &[&str]) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return None;
+    }
+
+    let mut best: Option<FuzzyMatch> = None;
+    for (field, candidate) in fields.iter().enumerate() {
+        if let Some((score, positions)) = match_field(&query, candidate) {
+            if best.as_ref().is_none_or(|b| score > b.score) {
+                best = Some(FuzzyMatch {
+                    field,
+                    score,
+                    positions,
+                });
+            }
+        }
+    }
+    best
+}
+
+fn match_field(query: &[char], candidate: &str) -> Option<(i64, Vec<usize>)> {
+    let mut positions = Vec::with_capacity(query.len());
+    let mut score = 0;
+    let mut next = 0;
+    let mut ordinal = 0;
+    let mut previous: Option<char> = None;
+    let mut last_match: Option<usize> = None;
+
+    for c in candidate.chars() {
+        if next == query.len() {
+            break;
+        }
+        if c.is_whitespace() {
+            previous = Some(c);
+            continue;
+        }
+
+        if c.to_lowercase().eq(std::iter::once(query[next])) {
+            score += 1;
+            if last_match.is_some_and(|last| last + 1 == ordinal) {
+                score += 5;
+            }
+            let word_start = match previous {
+                None => true,
+                Some(p) => !p.is_alphanumeric() || (p.is_lowercase() && c.is_uppercase()),
+            };
+            if word_start {
+                score += 3;
+            }
+
+            positions.push(ordinal);
+            last_match = Some(ordinal);
+            next += 1;
+        }
+
+        previous = Some(c);
+        ordinal += 1;
+    }
+
+    // Prefer matches that complete early, e.g. in a name over deep in a description
+    (next == query.len()).then(|| (score * 100 - ordinal as i64, positions))
+}
+
+/// Fields searched for a crate: name, description and keywords
+fn crate_fields(crate_data: &Crate) -> [String; 3] {
+    [
+        crate_data.name.clone(),
+        text::single_line(crate_data.description.as_deref().unwrap_or_default()),
+        crate_data.keywords.as_deref().unwrap_or_default().join(" "),
+    ]
+}
+
+/// Fields searched for a repository: name, description and language
+fn repo_fields(repo: &Repository) -> [String; 3] {
+    [
+        repo.full_name.clone(),
+        text::single_line(repo.description.as_deref().unwrap_or_default()),
+        repo.language.clone().unwrap_or_default(),
+    ]
+}
+
+/// Match a query against a crate's fields
+pub fn match_crate(query: &str, crate_data: &Crate) -> Option<FuzzyMatch> {
+    let fields = crate_fields(crate_data);
+    best_match(query, &fields.each_ref().map(String::as_str))
+}
+
+/// Match a query against a repository's fields
+pub fn match_repo(query: &str, repo: &Repository) -> Option<FuzzyMatch> {
+    let fields = repo_fields(repo);
+    best_match(query, &fields.each_ref().map(String::as_str))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(query: &str, candidate: &str) -> Option<i64> {
+        best_match(query, &[candidate]).map(|m| m.score)
+    }
+
+    #[test]
+    fn prefixes_rank_above_scattered_matches() {
+        let prefix = score("ser", "serde").unwrap();
+        let scattered = score("ser", "base-error-reporter").unwrap();
+        assert!(prefix > scattered, "{} <= {}", prefix, scattered);
+
+        // Consecutive characters beat the same letters spread out
+        assert!(score("json", "json5").unwrap() > score("json", "jq-string-conversion").unwrap());
+    }
+
+    #[test]
+    fn word_starts_rank_above_inner_letters() {
+        assert!(score("sj", "serde_json").unwrap() > score("sj", "subject").unwrap());
+
+        let m = best_match("hm", &["HashMap"]).unwrap();
+        assert_eq!(m.positions, [0, 4]);
+    }
+
+    #[test]
+    fn non_matches() {
+        assert!(score("xyz", "serde").is_none());
+        // Every character has to appear, in order
+        assert!(score("des", "serde").is_none());
+        assert!(score("serdes", "serde").is_none());
+        assert!(score("", "serde").is_none());
+        assert!(score("  ", "serde").is_none());
+        assert!(best_match("serde", &[]).is_none());
+    }
+
+    #[test]
+    fn matching_ignores_case_and_whitespace() {
+        assert!(score("SERDE", "serde").is_some());
+        assert_eq!(score("s j", "serde_json"), score("sj", "serde_json"));
+
+        // Positions skip the whitespace in the field
+        let m = best_match("ab", &["x  a b"]).unwrap();
+        assert_eq!(m.positions, [1, 2]);
+    }
+
+    #[test]
+    fn the_best_field_wins() {
+        let m = best_match("tokio", &["tokio", "a tokio runtime", ""]).unwrap();
+        assert_eq!(m.field, 0);
+
+        let m = best_match("runtime", &["tokio", "an async runtime", "runtime async"]).unwrap();
+        assert_eq!(m.field, 2);
+        assert_eq!(m.positions, (0..7).collect::<Vec<_>>());
+    }
+}
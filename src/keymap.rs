@@ -0,0 +1,792 @@
+use crate::browser::Link;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeMap;
+use std::fmt;
+
+// Keys are written like `j`, `G`, `ctrl-d`, `alt-x`, `enter` or `pagedown`;
+// a sequence is several keys separated by spaces, e.g. `g g`. In the config
+// file each action maps to the full list of its keys:
+//
+//   [keys]
+//   down = ["j", "down", "ctrl-n"]
+//   quit = ["ctrl-q"]
+//
+// Ctrl+C always quits, and text input (search, prompts) is never remapped.
+
+/// Where a key was pressed; each view has its own bindings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Context {
+    /// A tab's list, tree or table
+    List,
+    /// The Compare tab's column menu
+    Columns,
+    /// The Compare tab's saved sets list
+    Sets,
+    Detail,
+    Readme,
+    Api,
+    Versions,
+    Diff,
+    SideBySide,
+}
+
+impl Context {
+    pub const ALL: [Context; 9] = [
+        Context::List,
+        Context::Columns,
+        Context::Sets,
+        Context::Detail,
+        Context::SideBySide,
+        Context::Readme,
+        Context::Api,
+        Context::Versions,
+        Context::Diff,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Context::List => "Lists",
+            Context::Columns => "Column Menu",
+            Context::Sets => "Saved Sets",
+            Context::Detail => "Detail View",
+            Context::Readme => "README",
+            Context::Api => "API Docs",
+            Context::Versions => "Version History",
+            Context::Diff => "Version Diff",
+            Context::SideBySide => "Side by Side",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    NextTab,
+    PrevTab,
+    Down,
+    Up,
+    PageDown,
+    PageUp,
+    Top,
+    Bottom,
+    Open,
+    Back,
+    GoSearch,
+    GoRecent,
+    GoTrending,
+    GoCompare,
+    GoCategories,
+    GoWatchlist,
+    GoHelp,
+    Search,
+    Sort,
+    Filters,
+    QuickFilter,
+    Add,
+    Remove,
+    SaveSet,
+    OpenSets,
+    Rename,
+    Columns,
+    Expand,
+    SwitchPane,
+    Watch,
+    AcknowledgeOwners,
+    CheckWatchlist,
+    MarkRead,
+    Versions,
+    Readme,
+    Api,
+    NextTag,
+    PrevTag,
+    Mark,
+    SideBySide,
+    Diff,
+    ScrollDown,
+    ScrollUp,
+    MoveDown,
+    MoveUp,
+    OpenLink(Link),
+    CopyLink(Link),
+    CopySnippet,
+}
+
+impl Action {
+    /// Name used in the `[keys]` table of the config file
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::NextTab => "next-tab",
+            Action::PrevTab => "prev-tab",
+            Action::Down => "down",
+            Action::Up => "up",
+            Action::PageDown => "page-down",
+            Action::PageUp => "page-up",
+            Action::Top => "top",
+            Action::Bottom => "bottom",
+            Action::Open => "open",
+            Action::Back => "back",
+            Action::GoSearch => "tab-search",
+            Action::GoRecent => "tab-recent",
+            Action::GoTrending => "tab-trending",
+            Action::GoCompare => "tab-compare",
+            Action::GoCategories => "tab-categories",
+            Action::GoWatchlist => "tab-watchlist",
+            Action::GoHelp => "tab-help",
+            Action::Search => "search",
+            Action::Sort => "sort",
+            Action::Filters => "filters",
+            Action::QuickFilter => "quick-filter",
+            Action::Add => "add",
+            Action::Remove => "remove",
+            Action::SaveSet => "save-set",
+            Action::OpenSets => "open-sets",
+            Action::Rename => "rename",
+            Action::Columns => "columns",
+            Action::Expand => "expand",
+            Action::SwitchPane => "switch-pane",
+            Action::Watch => "watch",
+            Action::AcknowledgeOwners => "acknowledge-owners",
+            Action::CheckWatchlist => "check-watchlist",
+            Action::MarkRead => "mark-read",
+            Action::Versions => "versions",
+            Action::Readme => "readme",
+            Action::Api => "api-docs",
+            Action::NextTag => "next-tag",
+            Action::PrevTag => "prev-tag",
+            Action::Mark => "mark",
+            Action::SideBySide => "side-by-side",
+            Action::Diff => "diff",
+            Action::ScrollDown => "scroll-down",
+            Action::ScrollUp => "scroll-up",
+            Action::MoveDown => "move-down",
+            Action::MoveUp => "move-up",
+            Action::OpenLink(Link::Docs) => "open-docs",
+            Action::OpenLink(Link::Repository) => "open-repository",
+            Action::OpenLink(Link::CratesIo) => "open-crates-io",
+            Action::OpenLink(Link::Homepage) => "open-homepage",
+            Action::CopyLink(Link::Docs) => "copy-docs-url",
+            Action::CopyLink(Link::Repository) => "copy-repository-url",
+            Action::CopyLink(Link::CratesIo) => "copy-crates-io-url",
+            Action::CopyLink(Link::Homepage) => "copy-homepage-url",
+            Action::CopySnippet => "copy-snippet",
+        }
+    }
+
+    pub fn description(&self, context: Context) -> &'static str {
+        match (self, context) {
+            (Action::Quit, _) => "Quit",
+            (Action::NextTab, _) => "Next tab",
+            (Action::PrevTab, _) => "Previous tab",
+            (Action::Down, Context::Detail | Context::Readme | Context::SideBySide) => {
+                "Scroll down"
+            }
+            (Action::Up, Context::Detail | Context::Readme | Context::SideBySide) => "Scroll up",
+            (Action::Down, Context::Diff) => "Next file",
+            (Action::Up, Context::Diff) => "Previous file",
+            (Action::Down, _) => "Move down",
+            (Action::Up, _) => "Move up",
+            (Action::PageDown, _) => "Scroll down a page",
+            (Action::PageUp, _) => "Scroll up a page",
+            (Action::Top, Context::Readme | Context::SideBySide) => "Jump to the start",
+            (Action::Bottom, Context::Readme | Context::SideBySide) => "Jump to the end",
+            (Action::Top, _) => "Jump to the first row",
+            (Action::Bottom, _) => "Jump to the last row",
+            (Action::Open, Context::Detail) => "Browse crates with the selected tag",
+            (Action::Open, Context::Sets) => "Load the set into the comparison",
+            (Action::Open, Context::Api) => "Expand an item, or show a search result in the tree",
+            (Action::Open, _) => "Show details, or list the crates of a category or keyword",
+            (Action::Back, Context::List) => "Clear the quick filter, or leave a category listing",
+            (Action::Back, Context::Api) => "Clear the search, or go back",
+            (Action::Back, Context::Columns | Context::Sets) => "Close the menu",
+            (Action::Back, _) => "Go back",
+            (Action::GoSearch, _) => "Search tab",
+            (Action::GoRecent, _) => "Recent tab",
+            (Action::GoTrending, _) => "Trending tab",
+            (Action::GoCompare, _) => "Compare tab",
+            (Action::GoCategories, _) => "Categories tab",
+            (Action::GoWatchlist, _) => "Watchlist tab",
+            (Action::GoHelp, _) => "Help tab",
+            (Action::Search, Context::Api) => "Search items by name",
+            (Action::Search, _) => "Type a search (Search tab)",
+            (Action::Sort, Context::Columns) => "Sort the comparison by the column",
+            (Action::Sort, _) => "Cycle the search sort order, or the comparison's sort column",
+            (Action::Filters, _) => "Search filters (Search tab)",
+            (Action::QuickFilter, _) => {
+                "Quick filter the loaded list by name, description or keywords"
+            }
+            (Action::Add, _) => "Add the selected crate to the comparison, or one by name",
+            (Action::Remove, Context::Sets) => "Delete the set",
+            (Action::Remove, _) => "Remove from the comparison or the watchlist",
+            (Action::SaveSet, _) => "Save the comparison as a named set",
+            (Action::OpenSets, Context::Sets) => "Close the menu",
+            (Action::OpenSets, _) => "Open saved comparison sets",
+            (Action::Rename, _) => "Rename the set",
+            (Action::Columns, Context::Columns) => "Close the menu",
+            (Action::Columns, _) => "Choose, reorder and sort the comparison's columns",
+            (Action::Expand, Context::Api) => "Expand or collapse an item",
+            (Action::Expand, Context::Columns) => "Show or hide the column",
+            (Action::Expand, _) => "Expand or collapse a category",
+            (Action::SwitchPane, _) => "Switch between categories and keywords",
+            (Action::Watch, _) => "Watch or unwatch the crate",
+            (Action::AcknowledgeOwners, _) => "Accept the crate's current owners as trusted",
+            (Action::CheckWatchlist, _) => "Check watched crates now",
+            (Action::MarkRead, _) => "Mark all watchlist changes read",
+            (Action::Versions, _) => "Version history",
+            (Action::Readme, _) => "Read the README",
+            (Action::Api, _) => "Browse the API docs",
+            (Action::NextTag, _) => "Select the next keyword or category",
+            (Action::PrevTag, _) => "Select the previous keyword or category",
+            (Action::Mark, Context::List) => "Mark a compared crate to view side by side",
+            (Action::Mark, _) => "Mark a version to diff",
+            (Action::SideBySide, _) => "Compare two to four marked crates side by side",
+            (Action::Diff, _) => "Diff the marked versions",
+            (Action::ScrollDown, Context::Api) => "Scroll the docs down",
+            (Action::ScrollUp, Context::Api) => "Scroll the docs up",
+            (Action::ScrollDown, _) => "Scroll the diff down",
+            (Action::ScrollUp, _) => "Scroll the diff up",
+            (Action::MoveDown, _) => "Move the column right",
+            (Action::MoveUp, _) => "Move the column left",
+            (Action::OpenLink(Link::Docs), _) => "Open the documentation in the browser",
+            (Action::OpenLink(Link::Repository), _) => "Open the repository in the browser",
+            (Action::OpenLink(Link::CratesIo), _) => "Open the crates.io page in the browser",
+            (Action::OpenLink(Link::Homepage), _) => "Open the homepage in the browser",
+            (Action::CopyLink(Link::Docs), _) => "Copy the documentation URL",
+            (Action::CopyLink(Link::Repository), _) => "Copy the repository URL",
+            (Action::CopyLink(Link::CratesIo), _) => "Copy the crates.io URL",
+            (Action::CopyLink(Link::Homepage), _) => "Copy the homepage URL",
+            (Action::CopySnippet, _) => "Copy a Cargo.toml line, e.g. serde = \"1.0.0\"",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Action> {
+        DEFAULTS
+            .iter()
+            .map(|(_, action, _)| *action)
+            .find(|action| action.name() == name)
+    }
+}
+
+// Built-in bindings, in the order the help tab lists them
+const DEFAULTS: &[(Context, Action, &[&str])] = &[
+    (Context::List, Action::Down, &["j", "down"]),
+    (Context::List, Action::Up, &["k", "up"]),
+    (Context::List, Action::Top, &["g g", "home"]),
+    (Context::List, Action::Bottom, &["G", "end"]),
+    (Context::List, Action::Open, &["enter"]),
+    (Context::List, Action::Back, &["esc"]),
+    (Context::List, Action::NextTab, &["tab"]),
+    (Context::List, Action::PrevTab, &["backtab"]),
+    (Context::List, Action::GoSearch, &["1"]),
+    (Context::List, Action::GoRecent, &["2"]),
+    (Context::List, Action::GoTrending, &["3"]),
+    (Context::List, Action::GoHelp, &["4"]),
+    (Context::List, Action::GoCompare, &["5"]),
+    (Context::List, Action::GoCategories, &["6"]),
+    (Context::List, Action::GoWatchlist, &["7"]),
+    (Context::List, Action::Search, &["/"]),
+    (Context::List, Action::Sort, &["s"]),
+    (Context::List, Action::Filters, &["F"]),
+    (Context::List, Action::QuickFilter, &["f"]),
+    (Context::List, Action::Add, &["a"]),
+    (Context::List, Action::Remove, &["d"]),
+    (Context::List, Action::SaveSet, &["S"]),
+    (Context::List, Action::OpenSets, &["o"]),
+    (Context::List, Action::Columns, &["C"]),
+    (Context::List, Action::Mark, &["m"]),
+    (Context::List, Action::SideBySide, &["v"]),
+    (Context::List, Action::Expand, &["space"]),
+    (
+        Context::List,
+        Action::SwitchPane,
+        &["h", "l", "left", "right"],
+    ),
+    (Context::List, Action::Watch, &["w"]),
+    (Context::List, Action::CheckWatchlist, &["c"]),
+    (Context::List, Action::MarkRead, &["r"]),
+    (Context::List, Action::Quit, &["q"]),
+    (Context::Columns, Action::Down, &["j", "down"]),
+    (Context::Columns, Action::Up, &["k", "up"]),
+    (Context::Columns, Action::Expand, &["space"]),
+    (Context::Columns, Action::MoveDown, &["J"]),
+    (Context::Columns, Action::MoveUp, &["K"]),
+    (Context::Columns, Action::Sort, &["s"]),
+    (Context::Columns, Action::Back, &["esc", "enter"]),
+    (Context::Columns, Action::Columns, &["C"]),
+    (Context::Sets, Action::Down, &["j", "down"]),
+    (Context::Sets, Action::Up, &["k", "up"]),
+    (Context::Sets, Action::Open, &["enter"]),
+    (Context::Sets, Action::Rename, &["r"]),
+    (Context::Sets, Action::Remove, &["d"]),
+    (Context::Sets, Action::Back, &["esc"]),
+    (Context::Sets, Action::OpenSets, &["o"]),
+    (Context::Detail, Action::Down, &["j", "down"]),
+    (Context::Detail, Action::Up, &["k", "up"]),
+    (Context::Detail, Action::PageDown, &["pagedown"]),
+    (Context::Detail, Action::PageUp, &["pageup"]),
+    (Context::Detail, Action::Versions, &["v"]),
+    (Context::Detail, Action::Readme, &["R"]),
+    (Context::Detail, Action::Api, &["A"]),
+    (Context::Detail, Action::Watch, &["w"]),
+    (Context::Detail, Action::AcknowledgeOwners, &["a"]),
+    (Context::Detail, Action::NextTag, &["t"]),
+    (Context::Detail, Action::PrevTag, &["T"]),
+    (Context::Detail, Action::Open, &["enter"]),
+    (Context::Detail, Action::OpenLink(Link::Docs), &["o d"]),
+    (
+        Context::Detail,
+        Action::OpenLink(Link::Repository),
+        &["o r"],
+    ),
+    (Context::Detail, Action::OpenLink(Link::CratesIo), &["o c"]),
+    (Context::Detail, Action::OpenLink(Link::Homepage), &["o h"]),
+    (Context::Detail, Action::CopySnippet, &["y y"]),
+    (Context::Detail, Action::CopyLink(Link::Docs), &["y d"]),
+    (
+        Context::Detail,
+        Action::CopyLink(Link::Repository),
+        &["y r"],
+    ),
+    (Context::Detail, Action::CopyLink(Link::CratesIo), &["y c"]),
+    (Context::Detail, Action::CopyLink(Link::Homepage), &["y h"]),
+    (Context::Detail, Action::Back, &["esc"]),
+    (Context::Detail, Action::Quit, &["q"]),
+    (Context::SideBySide, Action::Down, &["j", "down"]),
+    (Context::SideBySide, Action::Up, &["k", "up"]),
+    (Context::SideBySide, Action::PageDown, &["pagedown"]),
+    (Context::SideBySide, Action::PageUp, &["pageup"]),
+    (Context::SideBySide, Action::Top, &["g g", "home"]),
+    (Context::SideBySide, Action::Bottom, &["G", "end"]),
+    (Context::SideBySide, Action::Back, &["esc"]),
+    (Context::SideBySide, Action::Quit, &["q"]),
+    (Context::Readme, Action::Down, &["j", "down"]),
+    (Context::Readme, Action::Up, &["k", "up"]),
+    (Context::Readme, Action::PageDown, &["pagedown"]),
+    (Context::Readme, Action::PageUp, &["pageup"]),
+    (Context::Readme, Action::Top, &["g g", "home"]),
+    (Context::Readme, Action::Bottom, &["G", "end"]),
+    (Context::Readme, Action::Back, &["esc"]),
+    (Context::Readme, Action::Quit, &["q"]),
+    (Context::Api, Action::Down, &["j", "down"]),
+    (Context::Api, Action::Up, &["k", "up"]),
+    (Context::Api, Action::Top, &["g g", "home"]),
+    (Context::Api, Action::Bottom, &["G", "end"]),
+    (Context::Api, Action::Expand, &["space"]),
+    (Context::Api, Action::Open, &["enter"]),
+    (Context::Api, Action::Search, &["/"]),
+    (Context::Api, Action::ScrollDown, &["J"]),
+    (Context::Api, Action::ScrollUp, &["K"]),
+    (Context::Api, Action::PageDown, &["pagedown"]),
+    (Context::Api, Action::PageUp, &["pageup"]),
+    (Context::Api, Action::Back, &["esc"]),
+    (Context::Api, Action::Quit, &["q"]),
+    (Context::Versions, Action::Down, &["j", "down"]),
+    (Context::Versions, Action::Up, &["k", "up"]),
+    (Context::Versions, Action::Top, &["g g", "home"]),
+    (Context::Versions, Action::Bottom, &["G", "end"]),
+    (Context::Versions, Action::Mark, &["space"]),
+    (Context::Versions, Action::Diff, &["d"]),
+    (Context::Versions, Action::Back, &["esc"]),
+    (Context::Versions, Action::Quit, &["q"]),
+    (Context::Diff, Action::Down, &["j", "down"]),
+    (Context::Diff, Action::Up, &["k", "up"]),
+    (Context::Diff, Action::ScrollDown, &["J"]),
+    (Context::Diff, Action::ScrollUp, &["K"]),
+    (Context::Diff, Action::PageDown, &["pagedown"]),
+    (Context::Diff, Action::PageUp, &["pageup"]),
+    (Context::Diff, Action::Back, &["esc"]),
+    (Context::Diff, Action::Quit, &["q"]),
+];
+
+/// A single key with the modifiers that matter for bindings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyPress {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl From<KeyEvent> for KeyPress {
+    fn from(key: KeyEvent) -> Self {
+        // Shift is already part of the character (`G`, `?`), and terminals
+        // differ in whether they report it
+        KeyPress {
+            code: key.code,
+            modifiers: key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT),
+        }
+    }
+}
+
+impl KeyPress {
+    fn parse(text: &str) -> Result<KeyPress, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text;
+        loop {
+            if let Some(r) = rest.strip_prefix("ctrl-") {
+                modifiers |= KeyModifiers::CONTROL;
+                rest = r;
+            } else if let Some(r) = rest.strip_prefix("alt-") {
+                modifiers |= KeyModifiers::ALT;
+                rest = r;
+            } else {
+                break;
+            }
+        }
+
+        if let Some(n) = rest.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+            return Ok(KeyPress {
+                code: KeyCode::F(n),
+                modifiers,
+            });
+        }
+
+        let code = match rest {
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "space" => KeyCode::Char(' '),
+            "backspace" => KeyCode::Backspace,
+            "delete" => KeyCode::Delete,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            _ => {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return Err(format!("unknown key \"{}\"", text)),
+                }
+            }
+        };
+
+        Ok(KeyPress { code, modifiers })
+    }
+}
+
+impl fmt::Display for KeyPress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "enter"),
+            KeyCode::Esc => write!(f, "esc"),
+            KeyCode::Tab => write!(f, "tab"),
+            KeyCode::BackTab => write!(f, "backtab"),
+            KeyCode::Backspace => write!(f, "backspace"),
+            KeyCode::Delete => write!(f, "delete"),
+            KeyCode::Up => write!(f, "up"),
+            KeyCode::Down => write!(f, "down"),
+            KeyCode::Left => write!(f, "left"),
+            KeyCode::Right => write!(f, "right"),
+            KeyCode::Home => write!(f, "home"),
+            KeyCode::End => write!(f, "end"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            _ => write!(f, "?"),
+        }
+    }
+}
+
+fn parse_sequence(text: &str) -> Result<Vec<KeyPress>, String> {
+    let keys = text
+        .split_whitespace()
+        .map(KeyPress::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+    if keys.is_empty() {
+        return Err("empty key".to_string());
+    }
+    Ok(keys)
+}
+
+/// A key sequence as written in the config file, e.g. `g g`
+pub fn show_sequence(keys: &[KeyPress]) -> String {
+    keys.iter()
+        .map(|k| k.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+struct Binding {
+    context: Context,
+    keys: Vec<KeyPress>,
+    action: Action,
+}
+
+/// Outcome of looking up the keys pressed so far
+#[derive(Debug, PartialEq)]
+pub enum Lookup {
+    Action(Action),
+    /// The keys start a longer sequence; wait for the next one
+    Pending,
+    None,
+}
+
+/// Which action each key (or key sequence) triggers in each context
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap::new(&BTreeMap::new()).unwrap_or(Keymap {
+            bindings: Vec::new(),
+        })
+    }
+}
+
+impl Keymap {
+    /// The default bindings with the user's overrides applied. An override
+    /// replaces the action's keys in every context the action is used in.
+    pub fn new(overrides: &BTreeMap<String, Vec<String>>) -> Result<Keymap, String> {
+        for name in overrides.keys() {
+            if Action::from_name(name).is_none() {
+                return Err(format!("keys.{} is not a known action", name));
+            }
+        }
+
+        let mut bindings = Vec::new();
+        for (context, action, defaults) in DEFAULTS {
+            let keys: Vec<&str> = match overrides.get(action.name()) {
+                Some(keys) => keys.iter().map(String::as_str).collect(),
+                None => defaults.to_vec(),
+            };
+            for key in keys {
+                let keys =
+                    parse_sequence(key).map_err(|e| format!("keys.{}: {}", action.name(), e))?;
+                bindings.push(Binding {
+                    context: *context,
+                    keys,
+                    action: *action,
+                });
+            }
+        }
+
+        let keymap = Keymap { bindings };
+        keymap.check_conflicts()?;
+        Ok(keymap)
+    }
+
+    // A sequence bound twice, or one that starts another, would make a binding unreachable
+    fn check_conflicts(&self) -> Result<(), String> {
+        for (i, a) in self.bindings.iter().enumerate() {
+            for b in &self.bindings[i + 1..] {
+                if a.context != b.context || a.action == b.action {
+                    continue;
+                }
+                let (short, long) = if a.keys.len() <= b.keys.len() {
+                    (a, b)
+                } else {
+                    (b, a)
+                };
+                if long.keys.starts_with(&short.keys) {
+                    return Err(format!(
+                        "\"{}\" for {} conflicts with \"{}\" for {} in the {}",
+                        show_sequence(&short.keys),
+                        short.action.name(),
+                        show_sequence(&long.keys),
+                        long.action.name(),
+                        short.context.label().to_lowercase()
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn lookup(&self, context: Context, pressed: &[KeyPress]) -> Lookup {
+        let mut pending = false;
+        for binding in self.bindings.iter().filter(|b| b.context == context) {
+            if binding.keys == pressed {
+                return Lookup::Action(binding.action);
+            }
+            if binding.keys.starts_with(pressed) {
+                pending = true;
+            }
+        }
+        if pending {
+            Lookup::Pending
+        } else {
+            Lookup::None
+        }
+    }
+
+    /// The keys bound to an action, as written in the config file
+    pub fn keys(&self, context: Context, action: Action) -> Vec<String> {
+        self.bindings
+            .iter()
+            .filter(|b| b.context == context && b.action == action)
+            .map(|b| show_sequence(&b.keys))
+            .collect()
+    }
+
+    /// The first key bound to an action, for hints like "a to add"
+    pub fn hint(&self, context: Context, action: Action) -> String {
+        self.keys(context, action)
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| "(unbound)".to_string())
+    }
+
+    /// Actions of a context with their keys, in help order
+    pub fn actions(&self, context: Context) -> Vec<(Action, Vec<String>)> {
+        let mut actions: Vec<(Action, Vec<String>)> = Vec::new();
+        for binding in self.bindings.iter().filter(|b| b.context == context) {
+            if !actions.iter().any(|(action, _)| *action == binding.action) {
+                actions.push((binding.action, self.keys(context, binding.action)));
+            }
+        }
+        actions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(text: &str) -> Vec<KeyPress> {
+        parse_sequence(text).unwrap()
+    }
+
+    fn overrides(pairs: &[(&str, &[&str])]) -> BTreeMap<String, Vec<String>> {
+        pairs
+            .iter()
+            .map(|(name, keys)| {
+                (
+                    name.to_string(),
+                    keys.iter().map(|k| k.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn defaults_have_no_conflicts() {
+        assert!(Keymap::new(&BTreeMap::new()).is_ok());
+    }
+
+    #[test]
+    fn sequences_wait_for_their_last_key() {
+        let keymap = Keymap::default();
+        assert_eq!(keymap.lookup(Context::List, &keys("g")), Lookup::Pending);
+        assert_eq!(
+            keymap.lookup(Context::List, &keys("g g")),
+            Lookup::Action(Action::Top)
+        );
+        // A key that breaks the sequence is looked up on its own by the caller
+        assert_eq!(keymap.lookup(Context::List, &keys("g j")), Lookup::None);
+        assert_eq!(
+            keymap.lookup(Context::List, &keys("j")),
+            Lookup::Action(Action::Down)
+        );
+    }
+
+    #[test]
+    fn lookup_is_per_context() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.lookup(Context::Detail, &keys("o d")),
+            Lookup::Action(Action::OpenLink(Link::Docs))
+        );
+        assert_eq!(
+            keymap.lookup(Context::List, &keys("o")),
+            Lookup::Action(Action::OpenSets)
+        );
+        assert_eq!(keymap.lookup(Context::Diff, &keys("g")), Lookup::None);
+    }
+
+    #[test]
+    fn a_key_that_starts_a_sequence_conflicts() {
+        let err = Keymap::new(&overrides(&[("bottom", &["g"])]))
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            "\"g\" for bottom conflicts with \"g g\" for top in the lists"
+        );
+    }
+
+    #[test]
+    fn a_rebind_onto_another_action_conflicts() {
+        let err = Keymap::new(&overrides(&[("sort", &["j"])])).err().unwrap();
+        assert!(err.contains("\"j\" for "), "{}", err);
+        assert!(err.contains("down") && err.contains("sort"), "{}", err);
+
+        // The same key in different contexts is fine
+        assert!(Keymap::new(&overrides(&[("versions", &["x"]), ("sort", &["x"])])).is_ok());
+    }
+
+    #[test]
+    fn overrides_replace_the_default_keys() {
+        let keymap = Keymap::new(&overrides(&[("down", &["ctrl-n", "n"])])).unwrap();
+        assert_eq!(keymap.keys(Context::Diff, Action::Down), ["ctrl-n", "n"]);
+        assert_eq!(keymap.lookup(Context::List, &keys("j")), Lookup::None);
+        assert_eq!(keymap.hint(Context::List, Action::Down), "ctrl-n");
+    }
+
+    #[test]
+    fn overrides_are_validated() {
+        assert_eq!(
+            Keymap::new(&overrides(&[("jump", &["x"])])).err().unwrap(),
+            "keys.jump is not a known action"
+        );
+        assert_eq!(
+            Keymap::new(&overrides(&[("down", &["ctrl-"])]))
+                .err()
+                .unwrap(),
+            "keys.down: unknown key \"ctrl-\""
+        );
+        assert_eq!(
+            Keymap::new(&overrides(&[("down", &[" "])])).err().unwrap(),
+            "keys.down: empty key"
+        );
+    }
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        for text in [
+            "j",
+            "G",
+            "?",
+            "ctrl-d",
+            "alt-x",
+            "ctrl-alt-enter",
+            "space",
+            "f5",
+            "pagedown",
+        ] {
+            assert_eq!(KeyPress::parse(text).unwrap().to_string(), text);
+        }
+        assert_eq!(show_sequence(&keys("g  g")), "g g");
+    }
+
+    #[test]
+    fn parse_rejects_unknown_keys() {
+        for text in ["", "jj", "ctrl", "shift-a", "f1x", "pgdn"] {
+            assert!(KeyPress::parse(text).is_err(), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn shift_is_part_of_the_character() {
+        let pressed = KeyPress::from(KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT));
+        assert_eq!(pressed, KeyPress::parse("G").unwrap());
+
+        let pressed = KeyPress::from(KeyEvent::new(
+            KeyCode::Char('d'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        ));
+        assert_eq!(pressed, KeyPress::parse("ctrl-d").unwrap());
+    }
+}
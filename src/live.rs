@@ -0,0 +1,139 @@
+use crate::api::{self, CratesPage, SortOrder};
+use crate::config;
+use crate::query::Query;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+// Names shown in the autocomplete dropdown
+const SUGGESTIONS: usize = 8;
+
+/// Work for the background search thread
+struct Request {
+    generation: u64,
+    /// Results to fetch, or `None` when only suggestions are wanted
+    query: Option<(Query, SortOrder)>,
+    /// Crate name prefix to complete
+    prefix: Option<String>,
+}
+
+pub enum Response {
+    Results {
+        generation: u64,
+        query: Query,
+        sort: SortOrder,
+        page: Result<CratesPage, String>,
+    },
+    Suggestions {
+        generation: u64,
+        names: Vec<String>,
+    },
+}
+
+/// Runs searches off the UI thread while the user types. Every request gets a
+/// new generation number; responses from older generations are stale and
+/// dropped by `poll`.
+pub struct LiveSearch {
+    requests: Sender<Request>,
+    responses: Receiver<Response>,
+    generation: u64,
+    pub pending: bool,
+}
+
+impl LiveSearch {
+    pub fn spawn() -> Self {
+        let (request_sender, request_receiver) = mpsc::channel::<Request>();
+        let (response_sender, response_receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            while let Ok(mut request) = request_receiver.recv() {
+                // Only the newest request matters; skip anything typed over
+                while let Ok(newer) = request_receiver.try_recv() {
+                    request = newer;
+                }
+
+                if let Some((query, sort)) = request.query {
+                    let page =
+                        api::search_crates(&query.params, sort, 1, config::get().general.page_size)
+                            .map_err(|e| e.to_string());
+                    let response = Response::Results {
+                        generation: request.generation,
+                        query,
+                        sort,
+                        page,
+                    };
+                    if response_sender.send(response).is_err() {
+                        break;
+                    }
+                }
+
+                if let Some(prefix) = request.prefix {
+                    let names = api::suggest_crate_names(&prefix, SUGGESTIONS).unwrap_or_default();
+                    let response = Response::Suggestions {
+                        generation: request.generation,
+                        names,
+                    };
+                    if response_sender.send(response).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Self {
+            requests: request_sender,
+            responses: response_receiver,
+            generation: 0,
+            pending: false,
+        }
+    }
+
+    /// A live search without a thread. Nothing answers its requests unless
+    /// responses are sent through the returned sender, as tests do.
+    pub fn detached() -> (Self, Sender<Response>) {
+        let (requests, _) = mpsc::channel();
+        let (response_sender, responses) = mpsc::channel();
+        let live = Self {
+            requests,
+            responses,
+            generation: 0,
+            pending: false,
+        };
+        (live, response_sender)
+    }
+
+    /// Start a search, superseding any request still in flight
+    pub fn request(&mut self, query: Option<(Query, SortOrder)>, prefix: Option<String>) {
+        self.generation += 1;
+        self.pending = query.is_some();
+        // If the thread is gone live search just stops; Enter still searches
+        let _ = self.requests.send(Request {
+            generation: self.generation,
+            query,
+            prefix,
+        });
+    }
+
+    /// Discard whatever is in flight, e.g. after a search submitted with Enter
+    pub fn cancel(&mut self) {
+        self.generation += 1;
+        self.pending = false;
+    }
+
+    /// The next response for the latest request, if one has arrived
+    pub fn poll(&mut self) -> Option<Response> {
+        while let Ok(response) = self.responses.try_recv() {
+            let generation = match response {
+                Response::Results { generation, .. } => generation,
+                Response::Suggestions { generation, .. } => generation,
+            };
+            if generation != self.generation {
+                continue;
+            }
+            if matches!(response, Response::Results { .. }) {
+                self.pending = false;
+            }
+            return Some(response);
+        }
+        None
+    }
+}
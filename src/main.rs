@@ -1,39 +1,115 @@
 mod api;
 mod app;
+mod browser;
+mod cli;
+mod clipboard;
+mod columns;
+mod config;
+mod diff;
 mod event;
+mod filter;
+mod fuzzy;
+mod keymap;
+mod live;
+mod markdown;
+mod mouse;
+mod msrv;
+mod policy;
+mod query;
+mod readme;
+mod rustdoc;
+mod score;
+mod session;
+mod sets;
+mod store;
+mod text;
+mod theme;
 mod ui;
+mod watch;
 
 use app::{App, AppResult};
+use config::Config;
 use event::{Event, EventHandler};
+use policy::Policy;
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io;
+use session::Session;
+use std::{env, io, process};
+use theme::Theme;
 
 fn main() -> AppResult<()> {
+    // Headless commands run without touching the terminal
+    let args: Vec<String> = env::args().skip(1).collect();
+    let fresh = args == ["--fresh"];
+
+    // `crates config` loads the file itself so a broken config can still be fixed
+    if args.first().map(String::as_str) != Some("config") {
+        match Config::load() {
+            Ok(config) => config::init(config),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                process::exit(2);
+            }
+        }
+    }
+
+    if !args.is_empty() && !fresh {
+        match cli::run(&args) {
+            Ok(code) => process::exit(code),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                process::exit(2);
+            }
+        }
+    }
+
+    // Load the policy before entering raw mode so errors are readable
+    let policy = Policy::load().unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        process::exit(2);
+    });
+    match Theme::load(&config::get().general.theme) {
+        Ok(theme) => theme::init(theme),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(2);
+        }
+    }
+
     // Setup terminal
     let mut terminal = setup_terminal()?;
 
     // Create app state
-    let mut app = App::new();
+    let session = if fresh { None } else { Session::load() };
+    let mut app = App::new(policy, session);
 
     // Initialize event handler
-    let events = EventHandler::new(250);
+    let events = EventHandler::new(config::get().general.tick_rate_ms);
+
+    let hyperlinks = browser::hyperlinks_enabled();
 
     // Main loop
     while app.running {
         // Draw UI
-        terminal.draw(|f| ui::draw(f, &mut app))?;
+        let frame = terminal.draw(|f| ui::draw(f, &mut app))?;
+        if hyperlinks {
+            browser::write_hyperlinks(&mut io::stdout(), &app.hits, frame.buffer)?;
+        }
 
         // Handle events
         match events.next()? {
             Event::Tick => app.tick(),
             Event::Key(key_event) => app.handle_key_event(key_event),
-            Event::Mouse(_) => {}
+            Event::Mouse(mouse_event) => app.handle_mouse_event(mouse_event),
             Event::Resize(_, _) => {}
         }
     }
 
     // Cleanup and restore terminal
     restore_terminal(&mut terminal)?;
+
+    if let Err(e) = app.session().save() {
+        eprintln!("warning: could not save session: {}", e);
+    }
     Ok(())
 }
 
@@ -0,0 +1,1749 @@
+use crate::text;
+use crate::theme;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use std::collections::HashMap;
+use unicode_width::UnicodeWidthChar;
+
+// A small renderer for the markdown found in READMEs: headings, paragraphs,
+// emphasis, code spans, fenced and indented code, block quotes, nested and
+// task lists, and GFM tables. Inline HTML is reduced to its text, and links
+// are numbered like footnotes with their URLs listed at the end, since the
+// terminal can't hide a URL behind its text.
+
+// Narrower panes still render, just with very short lines
+const MIN_WIDTH: usize = 20;
+// Table columns are never squeezed below this
+const MIN_COLUMN: usize = 5;
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "yield",
+];
+
+const RUST_PRIMITIVES: &[&str] = &[
+    "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64",
+    "i128", "isize", "f32", "f64",
+];
+
+// HTML tags that start a new line when READMEs use them for layout
+const BLOCK_TAGS: &[&str] = &[
+    "p",
+    "div",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "li",
+    "ul",
+    "ol",
+    "tr",
+    "table",
+    "details",
+    "summary",
+    "hr",
+    "blockquote",
+    "pre",
+    "center",
+    "section",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug)]
+enum Block {
+    Heading(usize, String),
+    /// Text with `\n` marking hard line breaks
+    Paragraph(String),
+    Code {
+        lang: String,
+        lines: Vec<String>,
+    },
+    Quote(Vec<Block>),
+    /// `start` is the first number of an ordered list
+    List {
+        start: Option<u64>,
+        items: Vec<Item>,
+    },
+    /// The first row is the header
+    Table {
+        aligns: Vec<Align>,
+        rows: Vec<Vec<String>>,
+    },
+    Html(String),
+    Rule,
+}
+
+#[derive(Debug)]
+struct Item {
+    /// Whether a task list item is done
+    task: Option<bool>,
+    /// Without blank lines inside, the item's blocks are drawn without gaps
+    tight: bool,
+    blocks: Vec<Block>,
+}
+
+// Link reference definitions, by normalised label
+type Refs = HashMap<String, String>;
+
+// Text with its style; a lone "\n" is a line break
+type Piece = (String, Style);
+
+/// Render markdown as lines at most `width` columns wide (code blocks are
+/// left for the pane to clip), followed by the footnoted link URLs
+pub fn render(source: &str, width: usize) -> Vec<Line<'static>> {
+    let theme = theme::get();
+    let width = width.max(MIN_WIDTH);
+    let lines: Vec<String> = source.lines().map(|l| l.replace('\t', "    ")).collect();
+
+    let mut refs = Refs::new();
+    let blocks = parse(&lines, &mut refs);
+    let mut renderer = Renderer {
+        refs,
+        footnotes: Vec::new(),
+    };
+    let mut out = renderer.blocks(&blocks, width);
+
+    if !renderer.footnotes.is_empty() {
+        out.push(Line::default());
+        out.push(Line::from(Span::styled("Links", heading_style(2))));
+        for (i, url) in renderer.footnotes.iter().enumerate() {
+            // Long URLs continue under the first line's URL
+            let number = format!("[{}] ", i + 1);
+            let indent = number.len();
+            let url = vec![(
+                text::single_line(url),
+                theme.fg(theme.accent).add_modifier(Modifier::UNDERLINED),
+            )];
+            out.extend(prefixed(
+                wrap(url, width - indent),
+                Span::styled(number, theme.fg(theme.muted)),
+                Span::raw(" ".repeat(indent)),
+            ));
+        }
+    }
+    out
+}
+
+/// Render plain text, breaking lines that are wider than `width` columns
+pub fn render_plain(source: &str, width: usize) -> Vec<Line<'static>> {
+    let theme = theme::get();
+    let width = width.max(MIN_WIDTH);
+    let mut out = Vec::new();
+    for line in source.lines() {
+        let mut chunk = String::new();
+        let mut chunk_width = 0;
+        for c in text::single_line(line).chars() {
+            let char_width = c.width().unwrap_or(0);
+            if chunk_width + char_width > width {
+                out.push(Line::from(Span::styled(
+                    std::mem::take(&mut chunk),
+                    theme.fg(theme.text),
+                )));
+                chunk_width = 0;
+            }
+            chunk.push(c);
+            chunk_width += char_width;
+        }
+        out.push(Line::from(Span::styled(chunk, theme.fg(theme.text))));
+    }
+    out
+}
+
+// Block structure
+
+fn parse(lines: &[String], refs: &mut Refs) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = &lines[i];
+        if line.trim().is_empty() {
+            i += 1;
+            continue;
+        }
+        let indent = indent_of(line);
+        let rest = line.trim_start();
+
+        if indent >= 4 {
+            let mut code = Vec::new();
+            while i < lines.len() && (lines[i].trim().is_empty() || indent_of(&lines[i]) >= 4) {
+                code.push(strip_indent(&lines[i], 4).to_string());
+                i += 1;
+            }
+            while code.last().is_some_and(|l| l.trim().is_empty()) {
+                code.pop();
+            }
+            blocks.push(Block::Code {
+                lang: String::new(),
+                lines: code,
+            });
+            continue;
+        }
+
+        if let Some((fence, info)) = fence_open(rest) {
+            let lang = info
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .next()
+                .unwrap_or_default()
+                .trim_start_matches(['{', '.'])
+                .to_lowercase();
+            let fence_char = fence.chars().next().unwrap_or('`');
+            let mut code = Vec::new();
+            i += 1;
+            while i < lines.len() {
+                let inner = lines[i].trim_start();
+                if indent_of(&lines[i]) < 4
+                    && inner.starts_with(fence.as_str())
+                    && inner.trim_start_matches(fence_char).trim().is_empty()
+                {
+                    i += 1;
+                    break;
+                }
+                code.push(strip_indent(&lines[i], indent).to_string());
+                i += 1;
+            }
+            blocks.push(Block::Code { lang, lines: code });
+            continue;
+        }
+
+        if let Some((level, text)) = atx_heading(rest) {
+            blocks.push(Block::Heading(level, text));
+            i += 1;
+            continue;
+        }
+
+        if is_rule(rest) {
+            blocks.push(Block::Rule);
+            i += 1;
+            continue;
+        }
+
+        if rest.starts_with('>') {
+            let mut quoted = Vec::new();
+            while let Some(inner) = lines.get(i).and_then(|l| l.trim_start().strip_prefix('>')) {
+                quoted.push(inner.strip_prefix(' ').unwrap_or(inner).to_string());
+                i += 1;
+            }
+            blocks.push(Block::Quote(parse(&quoted, refs)));
+            continue;
+        }
+
+        if rest.starts_with("<!--") {
+            while i < lines.len() && !lines[i].contains("-->") {
+                i += 1;
+            }
+            i += 1;
+            continue;
+        }
+
+        if is_html_start(rest) {
+            let mut html = Vec::new();
+            while i < lines.len() && !lines[i].trim().is_empty() {
+                html.push(lines[i].trim());
+                i += 1;
+            }
+            blocks.push(Block::Html(html.join(" ")));
+            continue;
+        }
+
+        if list_marker(line).is_some() {
+            let (list, next) = parse_list(lines, i, refs);
+            blocks.push(list);
+            i = next;
+            continue;
+        }
+
+        if rest.contains('|') {
+            if let Some(aligns) = lines.get(i + 1).and_then(|l| table_delimiter(l)) {
+                let header = split_row(rest);
+                if header.len() == aligns.len() {
+                    let mut rows = vec![header];
+                    i += 2;
+                    while i < lines.len()
+                        && !lines[i].trim().is_empty()
+                        && !interrupts(lines[i].trim_start())
+                    {
+                        let mut row = split_row(lines[i].trim());
+                        row.resize(aligns.len(), String::new());
+                        rows.push(row);
+                        i += 1;
+                    }
+                    blocks.push(Block::Table { aligns, rows });
+                    continue;
+                }
+            }
+        }
+
+        if let Some((label, url)) = link_definition(rest) {
+            refs.insert(label, url);
+            i += 1;
+            continue;
+        }
+
+        // Anything else is a paragraph, or a setext heading if underlined
+        let mut text = String::new();
+        let mut hard_break = false;
+        let mut heading = None;
+        while i < lines.len() {
+            let line = &lines[i];
+            let rest = line.trim_start();
+            if rest.is_empty() {
+                break;
+            }
+            if !text.is_empty() && indent_of(line) < 4 {
+                if let Some(level) = setext_level(rest) {
+                    heading = Some(level);
+                    i += 1;
+                    break;
+                }
+                if interrupts(rest) || list_marker(line).is_some_and(|m| m.content < line.len()) {
+                    break;
+                }
+            }
+
+            if !text.is_empty() {
+                text.push(if hard_break { '\n' } else { ' ' });
+            }
+            let trimmed = rest.trim_end();
+            hard_break = line.ends_with("  ") || trimmed.ends_with('\\');
+            text.push_str(trimmed.strip_suffix('\\').unwrap_or(trimmed));
+            i += 1;
+        }
+
+        blocks.push(match heading {
+            Some(level) => Block::Heading(level, text),
+            None => Block::Paragraph(text),
+        });
+    }
+
+    blocks
+}
+
+struct Marker {
+    number: Option<u64>,
+    /// `-`, `*` or `+` for bullets, `.` or `)` after a number
+    delim: char,
+    /// Column the item's content starts at
+    content: usize,
+}
+
+fn list_marker(line: &str) -> Option<Marker> {
+    let indent = indent_of(line);
+    if indent >= 4 {
+        return None;
+    }
+    let rest = &line[indent..];
+
+    let (number, marker_len, delim) = match rest.chars().next() {
+        Some(c @ ('-' | '*' | '+')) => (None, 1, c),
+        _ => {
+            let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+            if digits == 0 || digits > 9 {
+                return None;
+            }
+            let delim = rest[digits..]
+                .chars()
+                .next()
+                .filter(|c| matches!(c, '.' | ')'))?;
+            (rest[..digits].parse().ok(), digits + 1, delim)
+        }
+    };
+
+    let after = &rest[marker_len..];
+    if !after.is_empty() && !after.starts_with(' ') {
+        return None;
+    }
+    // Content indented by more than four spaces is code inside the item
+    let spaces = after.chars().take_while(|c| *c == ' ').count();
+    let spaces = if after.trim().is_empty() || spaces > 4 {
+        1
+    } else {
+        spaces
+    };
+
+    Some(Marker {
+        number,
+        delim,
+        content: indent + marker_len + spaces,
+    })
+}
+
+fn parse_list(lines: &[String], mut i: usize, refs: &mut Refs) -> (Block, usize) {
+    let first = match list_marker(&lines[i]) {
+        Some(marker) => marker,
+        None => return (Block::Paragraph(lines[i].clone()), i + 1),
+    };
+    let ordered = first.number.is_some();
+    let mut items = Vec::new();
+
+    while i < lines.len() {
+        let Some(marker) = list_marker(&lines[i]) else {
+            break;
+        };
+        if marker.delim != first.delim || is_rule(lines[i].trim_start()) {
+            break;
+        }
+
+        let mut item = vec![lines[i]
+            .get(marker.content..)
+            .unwrap_or_default()
+            .to_string()];
+        i += 1;
+        while i < lines.len() {
+            let line = &lines[i];
+            if line.trim().is_empty() {
+                item.push(String::new());
+            } else if indent_of(line) >= marker.content {
+                item.push(line[marker.content..].to_string());
+            } else if !item.last().is_some_and(|l| l.is_empty())
+                && list_marker(line).is_none()
+                && !interrupts(line.trim_start())
+            {
+                // A lazy continuation of the item's paragraph
+                item.push(line.trim_start().to_string());
+            } else {
+                break;
+            }
+            i += 1;
+        }
+        while item.last().is_some_and(|l| l.is_empty()) {
+            item.pop();
+        }
+
+        // Blank lines only inside a nested block leave this item tight
+        let tight = !item
+            .windows(2)
+            .any(|pair| pair[0].is_empty() && !pair[1].is_empty() && indent_of(&pair[1]) == 0);
+        let mut blocks = parse(&item, refs);
+        let task = match blocks.first_mut() {
+            Some(Block::Paragraph(text)) if !ordered => take_task(text),
+            _ => None,
+        };
+        items.push(Item {
+            task,
+            tight,
+            blocks,
+        });
+    }
+
+    (
+        Block::List {
+            start: first.number,
+            items,
+        },
+        i,
+    )
+}
+
+// Strip a `[ ]` or `[x]` task box from the start of an item
+fn take_task(text: &mut String) -> Option<bool> {
+    for (prefix, done) in [("[ ] ", false), ("[x] ", true), ("[X] ", true)] {
+        if let Some(rest) = text.strip_prefix(prefix) {
+            *text = rest.to_string();
+            return Some(done);
+        }
+    }
+    None
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn strip_indent(line: &str, indent: usize) -> &str {
+    let strip = indent_of(line).min(indent);
+    &line[strip..]
+}
+
+// The fence and info string of a line opening a fenced code block
+fn fence_open(rest: &str) -> Option<(String, &str)> {
+    let fence_char = rest.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let len = rest.chars().take_while(|c| *c == fence_char).count();
+    if len < 3 {
+        return None;
+    }
+    let info = rest[len..].trim();
+    if fence_char == '`' && info.contains('`') {
+        return None;
+    }
+    Some((rest[..len].to_string(), info))
+}
+
+fn atx_heading(rest: &str) -> Option<(usize, String)> {
+    let level = rest.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let text = &rest[level..];
+    if !text.is_empty() && !text.starts_with(' ') {
+        return None;
+    }
+    let text = text.trim();
+    // A closing run of #s is decoration, unless it is part of a word
+    let stripped = text.trim_end_matches('#');
+    let text = if stripped.is_empty() || stripped.ends_with(' ') {
+        stripped.trim_end()
+    } else {
+        text
+    };
+    Some((level, text.to_string()))
+}
+
+fn setext_level(rest: &str) -> Option<usize> {
+    let rest = rest.trim_end();
+    if !rest.is_empty() && rest.chars().all(|c| c == '=') {
+        Some(1)
+    } else if !rest.is_empty() && rest.chars().all(|c| c == '-') {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+fn is_rule(rest: &str) -> bool {
+    let Some(marker) = rest.chars().find(|c| !c.is_whitespace()) else {
+        return false;
+    };
+    matches!(marker, '-' | '*' | '_')
+        && rest.chars().all(|c| c == marker || c == ' ')
+        && rest.chars().filter(|c| *c == marker).count() >= 3
+}
+
+fn is_html_start(rest: &str) -> bool {
+    let mut chars = rest.chars();
+    chars.next() == Some('<')
+        && chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '/')
+}
+
+// Whether a line starts a block that ends the paragraph before it
+fn interrupts(rest: &str) -> bool {
+    fence_open(rest).is_some()
+        || atx_heading(rest).is_some()
+        || is_rule(rest)
+        || rest.starts_with('>')
+        || is_html_start(rest)
+}
+
+// The column alignments of a table's delimiter row, like `|:---|--:|`
+fn table_delimiter(line: &str) -> Option<Vec<Align>> {
+    if !line.contains('-') {
+        return None;
+    }
+    split_row(line.trim())
+        .iter()
+        .map(|cell| {
+            let left = cell.starts_with(':');
+            let right = cell.ends_with(':');
+            let dashes = cell.trim_matches(':');
+            if dashes.is_empty() || !dashes.chars().all(|c| c == '-') {
+                return None;
+            }
+            Some(match (left, right) {
+                (true, true) => Align::Center,
+                (false, true) => Align::Right,
+                _ => Align::Left,
+            })
+        })
+        .collect()
+}
+
+fn split_row(row: &str) -> Vec<String> {
+    let row = row.strip_prefix('|').unwrap_or(row);
+    let row = match row.strip_suffix('|') {
+        Some(inner) if !inner.ends_with('\\') => inner,
+        _ => row,
+    };
+
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = row.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '|' => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            c => cell.push(c),
+        }
+    }
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+// `[label]: url "title"`
+fn link_definition(rest: &str) -> Option<(String, String)> {
+    let inner = rest.strip_prefix('[')?;
+    let (label, after) = inner.split_once("]:")?;
+    if label.trim().is_empty() || label.contains(']') {
+        return None;
+    }
+    let url = after.split_whitespace().next()?;
+    let url = url.trim_start_matches('<').trim_end_matches('>');
+    Some((normalize_label(label), url.to_string()))
+}
+
+fn normalize_label(label: &str) -> String {
+    label
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+// Rendering
+
+struct Renderer {
+    refs: Refs,
+    /// Link targets in order of first use; a link's footnote is its index + 1
+    footnotes: Vec<String>,
+}
+
+impl Renderer {
+    // Blocks one after another, separated by blank lines
+    fn blocks(&mut self, blocks: &[Block], width: usize) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        for block in blocks {
+            let rendered = self.block(block, width);
+            if rendered.is_empty() {
+                continue;
+            }
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.extend(rendered);
+        }
+        lines
+    }
+
+    fn block(&mut self, block: &Block, width: usize) -> Vec<Line<'static>> {
+        let theme = theme::get();
+        match block {
+            Block::Heading(level, text) => wrap(self.inline(text, heading_style(*level)), width),
+            Block::Paragraph(text) => wrap(self.inline(text, theme.fg(theme.text)), width),
+            Block::Html(html) => {
+                let style = html_heading(html)
+                    .map(heading_style)
+                    .unwrap_or_else(|| theme.fg(theme.text));
+                wrap(self.inline(html, style), width)
+            }
+            Block::Code { lang, lines } => {
+                let syntax = Syntax::for_lang(lang);
+                let mut in_comment = false;
+                lines
+                    .iter()
+                    .map(|line| {
+                        let mut spans = vec![Span::styled("│ ", theme.fg(theme.faint))];
+                        spans.extend(syntax.highlight(&text::single_line(line), &mut in_comment));
+                        Line::from(spans)
+                    })
+                    .collect()
+            }
+            Block::Quote(blocks) => {
+                let bar = Span::styled("│ ", theme.fg(theme.muted));
+                let inner = self.blocks(blocks, width.saturating_sub(2));
+                prefixed(inner, bar.clone(), bar)
+            }
+            Block::List { start, items } => {
+                let last = start.map(|s| s + items.len().saturating_sub(1) as u64);
+                let marker_width = last.map_or(2, |n| n.to_string().len() + 2);
+
+                let mut lines = Vec::new();
+                for (k, item) in items.iter().enumerate() {
+                    let marker = match (start, item.task) {
+                        (Some(s), _) => Span::styled(
+                            format!("{:>w$}. ", s + k as u64, w = marker_width - 2),
+                            theme.fg(theme.accent),
+                        ),
+                        (None, Some(true)) => Span::styled("☑ ", theme.fg(theme.good)),
+                        (None, Some(false)) => Span::styled("☐ ", theme.fg(theme.muted)),
+                        (None, None) => Span::styled("• ", theme.fg(theme.accent)),
+                    };
+                    let width = width.saturating_sub(marker_width);
+                    let mut inner = if item.tight {
+                        item.blocks
+                            .iter()
+                            .flat_map(|block| self.block(block, width))
+                            .collect()
+                    } else {
+                        self.blocks(&item.blocks, width)
+                    };
+                    if inner.is_empty() {
+                        inner.push(Line::default());
+                    }
+                    lines.extend(prefixed(inner, marker, Span::raw(" ".repeat(marker_width))));
+                }
+                lines
+            }
+            Block::Table { aligns, rows } => self.table(aligns, rows, width),
+            Block::Rule => vec![Line::from(Span::styled(
+                "─".repeat(width),
+                theme.fg(theme.faint),
+            ))],
+        }
+    }
+
+    fn table(
+        &mut self,
+        aligns: &[Align],
+        rows: &[Vec<String>],
+        width: usize,
+    ) -> Vec<Line<'static>> {
+        let theme = theme::get();
+        let cells: Vec<Vec<Vec<Piece>>> = rows
+            .iter()
+            .enumerate()
+            .map(|(r, row)| {
+                let style = if r == 0 {
+                    theme.heading()
+                } else {
+                    theme.fg(theme.text)
+                };
+                row.iter()
+                    .map(|cell| {
+                        // Line breaks inside a cell would break the grid
+                        self.inline(cell, style)
+                            .into_iter()
+                            .map(|(text, style)| (text.replace('\n', " "), style))
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut widths = vec![0; aligns.len()];
+        for row in &cells {
+            for (column, cell) in row.iter().enumerate() {
+                widths[column] = widths[column].max(pieces_width(cell));
+            }
+        }
+
+        // Narrow the widest column until the table fits
+        let available = width.saturating_sub(3 * aligns.len().saturating_sub(1));
+        while widths.iter().sum::<usize>() > available {
+            let Some((widest, &w)) = widths.iter().enumerate().max_by_key(|(_, w)| **w) else {
+                break;
+            };
+            if w <= MIN_COLUMN {
+                break;
+            }
+            widths[widest] -= 1;
+        }
+
+        let mut lines = Vec::new();
+        for (r, row) in cells.iter().enumerate() {
+            let mut spans = Vec::new();
+            for (column, cell) in row.iter().enumerate() {
+                if column > 0 {
+                    spans.push(Span::styled(" │ ", theme.fg(theme.faint)));
+                }
+                let (fitted, used) = fit(cell, widths[column]);
+                let pad = widths[column] - used;
+                let (left, right) = match aligns[column] {
+                    Align::Left => (0, pad),
+                    Align::Right => (pad, 0),
+                    Align::Center => (pad / 2, pad - pad / 2),
+                };
+                spans.push(Span::raw(" ".repeat(left)));
+                spans.extend(fitted);
+                spans.push(Span::raw(" ".repeat(right)));
+            }
+            lines.push(Line::from(spans));
+
+            if r == 0 {
+                let rule = widths
+                    .iter()
+                    .map(|w| "─".repeat(*w))
+                    .collect::<Vec<_>>()
+                    .join("─┼─");
+                lines.push(Line::from(Span::styled(rule, theme.fg(theme.faint))));
+            }
+        }
+        lines
+    }
+
+    fn inline(&mut self, text: &str, style: Style) -> Vec<Piece> {
+        let mut inline = Inline {
+            refs: &self.refs,
+            footnotes: &mut self.footnotes,
+            pieces: Vec::new(),
+            anchors: Vec::new(),
+        };
+        let chars: Vec<char> = text.chars().collect();
+        inline.run(&chars, style);
+        inline.pieces
+    }
+}
+
+fn heading_style(level: usize) -> Style {
+    let theme = theme::get();
+    match level {
+        1 => theme
+            .fg(theme.title)
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+        2 => theme.heading().add_modifier(Modifier::UNDERLINED),
+        3 => theme.heading(),
+        _ => theme.fg(theme.heading).add_modifier(Modifier::ITALIC),
+    }
+}
+
+// The level of an HTML block that is a heading, like `<h1 align="center">`
+fn html_heading(html: &str) -> Option<usize> {
+    let mut chars = html.strip_prefix("<h")?.chars();
+    let level = chars.next()?.to_digit(10)? as usize;
+    let after = chars.next()?;
+    ((1..=6).contains(&level) && (after == '>' || after.is_whitespace())).then_some(level)
+}
+
+// Put `first` before the first line and `rest` before the others
+fn prefixed(
+    lines: Vec<Line<'static>>,
+    first: Span<'static>,
+    rest: Span<'static>,
+) -> Vec<Line<'static>> {
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let prefix = if i == 0 { first.clone() } else { rest.clone() };
+            let mut spans = vec![prefix];
+            spans.extend(line.spans);
+            Line::from(spans)
+        })
+        .collect()
+}
+
+fn pieces_width(pieces: &[Piece]) -> usize {
+    pieces
+        .iter()
+        .map(|(text, _)| text::display_width(text))
+        .sum()
+}
+
+// Cut pieces down to `width` columns, ending with "…" if anything was cut,
+// returning the spans and the columns they take
+fn fit(pieces: &[Piece], width: usize) -> (Vec<Span<'static>>, usize) {
+    let total = pieces_width(pieces);
+    if total <= width {
+        let spans = pieces
+            .iter()
+            .map(|(text, style)| Span::styled(text.clone(), *style))
+            .collect();
+        return (spans, total);
+    }
+
+    let budget = width.saturating_sub(1);
+    let mut spans = Vec::new();
+    let mut used = 0;
+    let mut last_style = Style::default();
+    'pieces: for (text, style) in pieces {
+        let mut part = String::new();
+        for c in text.chars() {
+            let char_width = c.width().unwrap_or(0);
+            if used + char_width > budget {
+                spans.push(Span::styled(part, *style));
+                last_style = *style;
+                break 'pieces;
+            }
+            part.push(c);
+            used += char_width;
+        }
+        spans.push(Span::styled(part, *style));
+        last_style = *style;
+    }
+    spans.push(Span::styled("…", last_style));
+    (spans, used + 1)
+}
+
+enum Token {
+    Word {
+        parts: Vec<Piece>,
+        width: usize,
+        space_before: bool,
+    },
+    Break,
+}
+
+// Split pieces into words, which may span several styles ("**bold**,"),
+// dropping breaks that would only leave empty lines
+fn tokens(pieces: &[Piece]) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut word: Option<Token> = None;
+    let mut space = false;
+
+    for (text, style) in pieces {
+        if text == "\n" {
+            tokens.extend(word.take());
+            if matches!(tokens.last(), Some(Token::Word { .. })) {
+                tokens.push(Token::Break);
+            }
+            space = false;
+            continue;
+        }
+        for c in text.chars() {
+            if c.is_whitespace() {
+                tokens.extend(word.take());
+                space = true;
+                continue;
+            }
+            let Token::Word { parts, width, .. } = word.get_or_insert_with(|| Token::Word {
+                parts: Vec::new(),
+                width: 0,
+                space_before: std::mem::take(&mut space),
+            }) else {
+                continue;
+            };
+            *width += c.width().unwrap_or(0);
+            match parts.last_mut() {
+                Some((part, part_style)) if part_style == style => part.push(c),
+                _ => parts.push((c.to_string(), *style)),
+            }
+        }
+    }
+    tokens.extend(word);
+    if matches!(tokens.last(), Some(Token::Break)) {
+        tokens.pop();
+    }
+    tokens
+}
+
+// Lay out pieces in lines of at most `width` columns, breaking between
+// words and splitting words that are wider than a whole line
+fn wrap(pieces: Vec<Piece>, width: usize) -> Vec<Line<'static>> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut line: Vec<Span<'static>> = Vec::new();
+    let mut line_width = 0;
+
+    for token in tokens(&pieces) {
+        let (parts, word_width, space_before) = match token {
+            Token::Break => {
+                lines.push(Line::from(std::mem::take(&mut line)));
+                line_width = 0;
+                continue;
+            }
+            Token::Word {
+                parts,
+                width,
+                space_before,
+            } => (parts, width, space_before),
+        };
+
+        let gap = usize::from(space_before && line_width > 0);
+        if line_width > 0 && line_width + gap + word_width > width {
+            lines.push(Line::from(std::mem::take(&mut line)));
+            line_width = 0;
+        } else if gap > 0 {
+            line.push(Span::raw(" "));
+            line_width += 1;
+        }
+
+        for (part, style) in parts {
+            let mut chunk = String::new();
+            for c in part.chars() {
+                let char_width = c.width().unwrap_or(0);
+                if line_width + char_width > width && line_width > 0 {
+                    if !chunk.is_empty() {
+                        line.push(Span::styled(std::mem::take(&mut chunk), style));
+                    }
+                    lines.push(Line::from(std::mem::take(&mut line)));
+                    line_width = 0;
+                }
+                chunk.push(c);
+                line_width += char_width;
+            }
+            if !chunk.is_empty() {
+                line.push(Span::styled(chunk, style));
+            }
+        }
+    }
+
+    if !line.is_empty() {
+        lines.push(Line::from(line));
+    }
+    lines
+}
+
+// Inline markup
+
+struct Inline<'a> {
+    refs: &'a Refs,
+    footnotes: &'a mut Vec<String>,
+    pieces: Vec<Piece>,
+    /// Targets of the HTML `<a>` tags still open
+    anchors: Vec<Option<String>>,
+}
+
+impl Inline<'_> {
+    fn run(&mut self, chars: &[char], style: Style) {
+        let theme = theme::get();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            match c {
+                '\\' if chars.get(i + 1).is_some_and(|n| n.is_ascii_punctuation()) => {
+                    self.push(&chars[i + 1].to_string(), style);
+                    i += 2;
+                }
+                '\n' => {
+                    self.line_break(style);
+                    i += 1;
+                }
+                '`' => {
+                    let n = run_length(chars, i, '`');
+                    match find_backticks(chars, i + n, n) {
+                        Some(end) => {
+                            let code: String = chars[i + n..end].iter().collect();
+                            let code = code.replace('\n', " ");
+                            let code =
+                                match code.strip_prefix(' ').and_then(|c| c.strip_suffix(' ')) {
+                                    Some(inner) if !inner.trim().is_empty() => inner.to_string(),
+                                    _ => code,
+                                };
+                            self.push(&code, style.fg(theme.key));
+                            i = end + n;
+                        }
+                        None => {
+                            self.push(&"`".repeat(n), style);
+                            i += n;
+                        }
+                    }
+                }
+                '*' | '_' | '~' => {
+                    let n = run_length(chars, i, c);
+                    match emphasis(chars, i, n) {
+                        Some((end, modifier)) => {
+                            self.run(&chars[i + n..end], style.add_modifier(modifier));
+                            i = end + n;
+                        }
+                        None => {
+                            self.push(&c.to_string().repeat(n), style);
+                            i += n;
+                        }
+                    }
+                }
+                '!' if chars.get(i + 1) == Some(&'[') => match self.link(chars, i + 1) {
+                    Some((alt, _, end)) => {
+                        // Images show their alt text; a badge's link gets the footnote
+                        let alt: String = alt.iter().collect();
+                        self.image(&alt, style);
+                        i = end;
+                    }
+                    None => {
+                        self.push("!", style);
+                        i += 1;
+                    }
+                },
+                '[' => match self.link(chars, i) {
+                    Some((label, url, end)) => {
+                        self.run(&label, link_style(style));
+                        if label.iter().collect::<String>() != url {
+                            self.footnote(&url);
+                        }
+                        i = end;
+                    }
+                    None => {
+                        self.push("[", style);
+                        i += 1;
+                    }
+                },
+                '<' => match self.tag(chars, i, style) {
+                    Some(end) => i = end,
+                    None => {
+                        self.push("<", style);
+                        i += 1;
+                    }
+                },
+                '&' => match entity(chars, i) {
+                    Some((decoded, end)) => {
+                        self.push(&decoded, style);
+                        i = end;
+                    }
+                    None => {
+                        self.push("&", style);
+                        i += 1;
+                    }
+                },
+                c => {
+                    let style = if self.anchors.iter().any(Option::is_some) {
+                        link_style(style)
+                    } else {
+                        style
+                    };
+                    self.push(&c.to_string(), style);
+                    i += 1;
+                }
+            }
+        }
+    }
+
+    fn push(&mut self, text: &str, style: Style) {
+        match self.pieces.last_mut() {
+            Some((last, last_style)) if *last_style == style && last != "\n" => last.push_str(text),
+            _ => self.pieces.push((text.to_string(), style)),
+        }
+    }
+
+    fn line_break(&mut self, style: Style) {
+        self.pieces.push(("\n".to_string(), style));
+    }
+
+    fn image(&mut self, alt: &str, style: Style) {
+        let theme = theme::get();
+        if !alt.trim().is_empty() {
+            self.push(&format!("[{}]", alt.trim()), style.fg(theme.muted));
+        }
+    }
+
+    fn footnote(&mut self, url: &str) {
+        let theme = theme::get();
+        // Anchors within the README lead nowhere from here
+        if url.is_empty() || url.starts_with('#') {
+            return;
+        }
+        let number = match self.footnotes.iter().position(|u| u == url) {
+            Some(i) => i + 1,
+            None => {
+                self.footnotes.push(url.to_string());
+                self.footnotes.len()
+            }
+        };
+        self.push(&format!("[{}]", number), theme.fg(theme.muted));
+    }
+
+    // A link or image starting at the `[`: its label, URL and where it ends
+    fn link(&self, chars: &[char], start: usize) -> Option<(Vec<char>, String, usize)> {
+        let close = matching(chars, start, '[', ']')?;
+        let label = chars[start + 1..close].to_vec();
+
+        match chars.get(close + 1) {
+            Some('(') => {
+                let end = matching(chars, close + 1, '(', ')')?;
+                let destination: String = chars[close + 2..end].iter().collect();
+                let destination = destination.trim();
+                let url = match destination.strip_prefix('<') {
+                    Some(rest) => rest.split('>').next().unwrap_or_default(),
+                    None => destination.split_whitespace().next().unwrap_or_default(),
+                };
+                Some((label, url.to_string(), end + 1))
+            }
+            Some('[') => {
+                let end = matching(chars, close + 1, '[', ']')?;
+                let reference: String = chars[close + 2..end].iter().collect();
+                let reference = if reference.trim().is_empty() {
+                    label.iter().collect()
+                } else {
+                    reference
+                };
+                let url = self.refs.get(&normalize_label(&reference))?;
+                Some((label, url.clone(), end + 1))
+            }
+            _ => {
+                let reference: String = label.iter().collect();
+                let url = self.refs.get(&normalize_label(&reference))?;
+                Some((label, url.clone(), close + 1))
+            }
+        }
+    }
+
+    // An autolink, HTML comment or tag starting at the `<`, returning where it
+    // ends. Tags are dropped, apart from what they mean for the text.
+    fn tag(&mut self, chars: &[char], start: usize, style: Style) -> Option<usize> {
+        let rest: String = chars[start..].iter().collect();
+
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->")?;
+            return Some(start + rest[..end].chars().count() + 3);
+        }
+
+        let end = rest.find('>')?;
+        let inner = &rest[1..end];
+        let end = start + rest[..end].chars().count() + 1;
+
+        if !inner.contains(char::is_whitespace)
+            && (inner.starts_with("http://")
+                || inner.starts_with("https://")
+                || inner.starts_with("mailto:"))
+        {
+            self.push(inner, link_style(style));
+            return Some(end);
+        }
+
+        let closing = inner.starts_with('/');
+        let name: String = inner
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        if name.is_empty() || !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return None;
+        }
+
+        match (name.as_str(), closing) {
+            ("br", _) => self.line_break(style),
+            ("img", _) => {
+                let alt = attribute(inner, "alt").unwrap_or_default();
+                self.image(&alt, style);
+            }
+            ("a", false) => self.anchors.push(attribute(inner, "href")),
+            ("a", true) => {
+                if let Some(Some(url)) = self.anchors.pop() {
+                    self.footnote(&url);
+                }
+            }
+            (name, _) if BLOCK_TAGS.contains(&name) => self.line_break(style),
+            _ => {}
+        }
+        Some(end)
+    }
+}
+
+fn link_style(style: Style) -> Style {
+    let theme = theme::get();
+    style.fg(theme.accent).add_modifier(Modifier::UNDERLINED)
+}
+
+fn run_length(chars: &[char], start: usize, c: char) -> usize {
+    chars[start..].iter().take_while(|&&x| x == c).count()
+}
+
+// The start of the next run of exactly `n` backticks
+fn find_backticks(chars: &[char], from: usize, n: usize) -> Option<usize> {
+    let mut i = from;
+    while i < chars.len() {
+        if chars[i] == '`' {
+            let len = run_length(chars, i, '`');
+            if len == n {
+                return Some(i);
+            }
+            i += len;
+        } else {
+            i += 1;
+        }
+    }
+    None
+}
+
+// Where the emphasis opened by the run of `n` delimiters at `start` closes,
+// and the style it adds
+fn emphasis(chars: &[char], start: usize, n: usize) -> Option<(usize, Modifier)> {
+    let c = chars[start];
+    let modifier = match (c, n) {
+        ('~', 2) => Modifier::CROSSED_OUT,
+        ('~', _) => return None,
+        (_, 1) => Modifier::ITALIC,
+        (_, 2) => Modifier::BOLD,
+        (_, 3) => Modifier::BOLD | Modifier::ITALIC,
+        _ => return None,
+    };
+
+    let after = chars.get(start + n)?;
+    if after.is_whitespace() {
+        return None;
+    }
+    // snake_case names are not emphasis
+    if c == '_' && start > 0 && chars[start - 1].is_alphanumeric() {
+        return None;
+    }
+
+    let mut i = start + n;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            '`' => {
+                let len = run_length(chars, i, '`');
+                i = find_backticks(chars, i + len, len).map_or(i + len, |end| end + len);
+            }
+            x if x == c => {
+                let len = run_length(chars, i, c);
+                let closes = len == n
+                    && i > start + n
+                    && !chars[i - 1].is_whitespace()
+                    && !(c == '_' && chars.get(i + len).is_some_and(|x| x.is_alphanumeric()));
+                if closes {
+                    return Some((i, modifier));
+                }
+                i += len;
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+// The bracket closing the one at `start`, skipping nested pairs, escapes and
+// code spans
+fn matching(chars: &[char], start: usize, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = start;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            '`' if open == '[' => {
+                let len = run_length(chars, i, '`');
+                if let Some(end) = find_backticks(chars, i + len, len) {
+                    i = end + len - 1;
+                } else {
+                    i += len - 1;
+                }
+            }
+            x if x == open => depth += 1,
+            x if x == close => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+// An HTML entity like `&amp;` or `&#169;` at `start`, decoded
+fn entity(chars: &[char], start: usize) -> Option<(String, usize)> {
+    let semi = chars[start..].iter().take(12).position(|c| *c == ';')? + start;
+    let name: String = chars[start + 1..semi].iter().collect();
+    let decoded = match name.as_str() {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        "mdash" => '—',
+        "ndash" => '–',
+        "hellip" => '…',
+        _ => {
+            let number = name.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)?
+        }
+    };
+    Some((decoded.to_string(), semi + 1))
+}
+
+// The value of an attribute in the inside of an HTML tag
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    // ASCII lowercasing keeps byte offsets the same as in `tag`
+    let lower = tag.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(pos) = lower[from..].find(name).map(|p| from + p) {
+        from = pos + name.len();
+        let rest = lower[from..].trim_start();
+        if !lower[..pos].ends_with(char::is_whitespace) || !rest.starts_with('=') {
+            continue;
+        }
+        let value = tag[tag.len() - rest.len() + 1..].trim_start();
+        return Some(
+            match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+                _ => value
+                    .split(|c: char| c.is_whitespace() || c == '/')
+                    .next()
+                    .unwrap_or_default(),
+            }
+            .to_string(),
+        );
+    }
+    None
+}
+
+// Code highlighting
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Syntax {
+    Rust,
+    Toml,
+    Shell,
+    Plain,
+}
+
+impl Syntax {
+    // Rustdoc attributes like `ignore` or `no_run` mark Rust code too
+    fn for_lang(lang: &str) -> Syntax {
+        match lang {
+            "rust" | "rs" | "ignore" | "no_run" | "should_panic" | "compile_fail" => Syntax::Rust,
+            lang if lang.starts_with("edition") => Syntax::Rust,
+            "toml" => Syntax::Toml,
+            "sh" | "bash" | "shell" | "console" | "zsh" | "fish" | "powershell" | "ps1" => {
+                Syntax::Shell
+            }
+            _ => Syntax::Plain,
+        }
+    }
+
+    // Colour one line of code. `in_comment` carries a Rust block comment
+    // over to the next line.
+    fn highlight(self, line: &str, in_comment: &mut bool) -> Vec<Span<'static>> {
+        let theme = theme::get();
+        let plain = theme.fg(theme.text);
+        if self == Syntax::Plain {
+            return vec![Span::styled(line.to_string(), plain)];
+        }
+        let comment = theme.fg(theme.faint);
+        let string = theme.fg(theme.good);
+        let number = theme.fg(theme.warn);
+        let keyword = theme.fg(theme.key).add_modifier(Modifier::BOLD);
+        let name = theme.fg(theme.tag);
+        let call = theme.fg(theme.accent);
+
+        if self == Syntax::Toml && line.trim_start().starts_with('[') {
+            return vec![Span::styled(line.to_string(), keyword)];
+        }
+
+        let chars: Vec<char> = line.chars().collect();
+        let mut pieces: Vec<Piece> = Vec::new();
+        let mut push = |text: &[char], style: Style| match pieces.last_mut() {
+            Some((last, last_style)) if *last_style == style => last.extend(text),
+            _ => pieces.push((text.iter().collect(), style)),
+        };
+
+        let mut i = 0;
+        if self == Syntax::Shell {
+            for prompt in ["$ ", "> ", "% "] {
+                if line.starts_with(prompt) {
+                    push(&chars[..2], comment);
+                    i = 2;
+                }
+            }
+        }
+        let command_start = i;
+
+        while i < chars.len() {
+            let c = chars[i];
+            let rest = &chars[i..];
+
+            if *in_comment {
+                let end = (i..chars.len().saturating_sub(1))
+                    .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                    .map(|j| j + 2);
+                *in_comment = end.is_none();
+                let end = end.unwrap_or(chars.len());
+                push(&chars[i..end], comment);
+                i = end;
+                continue;
+            }
+
+            if self == Syntax::Rust && rest.starts_with(&['/', '*']) {
+                *in_comment = true;
+                push(&rest[..2], comment);
+                i += 2;
+                continue;
+            }
+            let line_comment = match self {
+                Syntax::Rust => rest.starts_with(&['/', '/']),
+                _ => c == '#' && (i == 0 || chars[i - 1].is_whitespace()),
+            };
+            if line_comment {
+                push(rest, comment);
+                break;
+            }
+
+            if c == '"' || (c == '\'' && self != Syntax::Rust) {
+                let mut end = i + 1;
+                while end < chars.len() && chars[end] != c {
+                    end += if chars[end] == '\\' { 2 } else { 1 };
+                }
+                let end = (end + 1).min(chars.len());
+                push(&chars[i..end], string);
+                i = end;
+                continue;
+            }
+
+            if c == '\'' {
+                // A char literal, or else a lifetime
+                let close = match chars.get(i + 1) {
+                    Some('\\') => (i + 3..chars.len().min(i + 12)).find(|&j| chars[j] == '\''),
+                    Some(_) if chars.get(i + 2) == Some(&'\'') => Some(i + 2),
+                    _ => None,
+                };
+                if let Some(close) = close {
+                    push(&chars[i..=close], string);
+                    i = close + 1;
+                } else {
+                    let len = 1 + ident_len(&rest[1..]);
+                    push(&rest[..len], name);
+                    i += len;
+                }
+                continue;
+            }
+
+            if self == Syntax::Rust && c == '#' && matches!(chars.get(i + 1), Some('[' | '!')) {
+                let end = rest
+                    .iter()
+                    .position(|c| *c == ']')
+                    .map_or(chars.len(), |p| i + p + 1);
+                push(&chars[i..end], comment);
+                i = end;
+                continue;
+            }
+
+            if c.is_ascii_digit() && (i == 0 || !is_ident_char(chars[i - 1])) {
+                let mut end = i + 1;
+                while end < chars.len()
+                    && (is_ident_char(chars[end])
+                        || (chars[end] == '.'
+                            && chars.get(end + 1).is_some_and(|c| c.is_ascii_digit())))
+                {
+                    end += 1;
+                }
+                push(&chars[i..end], number);
+                i = end;
+                continue;
+            }
+
+            if c.is_alphabetic() || c == '_' {
+                let len = ident_len(rest);
+                let word: String = rest[..len].iter().collect();
+                let next = rest[len..].iter().find(|c| !c.is_whitespace());
+                let style = match self {
+                    Syntax::Rust if RUST_KEYWORDS.contains(&word.as_str()) => keyword,
+                    Syntax::Rust if rest.get(len) == Some(&'!') => {
+                        push(&rest[..=len], call);
+                        i += len + 1;
+                        continue;
+                    }
+                    Syntax::Rust
+                        if RUST_PRIMITIVES.contains(&word.as_str())
+                            || word.starts_with(char::is_uppercase) =>
+                    {
+                        name
+                    }
+                    Syntax::Toml if next == Some(&'=') => name,
+                    Syntax::Toml if word == "true" || word == "false" => number,
+                    Syntax::Shell if i == command_start => call,
+                    _ => plain,
+                };
+                push(&rest[..len], style);
+                i += len;
+                continue;
+            }
+
+            if self == Syntax::Shell && c == '-' && (i == 0 || chars[i - 1].is_whitespace()) {
+                let len = rest.iter().take_while(|c| !c.is_whitespace()).count();
+                push(&rest[..len], name);
+                i += len;
+                continue;
+            }
+
+            push(&rest[..1], plain);
+            i += 1;
+        }
+
+        pieces
+            .into_iter()
+            .map(|(text, style)| Span::styled(text, style))
+            .collect()
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn ident_len(chars: &[char]) -> usize {
+    chars.iter().take_while(|c| is_ident_char(**c)).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const WIDTH: usize = 40;
+
+    fn text_of(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    fn rendered(source: &str) -> Vec<String> {
+        render(source, WIDTH).iter().map(text_of).collect()
+    }
+
+    // The style of the span holding `text`
+    fn style_of(lines: &[Line], text: &str) -> Style {
+        lines
+            .iter()
+            .flat_map(|l| l.spans.iter())
+            .find(|s| s.content == text)
+            .unwrap_or_else(|| panic!("no span {:?}", text))
+            .style
+    }
+
+    #[test]
+    fn headings() {
+        let lines = render("# Title\n\nSub\n---\n\n### Small ###", WIDTH);
+        assert_eq!(
+            lines.iter().map(text_of).collect::<Vec<_>>(),
+            ["Title", "", "Sub", "", "Small"]
+        );
+        assert_eq!(style_of(&lines, "Title"), heading_style(1));
+        assert_eq!(style_of(&lines, "Sub"), heading_style(2));
+        assert_eq!(style_of(&lines, "Small"), heading_style(3));
+    }
+
+    #[test]
+    fn lists() {
+        assert_eq!(
+            rendered("- one\n- two\n  - nested\n- [x] done\n\n3. three\n4. four"),
+            [
+                "• one",
+                "• two",
+                "  • nested",
+                "☑ done",
+                "",
+                "3. three",
+                "4. four"
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_emphasis() {
+        let lines = render("**bold *both* bold** and _it_", WIDTH);
+        assert_eq!(text_of(&lines[0]), "bold both bold and it");
+        let modifiers = |text| style_of(&lines, text).add_modifier;
+        assert!(modifiers("both").contains(Modifier::BOLD | Modifier::ITALIC));
+        assert!(!modifiers("it").contains(Modifier::BOLD));
+        assert!(modifiers("it").contains(Modifier::ITALIC));
+    }
+
+    #[test]
+    fn code_fences() {
+        let lines = rendered("```rust\nfn main() {}\n```\n\n~~~\n# not a heading\n~~~");
+        assert_eq!(lines, ["│ fn main() {}", "", "│ # not a heading"]);
+
+        // Keywords are highlighted in Rust code only
+        let rust = &render("```rust\nfn main() {}\n```", WIDTH)[0];
+        assert_eq!(rust.spans[1].content, "fn");
+        assert_ne!(rust.spans[1].style, rust.spans[2].style);
+        let plain = &render("```\nfn main() {}\n```", WIDTH)[0];
+        assert_eq!(plain.spans.len(), 2);
+    }
+
+    #[test]
+    fn code_lines_are_left_for_the_pane_to_clip() {
+        let long = "x".repeat(WIDTH * 2);
+        assert_eq!(
+            rendered(&format!("```\n{}\n```", long)),
+            [format!("│ {}", long)]
+        );
+    }
+
+    #[test]
+    fn tables() {
+        let lines = rendered("| Name | Count |\n|:-----|------:|\n| a | 1 |\n| bb | 22 |");
+        assert_eq!(lines.len(), 4, "{:?}", lines);
+        assert!(lines[0].contains("Name") && lines[0].contains("Count"));
+        // The right-aligned column ends at the same place on every row
+        let ends: Vec<usize> = [&lines[2], &lines[3]]
+            .iter()
+            .map(|l| l.trim_end().len())
+            .collect();
+        assert_eq!(ends[0], ends[1]);
+    }
+
+    #[test]
+    fn links_become_footnotes() {
+        assert_eq!(
+            rendered(
+                "See [docs](https://docs.rs/x) and [docs again](https://docs.rs/x), [ref][r].\n\n\
+                 [r]: https://example.com"
+            ),
+            [
+                "See docs[1] and docs again[1], ref[2].",
+                "",
+                "Links",
+                "[1] https://docs.rs/x",
+                "[2] https://example.com",
+            ]
+        );
+    }
+
+    #[test]
+    fn long_footnote_urls_wrap() {
+        let url = format!("https://example.com/{}", "a".repeat(WIDTH * 2));
+        let lines = rendered(&format!("[x]({})", url));
+        let links = &lines[lines.iter().position(|l| l == "Links").unwrap() + 1..];
+        assert!(links.len() > 1);
+        assert!(links[0].starts_with("[1] https://"));
+        assert!(links[1..].iter().all(|l| l.starts_with("    ")));
+        let joined: String = links.iter().map(|l| l[4..].to_string()).collect();
+        assert_eq!(joined, url);
+    }
+
+    // Markdown made of the constructs that wrap, with no code blocks, whose
+    // lines are left for the pane to clip
+    fn markdown() -> impl Strategy<Value = String> {
+        let word = prop_oneof![
+            "[a-z]{1,12}",
+            "[a-z]{30,60}",
+            "[\u{4e00}-\u{4e20}\u{1f600}-\u{1f620}]{1,6}",
+            Just("**bold**".to_string()),
+            Just("*it*".to_string()),
+            Just("`code`".to_string()),
+            Just("[link](https://example.com/some/rather/long/path/to/a/page)".to_string()),
+            Just("<b>html</b>".to_string()),
+            Just("|".to_string()),
+        ];
+        let line = (
+            prop_oneof![
+                Just(""),
+                Just("# "),
+                Just("- "),
+                Just("1. "),
+                Just("> "),
+                Just("> - "),
+                Just("| "),
+            ],
+            prop::collection::vec(word, 0..12),
+        )
+            .prop_map(|(start, words)| format!("{}{}", start, words.join(" ")));
+        prop_oneof![
+            prop::collection::vec(line, 0..12).prop_map(|lines| lines.join("\n")),
+            Just("| a | b |\n|---|---|\n".to_string()).prop_flat_map(|head| {
+                prop::collection::vec("[a-z ]{0,40}", 1..4)
+                    .prop_map(move |rows| format!("{}| {} | x |", head, rows.join(" | x |\n| ")))
+            }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn rendered_lines_fit(source in markdown(), width in 0usize..80) {
+            for line in render(&source, width) {
+                let line_width = text::display_width(&text_of(&line));
+                prop_assert!(line_width <= width.max(MIN_WIDTH), "{:?}", line);
+            }
+        }
+
+        #[test]
+        fn plain_lines_fit(source in "[a-z \n\u{4e00}-\u{4e20}]{0,200}", width in 0usize..80) {
+            for line in render_plain(&source, width) {
+                prop_assert!(text::display_width(&text_of(&line)) <= width.max(MIN_WIDTH));
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::app::Tab;
+use crate::columns::Column;
+use ratatui::layout::Rect;
+use std::time::{Duration, Instant};
+
+// Two clicks on the same row within this count as a double click
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+/// Something on screen that reacts to the mouse
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    Tab(Tab),
+    /// A row of the tab's list or table, by position among the visible rows
+    Row(usize),
+    Category(usize),
+    Keyword(usize),
+    Version(usize),
+    DiffFile(usize),
+    /// A row of the API docs tree or search results
+    ApiItem(usize),
+    /// A Compare table heading, which sorts by the column
+    Column(Column),
+    Link(String),
+}
+
+/// Where the last frame drew each clickable thing. The UI rebuilds it on
+/// every draw, so it always matches what is on screen.
+#[derive(Debug, Default)]
+pub struct HitMap {
+    hits: Vec<(Rect, Target)>,
+    last_click: Option<(Instant, Target)>,
+}
+
+impl HitMap {
+    pub fn clear(&mut self) {
+        self.hits.clear();
+    }
+
+    pub fn add(&mut self, area: Rect, target: Target) {
+        self.hits.push((area, target));
+    }
+
+    /// Record the rows of a list drawn in `area` (borders included), given
+    /// the height of each row and how many rows were scrolled past. Rows
+    /// without a target, like headings, take up space but can't be clicked.
+    pub fn add_rows(
+        &mut self,
+        area: Rect,
+        offset: usize,
+        heights: &[usize],
+        target: impl Fn(usize) -> Option<Target>,
+    ) {
+        let inner = inner(area);
+        let mut y = inner.y;
+        for (index, height) in heights.iter().enumerate().skip(offset) {
+            if y >= inner.bottom() {
+                break;
+            }
+            let height = (*height as u16).min(inner.bottom() - y);
+            if let Some(target) = target(index) {
+                self.add(
+                    Rect {
+                        x: inner.x,
+                        y,
+                        width: inner.width,
+                        height,
+                    },
+                    target,
+                );
+            }
+            y += height;
+        }
+    }
+
+    /// The clickable URLs, with where each piece of them was drawn
+    pub fn links(&self) -> impl Iterator<Item = (Rect, &str)> {
+        self.hits.iter().filter_map(|(area, target)| match target {
+            Target::Link(url) => Some((*area, url.as_str())),
+            _ => None,
+        })
+    }
+
+    /// The topmost target under a cell
+    pub fn at(&self, column: u16, row: u16) -> Option<&Target> {
+        self.hits
+            .iter()
+            .rev()
+            .find(|(area, _)| {
+                area.left() <= column
+                    && column < area.right()
+                    && area.top() <= row
+                    && row < area.bottom()
+            })
+            .map(|(_, target)| target)
+    }
+
+    /// Note a click on `target`, returning whether it completes a double click
+    pub fn click(&mut self, target: &Target) -> bool {
+        let now = Instant::now();
+        let double = matches!(
+            self.last_click,
+            Some((at, ref last)) if last == target && now.duration_since(at) < DOUBLE_CLICK
+        );
+        // A third click starts over rather than counting as another double
+        self.last_click = if double {
+            None
+        } else {
+            Some((now, target.clone()))
+        };
+        double
+    }
+}
+
+// The area inside a block's borders
+fn inner(area: Rect) -> Rect {
+    Rect {
+        x: area.x.saturating_add(1),
+        y: area.y.saturating_add(1),
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(2),
+    }
+}
@@ -0,0 +1,367 @@
+use crate::api::{Finding, Severity, Version};
+use crate::policy::Policy;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+
+static TOOLCHAIN: OnceLock<Toolchain> = OnceLock::new();
+
+/// A Rust release like `1.70` or `1.70.1`, ordered numerically
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RustVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl RustVersion {
+    /// Parse a `rust-version` value or the version printed by `rustc`,
+    /// ignoring channel suffixes like `-nightly`
+    pub fn parse(text: &str) -> Option<RustVersion> {
+        let release = text.trim().split(['-', ' ']).next()?;
+        let mut parts = release.split('.').map(|p| p.parse::<u32>().ok());
+        let major = parts.next()??;
+        let minor = parts.next().unwrap_or(Some(0))?;
+        let patch = parts.next().unwrap_or(Some(0))?;
+        Some(RustVersion {
+            major,
+            minor,
+            patch,
+        })
+    }
+}
+
+impl fmt::Display for RustVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.patch == 0 {
+            write!(f, "{}.{}", self.major, self.minor)
+        } else {
+            write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+        }
+    }
+}
+
+/// The compiler installed here and the Rust version the project in the
+/// working directory promises to support
+#[derive(Debug, Clone, Default)]
+pub struct Toolchain {
+    /// From `rustc --version`
+    pub rustc: Option<RustVersion>,
+    /// The project's `rust-version`
+    pub msrv: Option<RustVersion>,
+    pub edition: Option<String>,
+    /// The manifest the MSRV was read from
+    pub manifest: Option<PathBuf>,
+}
+
+/// The Rust a crate's versions have to build with here
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Limit {
+    /// The project's declared MSRV
+    Msrv(RustVersion),
+    /// No MSRV is declared, so the installed compiler is the limit
+    Rustc(RustVersion),
+}
+
+impl Limit {
+    pub fn version(&self) -> RustVersion {
+        match self {
+            Limit::Msrv(version) | Limit::Rustc(version) => *version,
+        }
+    }
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Limit::Msrv(version) => write!(f, "the project's MSRV {}", version),
+            Limit::Rustc(version) => write!(f, "the local rustc {}", version),
+        }
+    }
+}
+
+impl Toolchain {
+    pub fn limit(&self) -> Option<Limit> {
+        self.msrv.map(Limit::Msrv).or(self.rustc.map(Limit::Rustc))
+    }
+}
+
+/// The local toolchain, detected on first use
+pub fn local() -> &'static Toolchain {
+    TOOLCHAIN.get_or_init(|| {
+        let mut toolchain = std::env::current_dir()
+            .map(|dir| project(&dir))
+            .unwrap_or_default();
+        toolchain.rustc = rustc_version();
+        toolchain
+    })
+}
+
+fn rustc_version() -> Option<RustVersion> {
+    let output = Command::new("rustc").arg("--version").output().ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    // e.g. `rustc 1.80.0 (051478957 2024-07-21)`
+    RustVersion::parse(text.split_whitespace().nth(1)?)
+}
+
+// The nearest manifest's `rust-version` and `edition`, taking values marked
+// `workspace = true` (or a virtual manifest's) from the workspace root
+fn project(start: &Path) -> Toolchain {
+    let manifests: Vec<(PathBuf, toml::Table)> = start
+        .ancestors()
+        .filter_map(|dir| {
+            let path = dir.join("Cargo.toml");
+            let manifest = fs::read_to_string(&path).ok()?.parse().ok()?;
+            Some((path, manifest))
+        })
+        .collect();
+    let Some((path, nearest)) = manifests.first() else {
+        return Toolchain::default();
+    };
+
+    let inherited = |key: &str| {
+        manifests.iter().find_map(|(_, manifest)| {
+            let value = manifest.get("workspace")?.get("package")?.get(key)?;
+            value.as_str().map(str::to_string)
+        })
+    };
+    let field = |key: &str| match nearest.get("package").map(|p| p.get(key)) {
+        Some(Some(toml::Value::String(value))) => Some(value.clone()),
+        Some(Some(_)) | None => inherited(key),
+        Some(None) => None,
+    };
+
+    Toolchain {
+        rustc: None,
+        msrv: field("rust-version").and_then(|v| RustVersion::parse(&v)),
+        edition: field("edition"),
+        manifest: Some(path.clone()),
+    }
+}
+
+/// What Rust a crate's latest version needs and whether that fits here
+#[derive(Debug, Clone)]
+pub struct RustSupport {
+    pub version: String,
+    /// The latest version's `rust-version`
+    pub rust_version: Option<RustVersion>,
+    pub edition: Option<String>,
+    /// Set when the latest version needs more than the local limit
+    pub raise: Option<Raise>,
+}
+
+/// The latest version needs a newer Rust than the project builds with
+#[derive(Debug, Clone)]
+pub struct Raise {
+    pub limit: Limit,
+    /// The newest version that still builds within the limit
+    pub compatible: Option<String>,
+}
+
+/// Check the version `latest` of a crate against the local limit. `versions`
+/// is newest first, as crates.io lists them.
+pub fn support(versions: &[Version], latest: &str) -> Option<RustSupport> {
+    support_within(versions, latest, local().limit())
+}
+
+fn support_within(versions: &[Version], latest: &str, limit: Option<Limit>) -> Option<RustSupport> {
+    let current = versions.iter().find(|v| v.num == latest)?;
+    let rust_version = current.rust_version.as_deref().and_then(RustVersion::parse);
+
+    let raise = match (rust_version, limit) {
+        (Some(needed), Some(limit)) if needed > limit.version() => Some(Raise {
+            limit,
+            compatible: newest_compatible(versions, limit.version()),
+        }),
+        _ => None,
+    };
+
+    Some(RustSupport {
+        version: current.num.clone(),
+        rust_version,
+        edition: current.edition.clone(),
+        raise,
+    })
+}
+
+// Versions that declare no `rust-version` are taken to be compatible, as
+// most released before the field existed. Pre-releases and yanked versions
+// are never suggested.
+fn newest_compatible(versions: &[Version], limit: RustVersion) -> Option<String> {
+    versions
+        .iter()
+        .filter(|v| !v.yanked && !v.num.contains('-'))
+        .find(|v| {
+            v.rust_version
+                .as_deref()
+                .and_then(RustVersion::parse)
+                .is_none_or(|needed| needed <= limit)
+        })
+        .map(|v| v.num.clone())
+}
+
+/// A finding for a crate whose latest version would raise the MSRV
+pub fn check(name: &str, support: Option<&RustSupport>, policy: &Policy) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let Some(support) = support else {
+        return findings;
+    };
+    let (Some(raise), Some(needed)) = (&support.raise, support.rust_version) else {
+        return findings;
+    };
+    if !policy.applies(name, &policy.rules.msrv) {
+        return findings;
+    }
+
+    let suggestion = match raise.compatible {
+        Some(ref version) => format!("v{} is the newest compatible version", version),
+        None => "no published version is compatible".to_string(),
+    };
+    findings.push(policy.finding(
+        "msrv",
+        Severity::Medium,
+        format!(
+            "v{} needs Rust {}, above {} - {}",
+            support.version, needed, raise.limit, suggestion
+        ),
+    ));
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rust(text: &str) -> RustVersion {
+        RustVersion::parse(text).unwrap()
+    }
+
+    fn version(num: &str, rust_version: Option<&str>, yanked: bool) -> Version {
+        serde_json::from_value(serde_json::json!({
+            "num": num,
+            "created_at": "2024-01-01T00:00:00Z",
+            "downloads": 0,
+            "yanked": yanked,
+            "license": null,
+            "crate_size": null,
+            "published_by": null,
+            "rust_version": rust_version,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_versions() {
+        for (text, expected) in [
+            ("1.70", (1, 70, 0)),
+            ("1.70.1", (1, 70, 1)),
+            ("1", (1, 0, 0)),
+            (" 1.80.0 ", (1, 80, 0)),
+            ("1.82.0-nightly", (1, 82, 0)),
+            ("1.80.0 (051478957 2024-07-21)", (1, 80, 0)),
+        ] {
+            let (major, minor, patch) = expected;
+            assert_eq!(
+                RustVersion::parse(text),
+                Some(RustVersion {
+                    major,
+                    minor,
+                    patch
+                }),
+                "{:?}",
+                text
+            );
+        }
+        for text in ["", "stable", "1.x", "v1.70", "1..2"] {
+            assert_eq!(RustVersion::parse(text), None, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn versions_order_numerically() {
+        assert!(rust("1.9") < rust("1.10"));
+        assert!(rust("1.70") < rust("1.70.1"));
+        assert_eq!(rust("1.70.0"), rust("1.70"));
+        assert_eq!(rust("1.70.0").to_string(), "1.70");
+        assert_eq!(rust("1.70.2").to_string(), "1.70.2");
+    }
+
+    #[test]
+    fn newest_compatible_skips_yanked_and_prereleases() {
+        let versions = [
+            version("2.0.0-rc.1", None, false),
+            version("1.3.0", Some("1.80"), false),
+            version("1.2.1", Some("1.70"), true),
+            version("1.2.0", Some("1.70"), false),
+            version("1.1.0", None, false),
+        ];
+        assert_eq!(
+            newest_compatible(&versions, rust("1.75")).as_deref(),
+            Some("1.2.0")
+        );
+        assert_eq!(
+            newest_compatible(&versions, rust("1.80")).as_deref(),
+            Some("1.3.0")
+        );
+        // Versions without a rust-version are taken to be compatible
+        assert_eq!(
+            newest_compatible(&versions, rust("1.60")).as_deref(),
+            Some("1.1.0")
+        );
+        assert_eq!(newest_compatible(&versions[..4], rust("1.60")), None);
+    }
+
+    #[test]
+    fn support_reports_a_raise_above_the_limit() {
+        let versions = [
+            version("1.3.0", Some("1.80"), false),
+            version("1.2.0", None, false),
+        ];
+
+        let limit = Some(Limit::Msrv(rust("1.75")));
+        let support = support_within(&versions, "1.3.0", limit).unwrap();
+        assert_eq!(support.rust_version, Some(rust("1.80")));
+        let raise = support.raise.unwrap();
+        assert_eq!(raise.limit, Limit::Msrv(rust("1.75")));
+        assert_eq!(raise.compatible.as_deref(), Some("1.2.0"));
+
+        let limit = Some(Limit::Rustc(rust("1.80")));
+        assert!(support_within(&versions, "1.3.0", limit)
+            .unwrap()
+            .raise
+            .is_none());
+
+        // Nothing to compare without a rust-version or a limit
+        assert!(support_within(&versions, "1.2.0", limit)
+            .unwrap()
+            .raise
+            .is_none());
+        assert!(support_within(&versions, "1.3.0", None)
+            .unwrap()
+            .raise
+            .is_none());
+        assert!(support_within(&versions, "9.9.9", limit).is_none());
+    }
+
+    #[test]
+    fn check_suggests_the_compatible_version() {
+        let versions = [
+            version("1.3.0", Some("1.80"), false),
+            version("1.2.0", Some("1.60"), false),
+        ];
+        let support = support_within(&versions, "1.3.0", Some(Limit::Msrv(rust("1.70"))));
+        let findings = check("demo", support.as_ref(), &Policy::default());
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].message,
+            "v1.3.0 needs Rust 1.80, above the project's MSRV 1.70 - \
+             v1.2.0 is the newest compatible version"
+        );
+
+        let mut policy = Policy::default();
+        policy.allow.push("demo".to_string());
+        assert!(check("demo", support.as_ref(), &policy).is_empty());
+    }
+}
@@ -0,0 +1,416 @@
+use crate::api::{Finding, Severity};
+use crate::app::AppResult;
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer};
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+const POLICY_FILE: &str = "policy.toml";
+const PROJECT_POLICY_FILE: &str = ".crates-policy.toml";
+
+/// What a rule does when it fires
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    /// Shown as a warning
+    Warn,
+    /// Shown as a warning and fails headless audits
+    Error,
+}
+
+/// Settings shared by every rule
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct RuleToggle {
+    pub enabled: bool,
+    pub level: Level,
+    /// Override the severity the rule reports with
+    pub severity: Option<Severity>,
+    /// Crates this rule never fires for
+    pub allow: Vec<String>,
+}
+
+impl Default for RuleToggle {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            level: Level::Warn,
+            severity: None,
+            allow: Vec::new(),
+        }
+    }
+}
+
+impl RuleToggle {
+    const KEYS: [&'static str; 4] = ["enabled", "level", "severity", "allow"];
+
+    /// Whether the rule should be evaluated for a crate
+    pub fn applies_to(&self, name: &str) -> bool {
+        self.enabled && !self.allow.iter().any(|a| a == name)
+    }
+}
+
+/// The settings a rule has on top of the shared ones
+pub trait RuleSettings: Default + DeserializeOwned {
+    /// Level the rule fires with unless the policy sets one
+    const LEVEL: Level = Level::Warn;
+}
+
+/// A rule with settings of its own. The policy writes both kinds of keys in
+/// the rule's table; they are split here rather than with
+/// `#[serde(flatten)]`, which can't reject misspelled keys.
+#[derive(Debug, Clone)]
+pub struct Rule<S> {
+    pub rule: RuleToggle,
+    pub settings: S,
+}
+
+impl<S: RuleSettings> Default for Rule<S> {
+    fn default() -> Self {
+        Self {
+            rule: RuleToggle {
+                level: S::LEVEL,
+                ..RuleToggle::default()
+            },
+            settings: S::default(),
+        }
+    }
+}
+
+impl<S> Deref for Rule<S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        &self.settings
+    }
+}
+
+impl<'de, S: RuleSettings> Deserialize<'de> for Rule<S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut settings = toml::Table::deserialize(deserializer)?;
+        let mut toggle = toml::Table::new();
+        for key in RuleToggle::KEYS {
+            if let Some(value) = settings.remove(key) {
+                toggle.insert(key.to_string(), value);
+            }
+        }
+
+        let level_set = toggle.contains_key("level");
+        let mut rule: RuleToggle = toml::Value::Table(toggle)
+            .try_into()
+            .map_err(D::Error::custom)?;
+        if !level_set {
+            rule.level = S::LEVEL;
+        }
+        let settings = toml::Value::Table(settings)
+            .try_into()
+            .map_err(D::Error::custom)?;
+        Ok(Rule { rule, settings })
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct LicenseSettings {
+    /// Additional license names treated as common, matched case-insensitively
+    pub extra_licenses: Vec<String>,
+}
+
+impl RuleSettings for LicenseSettings {}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct NewPopularSettings {
+    pub max_age_days: i64,
+    pub min_downloads: u64,
+}
+
+impl Default for NewPopularSettings {
+    fn default() -> Self {
+        Self {
+            max_age_days: 30,
+            min_downloads: 10000,
+        }
+    }
+}
+
+impl RuleSettings for NewPopularSettings {}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct TyposquatSettings {
+    /// Maximum edit distance from a popular crate name that counts as similar
+    pub max_distance: usize,
+    /// Extra crate names to protect in addition to the built-in list
+    pub popular: Vec<String>,
+}
+
+impl Default for TyposquatSettings {
+    fn default() -> Self {
+        Self {
+            max_distance: 2,
+            popular: Vec::new(),
+        }
+    }
+}
+
+impl RuleSettings for TyposquatSettings {}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct EarlyVersionSettings {
+    /// Versions starting with this prefix are considered pre-production
+    pub prefix: String,
+}
+
+impl Default for EarlyVersionSettings {
+    fn default() -> Self {
+        Self {
+            prefix: "0.0.".to_string(),
+        }
+    }
+}
+
+impl RuleSettings for EarlyVersionSettings {}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct OwnershipSettings {
+    /// A release by a first-time publisher within this many days is flagged
+    pub recent_days: i64,
+}
+
+impl Default for OwnershipSettings {
+    fn default() -> Self {
+        Self { recent_days: 90 }
+    }
+}
+
+impl RuleSettings for OwnershipSettings {}
+
+/// The banned rule has no settings of its own, but fails audits by default
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BannedSettings {}
+
+impl RuleSettings for BannedSettings {
+    const LEVEL: Level = Level::Error;
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Rules {
+    pub license: Rule<LicenseSettings>,
+    pub copyleft: RuleToggle,
+    pub new_popular: Rule<NewPopularSettings>,
+    pub typosquat: Rule<TyposquatSettings>,
+    pub no_repository: RuleToggle,
+    /// Formerly `no-documentation`, which flagged a missing documentation link
+    #[serde(alias = "no-documentation")]
+    pub docs_failed: RuleToggle,
+    pub msrv: RuleToggle,
+    pub early_version: Rule<EarlyVersionSettings>,
+    pub ownership_change: Rule<OwnershipSettings>,
+    pub banned: Rule<BannedSettings>,
+}
+
+impl Rules {
+    /// Look up the shared settings of a rule by its id
+    pub fn toggle(&self, id: &str) -> Option<&RuleToggle> {
+        match id {
+            "license" => Some(&self.license.rule),
+            "copyleft" => Some(&self.copyleft),
+            "new-popular" => Some(&self.new_popular.rule),
+            "typosquat" => Some(&self.typosquat.rule),
+            "no-repository" => Some(&self.no_repository),
+            "docs-failed" => Some(&self.docs_failed),
+            "msrv" => Some(&self.msrv),
+            "early-version" => Some(&self.early_version.rule),
+            "ownership-change" => Some(&self.ownership_change.rule),
+            "banned" => Some(&self.banned.rule),
+            _ => None,
+        }
+    }
+}
+
+/// Team policy for the security heuristics, loaded from
+/// `~/.config/crates/policy.toml` and a `.crates-policy.toml` in the project
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Policy {
+    pub rules: Rules,
+    /// Crates that must never be used. A project's list adds to the global
+    /// one; `[rules.banned] allow` exempts a crate from it.
+    pub banned: Vec<String>,
+    /// Known-good crates that skip every heuristic except `banned`
+    pub allow: Vec<String>,
+}
+
+impl Policy {
+    /// Load the global policy with the nearest project policy layered on top.
+    /// Missing files fall back to the built-in defaults.
+    pub fn load() -> AppResult<Self> {
+        let mut merged = toml::Table::new();
+
+        let project = std::env::current_dir()
+            .ok()
+            .and_then(|dir| find_project_policy(&dir));
+
+        for path in global_policy_path().into_iter().chain(project) {
+            if !path.exists() {
+                continue;
+            }
+            let text = fs::read_to_string(&path)?;
+            let table: toml::Table = text
+                .parse()
+                .map_err(|e| format!("Invalid policy file {}: {}", path.display(), e))?;
+            merge_tables(&mut merged, table);
+        }
+
+        Self::from_table(merged)
+    }
+
+    /// Load a single policy file, ignoring the global and project files
+    pub fn load_file(path: &Path) -> AppResult<Self> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read policy file {}: {}", path.display(), e))?;
+        let table: toml::Table = text
+            .parse()
+            .map_err(|e| format!("Invalid policy file {}: {}", path.display(), e))?;
+        Self::from_table(table)
+    }
+
+    fn from_table(table: toml::Table) -> AppResult<Self> {
+        Ok(toml::Value::Table(table)
+            .try_into()
+            .map_err(|e| format!("Invalid policy: {}", e))?)
+    }
+
+    /// Whether a heuristic rule should run for a crate
+    pub fn applies(&self, name: &str, rule: &RuleToggle) -> bool {
+        !self.allow.iter().any(|a| a == name) && rule.applies_to(name)
+    }
+
+    pub fn is_banned(&self, name: &str) -> bool {
+        self.rules.banned.rule.applies_to(name) && self.banned.iter().any(|b| b == name)
+    }
+
+    /// Build a finding for a rule, applying the configured level and severity
+    pub fn finding(
+        &self,
+        rule: &'static str,
+        severity: Severity,
+        message: impl Into<String>,
+    ) -> Finding {
+        let toggle = self.rules.toggle(rule);
+
+        Finding {
+            rule,
+            severity: toggle.and_then(|t| t.severity).unwrap_or(severity),
+            level: toggle.map(|t| t.level).unwrap_or(Level::Warn),
+            message: message.into(),
+        }
+    }
+}
+
+pub fn global_policy_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("crates").join(POLICY_FILE))
+}
+
+// Walk up from the working directory so the policy applies anywhere in a project
+fn find_project_policy(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_POLICY_FILE))
+        .find(|path| path.exists())
+}
+
+// Project settings win; nested tables are merged key by key so a project can
+// tweak a single threshold without restating the whole rule. Other lists
+// (`allow`, `extra-licenses`, ...) are replaced, but the top-level `banned`
+// list is appended to, so a project can't silently drop the team's bans.
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(overlay_table)) => {
+                merge_tables(base_table, overlay_table);
+            }
+            (Some(toml::Value::Array(base_list)), toml::Value::Array(overlay_list))
+                if key == "banned" =>
+            {
+                base_list.extend(overlay_list);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> AppResult<Policy> {
+        Policy::from_table(text.parse()?)
+    }
+
+    #[test]
+    fn rule_settings_and_toggle_share_a_table() {
+        let policy = parse(
+            "[rules.new-popular]\nmax-age-days = 7\nlevel = \"error\"\nallow = [\"serde\"]\n\n\
+             [rules.banned]\nallow = [\"openssl\"]",
+        )
+        .unwrap();
+
+        let rule = &policy.rules.new_popular;
+        assert_eq!(rule.max_age_days, 7);
+        assert_eq!(rule.min_downloads, 10000);
+        assert_eq!(rule.rule.level, Level::Error);
+        assert!(!rule.rule.applies_to("serde"));
+        // Rules keep their own default level when the policy doesn't set one
+        assert_eq!(policy.rules.banned.rule.level, Level::Error);
+    }
+
+    #[test]
+    fn banned_allow_list_exempts_crates() {
+        let policy = parse(
+            "banned = [\"openssl\", \"failure\"]\n\n\
+             [rules.banned]\nallow = [\"openssl\"]",
+        )
+        .unwrap();
+        assert!(!policy.is_banned("openssl"));
+        assert!(policy.is_banned("failure"));
+        assert!(!policy.is_banned("serde"));
+
+        let policy = parse("banned = [\"failure\"]\n[rules.banned]\nenabled = false").unwrap();
+        assert!(!policy.is_banned("failure"));
+    }
+
+    #[test]
+    fn project_bans_add_to_global_ones() {
+        let mut merged: toml::Table =
+            "banned = [\"openssl\"]\n[rules.license]\nextra-licenses = [\"MPL-2.0\"]"
+                .parse()
+                .unwrap();
+        merge_tables(
+            &mut merged,
+            "banned = [\"failure\"]\n[rules.license]\nextra-licenses = [\"ISC\"]"
+                .parse()
+                .unwrap(),
+        );
+        let policy = Policy::from_table(merged).unwrap();
+        assert_eq!(policy.banned, ["openssl", "failure"]);
+        assert_eq!(policy.rules.license.extra_licenses, ["ISC"]);
+    }
+
+    #[test]
+    fn misspelled_rule_key_is_rejected() {
+        let error = parse("[rules.new-popular]\nmax-age-day = 7").unwrap_err();
+        assert!(error.to_string().contains("max-age-day"), "{}", error);
+
+        let error = parse("[rules.copyleft]\nenable = false").unwrap_err();
+        assert!(error.to_string().contains("enable"), "{}", error);
+    }
+}
@@ -0,0 +1,376 @@
+use crate::api::{Crate, SearchParams};
+use crate::filter::LicenseFamily;
+use chrono::DateTime;
+use std::fmt;
+
+// Search box syntax: free text plus `field:value` qualifiers, e.g.
+//
+//   http client license:mit downloads:>1M updated:<90d category:network-programming keyword:async
+//
+// `category` and `keyword` are sent to crates.io; the rest filter results locally.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+}
+
+impl Comparison {
+    fn holds<T: PartialOrd>(&self, left: T, right: T) -> bool {
+        match self {
+            Comparison::Less => left < right,
+            Comparison::LessOrEqual => left <= right,
+            Comparison::Greater => left > right,
+            Comparison::GreaterOrEqual => left >= right,
+            Comparison::Equal => left == right,
+        }
+    }
+}
+
+/// A qualifier evaluated against each result on our side
+#[derive(Debug, Clone, PartialEq)]
+pub enum Predicate {
+    /// A license family name or the start of an SPDX identifier
+    License(String),
+    Downloads(Comparison, u64),
+    /// Compares the number of days since the last update
+    Updated(Comparison, i64),
+}
+
+impl Predicate {
+    pub fn matches(&self, crate_data: &Crate) -> bool {
+        match self {
+            Predicate::License(wanted) => crate_data
+                .license
+                .as_deref()
+                .is_some_and(|license| license_matches(license, wanted)),
+            Predicate::Downloads(cmp, n) => cmp.holds(crate_data.downloads, *n),
+            Predicate::Updated(cmp, days) => DateTime::parse_from_rfc3339(&crate_data.updated_at)
+                .map(|t| {
+                    let age = chrono::Utc::now().signed_duration_since(t).num_days();
+                    cmp.holds(age, *days)
+                })
+                .unwrap_or(false),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryError {
+    pub message: String,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+fn error(message: String) -> QueryError {
+    QueryError { message }
+}
+
+/// A parsed search box query
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Query {
+    pub params: SearchParams,
+    pub predicates: Vec<Predicate>,
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Query, QueryError> {
+        let mut query = Query::default();
+        let mut words = Vec::new();
+
+        for token in input.split_whitespace() {
+            let Some((field, value)) = qualifier(token) else {
+                words.push(token);
+                continue;
+            };
+
+            if value.is_empty() {
+                return Err(error(format!("'{}:' needs a value", field)));
+            }
+
+            match field {
+                "category" => set_once(&mut query.params.category, field, value)?,
+                "keyword" => set_once(&mut query.params.keyword, field, value)?,
+                "license" => query
+                    .predicates
+                    .push(Predicate::License(value.to_lowercase())),
+                "downloads" => {
+                    let (cmp, amount) = comparison(value, Comparison::GreaterOrEqual);
+                    let n = parse_count(amount).ok_or_else(|| {
+                        error(format!(
+                            "downloads:{} is not a number (try downloads:>10K)",
+                            value
+                        ))
+                    })?;
+                    query.predicates.push(Predicate::Downloads(cmp, n));
+                }
+                "updated" => {
+                    let (cmp, amount) = comparison(value, Comparison::LessOrEqual);
+                    let days = parse_days(amount)
+                        .map_err(|e| error(format!("updated:{}: {}", value, e)))?;
+                    query.predicates.push(Predicate::Updated(cmp, days));
+                }
+                other => {
+                    return Err(error(format!(
+                        "Unknown qualifier '{}:' (use license, downloads, updated, category or keyword)",
+                        other
+                    )))
+                }
+            }
+        }
+
+        // Qualifiers alone are fine: crates.io then ranks every crate
+        query.params.text = words.join(" ");
+        Ok(query)
+    }
+}
+
+// `field:value` with an alphabetic field; paths like `std::io` are plain text
+fn qualifier(token: &str) -> Option<(&str, &str)> {
+    let (field, value) = token.split_once(':')?;
+    if field.is_empty() || !field.chars().all(|c| c.is_ascii_alphabetic()) || value.starts_with(':')
+    {
+        return None;
+    }
+    Some((field, value))
+}
+
+fn set_once(slot: &mut Option<String>, field: &str, value: &str) -> Result<(), QueryError> {
+    if slot.is_some() {
+        return Err(error(format!("Only one {}: qualifier is supported", field)));
+    }
+    *slot = Some(value.to_string());
+    Ok(())
+}
+
+// Split a leading operator off a value, using `default` when there is none
+fn comparison(value: &str, default: Comparison) -> (Comparison, &str) {
+    for (prefix, cmp) in [
+        (">=", Comparison::GreaterOrEqual),
+        ("<=", Comparison::LessOrEqual),
+        (">", Comparison::Greater),
+        ("<", Comparison::Less),
+        ("=", Comparison::Equal),
+    ] {
+        if let Some(rest) = value.strip_prefix(prefix) {
+            return (cmp, rest);
+        }
+    }
+    (default, value)
+}
+
+// 1500, 1.5K, 2M or 1B
+fn parse_count(value: &str) -> Option<u64> {
+    let value = value.to_lowercase();
+    let (number, multiplier) = match value.chars().last()? {
+        'k' => (&value[..value.len() - 1], 1_000.0),
+        'm' => (&value[..value.len() - 1], 1_000_000.0),
+        'b' => (&value[..value.len() - 1], 1_000_000_000.0),
+        _ => (value.as_str(), 1.0),
+    };
+    let number: f64 = number.replace('_', "").parse().ok()?;
+    (number >= 0.0).then_some((number * multiplier) as u64)
+}
+
+// 90, 90d, 2w, 6m or 1y, in days
+fn parse_days(value: &str) -> Result<i64, &'static str> {
+    const INVALID: &str = "not a duration (try updated:<90d, 6m or 1y)";
+    let value = value.to_lowercase();
+    let (number, unit) = match value.chars().last().ok_or(INVALID)? {
+        'd' => (&value[..value.len() - 1], 1),
+        'w' => (&value[..value.len() - 1], 7),
+        'm' => (&value[..value.len() - 1], 30),
+        'y' => (&value[..value.len() - 1], 365),
+        _ => (value.as_str(), 1),
+    };
+    let number: i64 = number.parse().map_err(|_| INVALID)?;
+    if number < 0 {
+        return Err(INVALID);
+    }
+    number.checked_mul(unit).ok_or("duration too large")
+}
+
+// Family names match by classification; anything else matches the start of
+// any SPDX identifier in the expression, so `mit` matches `MIT OR Apache-2.0`
+fn license_matches(license: &str, wanted: &str) -> bool {
+    let family = match wanted {
+        "permissive" => Some(LicenseFamily::Permissive),
+        "weak-copyleft" => Some(LicenseFamily::WeakCopyleft),
+        "copyleft" => Some(LicenseFamily::Copyleft),
+        "public-domain" => Some(LicenseFamily::PublicDomain),
+        _ => None,
+    };
+    if let Some(family) = family {
+        return LicenseFamily::of(license) == family;
+    }
+
+    license
+        .split(|c: char| c.is_whitespace() || c == '/' || c == '(' || c == ')')
+        .any(|id| id.to_lowercase().starts_with(wanted))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(text: &str, category: Option<&str>, keyword: Option<&str>) -> SearchParams {
+        SearchParams {
+            text: text.to_string(),
+            category: category.map(String::from),
+            keyword: keyword.map(String::from),
+        }
+    }
+
+    #[test]
+    fn qualifiers() {
+        use Comparison::*;
+        for (input, expected_params, predicates) in [
+            ("http client", params("http client", None, None), vec![]),
+            ("std::io", params("std::io", None, None), vec![]),
+            (
+                "tls license:MIT",
+                params("tls", None, None),
+                vec![Predicate::License("mit".to_string())],
+            ),
+            (
+                "downloads:>1M",
+                params("", None, None),
+                vec![Predicate::Downloads(Greater, 1_000_000)],
+            ),
+            (
+                "downloads:1.5k",
+                params("", None, None),
+                vec![Predicate::Downloads(GreaterOrEqual, 1_500)],
+            ),
+            (
+                "downloads:<=10_000",
+                params("", None, None),
+                vec![Predicate::Downloads(LessOrEqual, 10_000)],
+            ),
+            (
+                "updated:<90d",
+                params("", None, None),
+                vec![Predicate::Updated(Less, 90)],
+            ),
+            (
+                "updated:6m",
+                params("", None, None),
+                vec![Predicate::Updated(LessOrEqual, 180)],
+            ),
+            (
+                "updated:>=1y",
+                params("", None, None),
+                vec![Predicate::Updated(GreaterOrEqual, 365)],
+            ),
+            (
+                "async category:network-programming keyword:tokio runtime",
+                params("async runtime", Some("network-programming"), Some("tokio")),
+                vec![],
+            ),
+        ] {
+            let query = Query::parse(input).unwrap();
+            assert_eq!(query.params, expected_params, "{:?}", input);
+            assert_eq!(query.predicates, predicates, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn malformed_qualifiers() {
+        for (input, message) in [
+            ("license:", "'license:' needs a value"),
+            (
+                "downloads:lots",
+                "downloads:lots is not a number (try downloads:>10K)",
+            ),
+            (
+                "downloads:>-5",
+                "downloads:>-5 is not a number (try downloads:>10K)",
+            ),
+            (
+                "updated:<soon",
+                "updated:<soon: not a duration (try updated:<90d, 6m or 1y)",
+            ),
+            (
+                "category:a category:b",
+                "Only one category: qualifier is supported",
+            ),
+            (
+                "keyword:a keyword:b",
+                "Only one keyword: qualifier is supported",
+            ),
+            (
+                "color:red",
+                "Unknown qualifier 'color:' (use license, downloads, updated, category or keyword)",
+            ),
+        ] {
+            assert_eq!(
+                Query::parse(input).unwrap_err().message,
+                message,
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn license_qualifier_matching() {
+        for (license, wanted, expected) in [
+            ("MIT OR Apache-2.0", "mit", true),
+            ("MIT OR Apache-2.0", "apache", true),
+            ("(MIT OR Apache-2.0)", "apache-2.0", true),
+            ("MIT/Apache-2.0", "apache", true),
+            ("GPL-3.0-only", "mit", false),
+            ("GPL-3.0-only", "copyleft", true),
+            ("MIT OR GPL-3.0-only", "copyleft", false),
+            ("MIT OR GPL-3.0-only", "permissive", true),
+        ] {
+            assert_eq!(
+                license_matches(license, wanted),
+                expected,
+                "{} {}",
+                license,
+                wanted
+            );
+        }
+    }
+
+    #[test]
+    fn parse_days_units() {
+        assert_eq!(parse_days("90"), Ok(90));
+        assert_eq!(parse_days("90d"), Ok(90));
+        assert_eq!(parse_days("2w"), Ok(14));
+        assert_eq!(parse_days("6M"), Ok(180));
+        assert_eq!(parse_days("1y"), Ok(365));
+    }
+
+    #[test]
+    fn parse_days_rejects_non_durations() {
+        for value in ["", "d", "abc", "1.5y", "-3d"] {
+            assert!(parse_days(value).is_err(), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn parse_days_overflow() {
+        assert_eq!(
+            parse_days("9223372036854775807y"),
+            Err("duration too large")
+        );
+        assert_eq!(parse_days("9223372036854775807d"), Ok(i64::MAX));
+
+        let error = Query::parse("updated:<99999999999999999y").unwrap_err();
+        assert_eq!(
+            error.message,
+            "updated:<99999999999999999y: duration too large"
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use crate::api;
+use crate::app::AppResult;
+use crate::diff;
+
+// Files cargo packages as the README when the manifest doesn't name one
+const DEFAULT_NAMES: [&str; 3] = ["README.md", "README.txt", "README"];
+
+/// The README of a published version, as packaged in its `.crate` archive
+#[derive(Debug, Clone)]
+pub struct Readme {
+    pub name: String,
+    pub version: String,
+    /// Path inside the package, e.g. `README.md`
+    pub path: String,
+    pub text: String,
+}
+
+impl Readme {
+    /// Whether the file is markdown rather than plain text (or a format we
+    /// don't render, like reStructuredText)
+    pub fn is_markdown(&self) -> bool {
+        let path = self.path.to_lowercase();
+        !(path.ends_with(".txt") || path.ends_with(".rst") || path.ends_with(".adoc"))
+    }
+}
+
+/// Download a version and pull out its README. crates.io only serves READMEs
+/// already rendered to HTML, so the markdown comes from the archive.
+pub fn fetch(name: &str, version: &str) -> AppResult<Readme> {
+    let files = diff::unpack_crate(&api::download_crate(name, version)?, wanted)?;
+
+    // Cargo copies a README from outside the package into its root and
+    // rewrites the manifest to match, but older uploads kept the original path
+    let declared = match files.get("Cargo.toml") {
+        Some(manifest) => declared_readme(manifest)?,
+        None => None,
+    };
+    let candidates: Vec<String> = match declared {
+        Some(Some(path)) => {
+            let path = path.trim_start_matches("./").to_string();
+            let file_name = path.rsplit('/').next().unwrap_or_default().to_string();
+            vec![path, file_name]
+        }
+        Some(None) => return Err(format!("{} {} has no README", name, version).into()),
+        None => DEFAULT_NAMES.iter().map(|n| n.to_string()).collect(),
+    };
+
+    let (path, text) = candidates
+        .iter()
+        .find_map(|wanted| {
+            files
+                .iter()
+                .find(|(path, _)| path.eq_ignore_ascii_case(wanted))
+        })
+        .ok_or_else(|| format!("{} {} has no README", name, version))?;
+
+    Ok(Readme {
+        name: name.to_string(),
+        version: version.to_string(),
+        path: path.clone(),
+        text: text.clone(),
+    })
+}
+
+// The manifest's `readme` field: a path, `false` for none (Some(None)), or
+// unset so cargo's default names apply (None)
+fn declared_readme(manifest: &str) -> AppResult<Option<Option<String>>> {
+    let manifest: toml::Value = toml::from_str(manifest)
+        .map_err(|e| format!("Could not read the package's Cargo.toml: {}", e))?;
+    let declared = match manifest.get("package").and_then(|p| p.get("readme")) {
+        Some(toml::Value::String(path)) => Some(Some(path.clone())),
+        Some(toml::Value::Boolean(false)) => Some(None),
+        _ => None,
+    };
+    Ok(declared)
+}
+
+// Whether a file in the package could be the README, or is the manifest
+// naming it, so large crates don't end up in memory
+fn wanted(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    let file_name = lower.rsplit('/').next().unwrap_or_default();
+    path == "Cargo.toml"
+        || file_name.starts_with("readme")
+        || [".md", ".markdown", ".txt"]
+            .iter()
+            .any(|ext| file_name.ends_with(ext))
+}
@@ -0,0 +1,1295 @@
+use crate::api;
+use crate::app::AppResult;
+use crate::config;
+use crate::diff;
+use crate::fuzzy;
+use crate::store;
+use flate2::read::GzDecoder;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+// Rustdoc's JSON output is unstable, so building it needs a nightly toolchain
+const TOOLCHAIN: &str = "+nightly";
+// Decompressed rustdoc JSON larger than this is refused
+const MAX_JSON_SIZE: u64 = 512 * 1024 * 1024;
+// Re-exports can lead back up the tree; nesting deeper than this is a cycle
+const MAX_DEPTH: usize = 32;
+// Signatures longer than this put each parameter on its own line
+const WRAP_SIGNATURE: usize = 80;
+
+/// What an item of the API is, in the order a module lists them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ItemKind {
+    Reexport,
+    Module,
+    Macro,
+    Struct,
+    Enum,
+    Union,
+    Trait,
+    Function,
+    TypeAlias,
+    Constant,
+    Static,
+    Field,
+    Variant,
+    AssocType,
+    AssocConst,
+    Method,
+    Other,
+}
+
+impl ItemKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ItemKind::Reexport => "use",
+            ItemKind::Module => "mod",
+            ItemKind::Macro => "macro",
+            ItemKind::Struct => "struct",
+            ItemKind::Enum => "enum",
+            ItemKind::Union => "union",
+            ItemKind::Trait => "trait",
+            ItemKind::Function | ItemKind::Method => "fn",
+            ItemKind::TypeAlias | ItemKind::AssocType => "type",
+            ItemKind::Constant | ItemKind::AssocConst => "const",
+            ItemKind::Static => "static",
+            ItemKind::Field => "field",
+            ItemKind::Variant => "variant",
+            ItemKind::Other => "item",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiItem {
+    pub name: String,
+    /// Path from the crate root, e.g. `serde::de::Deserialize`
+    pub path: String,
+    pub kind: ItemKind,
+    /// The declaration as Rust code, without bodies
+    pub signature: String,
+    /// Doc comment, in markdown
+    pub docs: String,
+    pub deprecation: Option<String>,
+    /// Traits a type implements, e.g. `Clone` or `From<String>`
+    pub implements: Vec<String>,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+}
+
+/// The public API of a crate version, as a tree rooted at the crate
+#[derive(Debug, Clone)]
+pub struct ApiDocs {
+    pub name: String,
+    pub version: String,
+    /// Every item, the crate root first
+    pub items: Vec<ApiItem>,
+}
+
+impl ApiDocs {
+    /// The rows to show as (item, depth): the tree with the `expanded` items
+    /// open, or for a query, the items whose name matches it, best first
+    pub fn rows(&self, expanded: &HashSet<usize>, query: &str) -> Vec<(usize, usize)> {
+        if !query.trim().is_empty() {
+            let mut matches: Vec<(i64, usize)> = self
+                .items
+                .iter()
+                .enumerate()
+                .skip(1)
+                .filter_map(|(i, item)| {
+                    fuzzy::best_match(query, &[&item.name]).map(|m| (m.score, i))
+                })
+                .collect();
+            matches.sort_by(|a, b| {
+                b.0.cmp(&a.0)
+                    .then_with(|| self.items[a.1].path.cmp(&self.items[b.1].path))
+            });
+            return matches.into_iter().map(|(_, i)| (i, 0)).collect();
+        }
+
+        let mut rows = vec![(0, 0)];
+        self.push_rows(0, 1, expanded, &mut rows);
+        rows
+    }
+
+    fn push_rows(
+        &self,
+        item: usize,
+        depth: usize,
+        expanded: &HashSet<usize>,
+        rows: &mut Vec<(usize, usize)>,
+    ) {
+        for &child in &self.items[item].children {
+            rows.push((child, depth));
+            if expanded.contains(&child) {
+                self.push_rows(child, depth + 1, expanded, rows);
+            }
+        }
+    }
+
+    /// The items containing `item`, outermost first
+    pub fn ancestors(&self, item: usize) -> Vec<usize> {
+        let mut ancestors = Vec::new();
+        let mut current = self.items.get(item).and_then(|i| i.parent);
+        while let Some(parent) = current {
+            ancestors.push(parent);
+            current = self.items[parent].parent;
+        }
+        ancestors.reverse();
+        ancestors
+    }
+}
+
+/// Where rustdoc JSON is cached, with the crates built to make it
+pub fn cache_dir() -> AppResult<PathBuf> {
+    let dir = store::data_dir()
+        .ok_or("Could not find a data directory")?
+        .join("rustdoc");
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// The API docs of a version, or why they could not be had
+pub type LoadResult = Result<ApiDocs, String>;
+
+/// Load the API of a version on a background thread
+pub fn spawn_load(name: String, version: String) -> Receiver<LoadResult> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(load(&name, &version).map_err(|e| e.to_string()));
+    });
+    receiver
+}
+
+/// Build the API docs of a version on this machine on a background thread.
+/// This compiles the crate, running its build script and proc macros, so
+/// it is only offered when enabled in the config and confirmed each time.
+pub fn spawn_build(name: String, version: String) -> Receiver<LoadResult> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let docs = cached_path(&name, &version)
+            .and_then(|cached| build(&name, &version, &cached).map(|()| cached))
+            .and_then(|cached| read_cached(&name, &version, &cached));
+        let _ = sender.send(docs.map_err(|e| e.to_string()));
+    });
+    receiver
+}
+
+fn cached_path(name: &str, version: &str) -> AppResult<PathBuf> {
+    Ok(cache_dir()?.join(format!("{}-{}.json", name, version)))
+}
+
+/// Read the rustdoc JSON of a version: the cached copy, else the JSON
+/// docs.rs built, which is then cached. Nothing is compiled here.
+pub fn load(name: &str, version: &str) -> AppResult<ApiDocs> {
+    let cached = cached_path(name, version)?;
+    if !cached.exists() {
+        fetch(name, version, &cached)?;
+    }
+
+    read_cached(name, version, &cached)
+}
+
+fn read_cached(name: &str, version: &str, cached: &Path) -> AppResult<ApiDocs> {
+    let text = fs::read_to_string(cached)
+        .map_err(|e| format!("Could not read {}: {}", cached.display(), e))?;
+    let json: Value = serde_json::from_str(&text)
+        .map_err(|e| format!("{} is not rustdoc JSON: {}", cached.display(), e))?;
+    parse(name, version, &json)
+}
+
+// Download the JSON docs.rs built into `out`
+fn fetch(name: &str, version: &str, out: &Path) -> AppResult<()> {
+    let bytes = api::download_rustdoc_json(name, version)?;
+    let mut json = Vec::new();
+    GzDecoder::new(bytes.as_slice())
+        .take(MAX_JSON_SIZE + 1)
+        .read_to_end(&mut json)
+        .map_err(|e| format!("Could not decompress the rustdoc JSON: {}", e))?;
+    if json.len() as u64 > MAX_JSON_SIZE {
+        return Err(format!(
+            "The rustdoc JSON of {} {} is over {} MB",
+            name,
+            version,
+            MAX_JSON_SIZE / 1024 / 1024
+        )
+        .into());
+    }
+    serde_json::from_slice::<Value>(&json)
+        .map_err(|e| format!("docs.rs sent invalid rustdoc JSON: {}", e))?;
+    fs::write(out, json).map_err(|e| format!("Could not cache {}: {}", out.display(), e))?;
+    Ok(())
+}
+
+// Download the crate and document it with rustdoc's JSON output into `out`
+fn build(name: &str, version: &str, out: &Path) -> AppResult<()> {
+    if !config::get().api_docs.local_build {
+        return Err("Building API docs locally is off; see local-build under [api-docs]".into());
+    }
+    let dir = cache_dir()?;
+    let bytes = api::download_crate(name, version)?;
+    // A fresh copy of the package, so nothing is left from an earlier build
+    let package = dir.join("src").join(format!("{}-{}", name, version));
+    if package.exists() {
+        fs::remove_dir_all(&package)?;
+    }
+    diff::extract_crate(&bytes, &package)?;
+    let target = dir.join("target");
+
+    let output = Command::new("cargo")
+        .arg(TOOLCHAIN)
+        .args(["rustdoc", "--lib", "--manifest-path"])
+        .arg(package.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target)
+        .args(["--", "-Z", "unstable-options", "--output-format", "json"])
+        .output()
+        .map_err(|e| format!("Could not run cargo: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr
+            .lines()
+            .find(|l| l.starts_with("error"))
+            .or_else(|| stderr.lines().rfind(|l| !l.trim().is_empty()))
+            .unwrap_or("unknown error");
+        return Err(format!(
+            "Building the API docs with cargo {} rustdoc failed ({}). Rustdoc JSON can also be put at {}",
+            TOOLCHAIN,
+            reason.trim(),
+            out.display()
+        )
+        .into());
+    }
+
+    let json = target
+        .join("doc")
+        .join(format!("{}.json", lib_name(&package, name)));
+    fs::copy(&json, out).map_err(|e| format!("Could not cache {}: {}", json.display(), e))?;
+    Ok(())
+}
+
+// The library target's name, which rustdoc names the JSON file after
+fn lib_name(package: &Path, name: &str) -> String {
+    fs::read_to_string(package.join("Cargo.toml"))
+        .ok()
+        .and_then(|text| toml::from_str::<toml::Value>(&text).ok())
+        .and_then(|manifest| {
+            manifest
+                .get("lib")
+                .and_then(|lib| lib.get("name"))
+                .and_then(|name| name.as_str())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| name.replace('-', "_"))
+}
+
+fn parse(name: &str, version: &str, json: &Value) -> AppResult<ApiDocs> {
+    let index = json["index"]
+        .as_object()
+        .ok_or("The file has no rustdoc item index")?;
+    let root = index
+        .get(&key(&json["root"]))
+        .ok_or("The rustdoc JSON has no root module")?;
+
+    let mut parser = Parser {
+        index,
+        items: Vec::new(),
+    };
+    parser.add(root, None, None, 0);
+    if parser.items.is_empty() {
+        return Err("The rustdoc JSON has no root module".into());
+    }
+
+    Ok(ApiDocs {
+        name: name.to_string(),
+        version: version.to_string(),
+        items: parser.items,
+    })
+}
+
+// Item ids are numbers in newer formats and strings in older ones, but the
+// index is keyed by strings either way
+fn key(id: &Value) -> String {
+    match id {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+// The single `{"kind": {...}}` entry rustdoc uses for enums
+fn single(value: &Value) -> Option<(&str, &Value)> {
+    let object = value.as_object()?;
+    object.iter().next().map(|(k, v)| (k.as_str(), v))
+}
+
+fn ids(value: &Value) -> Vec<&Value> {
+    value
+        .as_array()
+        .map(|a| a.iter().collect())
+        .unwrap_or_default()
+}
+
+// A field under its current name, or under the name older formats used
+fn either<'v>(value: &'v Value, new: &str, old: &str) -> &'v Value {
+    if value[new].is_null() {
+        &value[old]
+    } else {
+        &value[new]
+    }
+}
+
+struct Parser<'a> {
+    index: &'a Map<String, Value>,
+    items: Vec<ApiItem>,
+}
+
+impl<'a> Parser<'a> {
+    fn lookup(&self, id: &Value) -> Option<&'a Value> {
+        self.index.get(&key(id))
+    }
+
+    // Add an item and what it contains under `parent`, returning its index
+    fn add(
+        &mut self,
+        item: &'a Value,
+        parent: Option<usize>,
+        alias: Option<&str>,
+        depth: usize,
+    ) -> Option<usize> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let (kind_name, inner) = single(&item["inner"])?;
+
+        // A re-export of an item documented here shows the item itself, and
+        // a glob re-export of a module shows its items in place of the glob
+        if kind_name == "use" {
+            if let Some(target) = self.lookup(&inner["id"]) {
+                if !inner["is_glob"].as_bool().unwrap_or(false) {
+                    return self.add(target, parent, inner["name"].as_str(), depth + 1);
+                }
+                if let (Some(parent), Some(("module", module))) = (parent, single(&target["inner"]))
+                {
+                    for child in ids(&module["items"]) {
+                        if let Some(child) = self.lookup(child).filter(|c| is_public(c)) {
+                            self.add(child, Some(parent), None, depth + 1);
+                        }
+                    }
+                    return None;
+                }
+            }
+        }
+
+        let in_type = parent.is_some_and(|p| self.items[p].kind != ItemKind::Module);
+        let kind = match kind_name {
+            "module" => ItemKind::Module,
+            "struct" => ItemKind::Struct,
+            "union" => ItemKind::Union,
+            "enum" => ItemKind::Enum,
+            "trait" => ItemKind::Trait,
+            "function" if in_type => ItemKind::Method,
+            "function" => ItemKind::Function,
+            "constant" => ItemKind::Constant,
+            "static" => ItemKind::Static,
+            "type_alias" | "typedef" => ItemKind::TypeAlias,
+            "macro" | "proc_macro" => ItemKind::Macro,
+            "use" => ItemKind::Reexport,
+            "struct_field" => ItemKind::Field,
+            "variant" => ItemKind::Variant,
+            "assoc_type" => ItemKind::AssocType,
+            "assoc_const" => ItemKind::AssocConst,
+            _ => ItemKind::Other,
+        };
+
+        let name = match kind {
+            ItemKind::Reexport if inner["is_glob"].as_bool().unwrap_or(false) => {
+                format!("{}::*", inner["source"].as_str().unwrap_or_default())
+            }
+            ItemKind::Reexport => inner["name"].as_str().unwrap_or_default().to_string(),
+            _ => alias
+                .or(item["name"].as_str())
+                .unwrap_or_default()
+                .to_string(),
+        };
+        let path = match parent {
+            Some(p) => format!("{}::{}", self.items[p].path, name),
+            None => name.clone(),
+        };
+        let deprecation = item["deprecation"].as_object().map(|d| {
+            match (
+                d.get("since").and_then(Value::as_str),
+                d.get("note").and_then(Value::as_str),
+            ) {
+                (Some(since), Some(note)) => format!("since {}: {}", since, note),
+                (Some(since), None) => format!("since {}", since),
+                (None, Some(note)) => note.to_string(),
+                (None, None) => String::new(),
+            }
+        });
+
+        let index = self.items.len();
+        self.items.push(ApiItem {
+            name: name.clone(),
+            path,
+            kind,
+            signature: String::new(),
+            docs: docs(item),
+            deprecation,
+            implements: Vec::new(),
+            parent,
+            children: Vec::new(),
+        });
+        if let Some(p) = parent {
+            self.items[p].children.push(index);
+        }
+
+        let visibility = if item["visibility"].as_str() == Some("public") {
+            "pub "
+        } else {
+            ""
+        };
+        let signature = match kind_name {
+            "module" => {
+                for child in ids(&inner["items"]) {
+                    if let Some(child) = self.lookup(child).filter(|c| is_public(c)) {
+                        self.add(child, Some(index), None, depth + 1);
+                    }
+                }
+                let mut children = std::mem::take(&mut self.items[index].children);
+                children.sort_by(|&a, &b| {
+                    let (a, b) = (&self.items[a], &self.items[b]);
+                    a.kind.cmp(&b.kind).then_with(|| a.name.cmp(&b.name))
+                });
+                self.items[index].children = children;
+                format!("{}mod {}", visibility, name)
+            }
+            "struct" | "union" => {
+                let (params, where_clause) = generics(&inner["generics"]);
+                let keyword = if kind_name == "struct" {
+                    "struct"
+                } else {
+                    "union"
+                };
+                let head = format!("{}{} {}{}", visibility, keyword, name, params);
+                let body = match single(&inner["kind"]) {
+                    Some(("tuple", fields)) => {
+                        let fields: Vec<String> = ids(fields)
+                            .into_iter()
+                            .map(|id| match self.lookup(id) {
+                                Some(field) => format!(
+                                    "{}{}",
+                                    if is_public(field) { "pub " } else { "" },
+                                    type_str(&field["inner"]["struct_field"])
+                                ),
+                                None => "_".to_string(),
+                            })
+                            .collect();
+                        format!("({}){};", fields.join(", "), where_clause)
+                    }
+                    Some(("plain", plain)) => {
+                        let fields = self.add_fields(&plain["fields"], index, depth);
+                        format!(
+                            "{} {}",
+                            where_clause,
+                            braced(fields, plain["has_stripped_fields"].as_bool())
+                        )
+                    }
+                    _ if kind_name == "union" => {
+                        let fields = self.add_fields(&inner["fields"], index, depth);
+                        format!(
+                            "{} {}",
+                            where_clause,
+                            braced(fields, inner["has_stripped_fields"].as_bool())
+                        )
+                    }
+                    _ => format!("{};", where_clause),
+                };
+                self.add_impls(&inner["impls"], index, depth);
+                format!("{}{}", head, body)
+            }
+            "enum" => {
+                let (params, where_clause) = generics(&inner["generics"]);
+                let mut variants = Vec::new();
+                for id in ids(&inner["variants"]) {
+                    if let Some(child) = self
+                        .lookup(id)
+                        .and_then(|v| self.add(v, Some(index), None, depth + 1))
+                    {
+                        variants.push(format!("{},", self.items[child].signature));
+                    }
+                }
+                self.add_impls(&inner["impls"], index, depth);
+                format!(
+                    "{}enum {}{}{} {}",
+                    visibility,
+                    name,
+                    params,
+                    where_clause,
+                    braced(variants, inner["has_stripped_variants"].as_bool())
+                )
+            }
+            "variant" => {
+                let fields = match single(&inner["kind"]) {
+                    Some(("tuple", fields)) => {
+                        let types: Vec<String> = ids(fields)
+                            .into_iter()
+                            .map(|id| {
+                                self.lookup(id).map_or("_".to_string(), |f| {
+                                    type_str(&f["inner"]["struct_field"])
+                                })
+                            })
+                            .collect();
+                        format!("({})", types.join(", "))
+                    }
+                    Some(("struct", fields)) => {
+                        let fields = self.add_fields(&fields["fields"], index, depth);
+                        let fields = fields.join(" ");
+                        format!(" {{ {} }}", fields.trim_end_matches(','))
+                    }
+                    _ => String::new(),
+                };
+                let discriminant = match inner["discriminant"]["expr"].as_str() {
+                    Some(expr) => format!(" = {}", expr),
+                    None => String::new(),
+                };
+                format!("{}{}{}", name, fields, discriminant)
+            }
+            "struct_field" => format!("{}{}: {}", visibility, name, type_str(inner)),
+            "trait" => {
+                let (params, where_clause) = generics(&inner["generics"]);
+                for child in ids(&inner["items"]) {
+                    if let Some(child) = self.lookup(child) {
+                        self.add(child, Some(index), None, depth + 1);
+                    }
+                }
+                let supertraits = bounds(&inner["bounds"]);
+                format!(
+                    "{}{}{}trait {}{}{}{}",
+                    visibility,
+                    if flag(inner, "is_unsafe", "is_unsafe") {
+                        "unsafe "
+                    } else {
+                        ""
+                    },
+                    if flag(inner, "is_auto", "is_auto") {
+                        "auto "
+                    } else {
+                        ""
+                    },
+                    name,
+                    params,
+                    if supertraits.is_empty() {
+                        String::new()
+                    } else {
+                        format!(": {}", supertraits)
+                    },
+                    where_clause
+                )
+            }
+            "function" => function(&name, inner, visibility),
+            "constant" => {
+                let expr = inner["const"]["expr"]
+                    .as_str()
+                    .or(inner["expr"].as_str())
+                    .unwrap_or("_");
+                format!(
+                    "{}const {}: {} = {};",
+                    visibility,
+                    name,
+                    type_str(&inner["type"]),
+                    expr
+                )
+            }
+            "static" => format!(
+                "{}static {}{}: {};",
+                visibility,
+                if flag(inner, "is_mutable", "mutable") {
+                    "mut "
+                } else {
+                    ""
+                },
+                name,
+                type_str(&inner["type"])
+            ),
+            "type_alias" | "typedef" => {
+                let (params, where_clause) = generics(&inner["generics"]);
+                format!(
+                    "{}type {}{}{} = {};",
+                    visibility,
+                    name,
+                    params,
+                    where_clause,
+                    type_str(&inner["type"])
+                )
+            }
+            "macro" => inner.as_str().unwrap_or_default().to_string(),
+            "proc_macro" => match inner["kind"].as_str() {
+                Some("derive") => format!("#[derive({})]", name),
+                Some("attr") => format!("#[{}]", name),
+                _ => format!("{}!()", name),
+            },
+            "assoc_type" => {
+                let bounds = bounds(&inner["bounds"]);
+                let default = either(inner, "type", "default");
+                format!(
+                    "type {}{}{};",
+                    name,
+                    if bounds.is_empty() {
+                        String::new()
+                    } else {
+                        format!(": {}", bounds)
+                    },
+                    if default.is_null() {
+                        String::new()
+                    } else {
+                        format!(" = {}", type_str(default))
+                    }
+                )
+            }
+            "assoc_const" => {
+                let value = either(inner, "value", "default");
+                format!(
+                    "const {}: {}{};",
+                    name,
+                    type_str(&inner["type"]),
+                    value
+                        .as_str()
+                        .map_or(String::new(), |v| format!(" = {}", v))
+                )
+            }
+            "use" if inner["is_glob"].as_bool().unwrap_or(false) => {
+                format!(
+                    "{}use {}::*;",
+                    visibility,
+                    inner["source"].as_str().unwrap_or("")
+                )
+            }
+            "use" => {
+                let source = inner["source"].as_str().unwrap_or_default();
+                if source.rsplit("::").next() == Some(name.as_str()) {
+                    format!("{}use {};", visibility, source)
+                } else {
+                    format!("{}use {} as {};", visibility, source, name)
+                }
+            }
+            other => format!("{} {}", other.replace('_', " "), name),
+        };
+        self.items[index].signature = signature;
+        Some(index)
+    }
+
+    // Add the fields of a struct, union or struct variant as children,
+    // returning their declarations
+    fn add_fields(&mut self, fields: &'a Value, parent: usize, depth: usize) -> Vec<String> {
+        let mut declarations = Vec::new();
+        for id in ids(fields) {
+            if let Some(child) = self
+                .lookup(id)
+                .and_then(|field| self.add(field, Some(parent), None, depth + 1))
+            {
+                declarations.push(format!("{},", self.items[child].signature));
+            }
+        }
+        declarations
+    }
+
+    // Methods of inherent impls become children; traits implemented by hand
+    // are listed, leaving out auto traits and blanket impls
+    fn add_impls(&mut self, impls: &'a Value, parent: usize, depth: usize) {
+        for id in ids(impls) {
+            let Some(inner) = self.lookup(id).map(|i| &i["inner"]["impl"]) else {
+                continue;
+            };
+            if flag(inner, "is_synthetic", "synthetic") || !inner["blanket_impl"].is_null() {
+                continue;
+            }
+
+            if inner["trait"].is_null() {
+                for item in ids(&inner["items"]) {
+                    if let Some(item) = self.lookup(item).filter(|i| is_public(i)) {
+                        self.add(item, Some(parent), None, depth + 1);
+                    }
+                }
+            } else {
+                let negative = if flag(inner, "is_negative", "negative") {
+                    "!"
+                } else {
+                    ""
+                };
+                let implemented = format!("{}{}", negative, path_str(&inner["trait"]));
+                if !self.items[parent].implements.contains(&implemented) {
+                    self.items[parent].implements.push(implemented);
+                }
+            }
+        }
+    }
+}
+
+// The doc comment, with its intra-doc links (`[Foo]`, `[text][crate::Foo]`)
+// defined as in-page anchors so they show as links without a URL
+fn docs(item: &Value) -> String {
+    let mut docs = item["docs"].as_str().unwrap_or_default().to_string();
+    if let Some(links) = item["links"].as_object().filter(|l| !l.is_empty()) {
+        docs.push('\n');
+        for link in links.keys() {
+            docs.push_str(&format!("\n[{}]: #", link));
+        }
+    }
+    docs
+}
+
+fn is_public(item: &Value) -> bool {
+    matches!(item["visibility"].as_str(), Some("public" | "default"))
+}
+
+fn flag(value: &Value, new: &str, old: &str) -> bool {
+    either(value, new, old).as_bool().unwrap_or(false)
+}
+
+// A `{ ... }` body with one entry per line, noting entries rustdoc left out
+fn braced(mut entries: Vec<String>, stripped: Option<bool>) -> String {
+    if stripped == Some(true) {
+        entries.push("/* private fields */".to_string());
+    }
+    if entries.is_empty() {
+        return "{}".to_string();
+    }
+    format!("{{\n    {}\n}}", entries.join("\n    "))
+}
+
+// Signature printing, from rustdoc's JSON types
+
+fn function(name: &str, inner: &Value, visibility: &str) -> String {
+    let sig = either(inner, "sig", "decl");
+    let header = &inner["header"];
+
+    let mut qualifiers = visibility.to_string();
+    if flag(header, "is_const", "const") {
+        qualifiers.push_str("const ");
+    }
+    if flag(header, "is_async", "async") {
+        qualifiers.push_str("async ");
+    }
+    if flag(header, "is_unsafe", "unsafe") {
+        qualifiers.push_str("unsafe ");
+    }
+    match &header["abi"] {
+        Value::String(abi) if abi == "Rust" => {}
+        Value::Null => {}
+        abi => {
+            let abi = single(abi).map_or_else(|| key(abi), |(name, _)| name.to_string());
+            qualifiers.push_str(&format!("extern \"{}\" ", abi));
+        }
+    }
+
+    let (params, where_clause) = generics(&inner["generics"]);
+    let mut inputs: Vec<String> = ids(&sig["inputs"])
+        .into_iter()
+        .map(|input| {
+            let name = input[0].as_str().unwrap_or("_");
+            self_param(name, &input[1])
+                .unwrap_or_else(|| format!("{}: {}", name, type_str(&input[1])))
+        })
+        .collect();
+    if sig["is_c_variadic"].as_bool().unwrap_or(false) {
+        inputs.push("...".to_string());
+    }
+    let output = if sig["output"].is_null() {
+        String::new()
+    } else {
+        format!(" -> {}", type_str(&sig["output"]))
+    };
+
+    let head = format!("{}fn {}{}", qualifiers, name, params);
+    let line = format!("{}({}){}{}", head, inputs.join(", "), output, where_clause);
+    if line.len() <= WRAP_SIGNATURE || inputs.is_empty() {
+        return line;
+    }
+    format!(
+        "{}(\n    {},\n){}{}",
+        head,
+        inputs.join(",\n    "),
+        output,
+        where_clause
+    )
+}
+
+// `self`, `&self` or `&mut self` rather than `self: &Self`
+fn self_param(name: &str, ty: &Value) -> Option<String> {
+    if name != "self" {
+        return None;
+    }
+    match single(ty)? {
+        ("generic", generic) if generic.as_str() == Some("Self") => Some("self".to_string()),
+        ("borrowed_ref", reference) if reference["type"]["generic"].as_str() == Some("Self") => {
+            Some(format!(
+                "&{}{}self",
+                reference["lifetime"]
+                    .as_str()
+                    .map_or(String::new(), |l| format!("{} ", l)),
+                if flag(reference, "is_mutable", "mutable") {
+                    "mut "
+                } else {
+                    ""
+                }
+            ))
+        }
+        _ => None,
+    }
+}
+
+// `<'a, T: Bound>` and ` where ...` for a generics object
+fn generics(generics: &Value) -> (String, String) {
+    let params: Vec<String> = ids(&generics["params"])
+        .into_iter()
+        .filter_map(|param| {
+            let name = param["name"].as_str().unwrap_or("_");
+            match single(&param["kind"])? {
+                ("lifetime", lifetime) => {
+                    let outlives: Vec<&str> = ids(&lifetime["outlives"])
+                        .into_iter()
+                        .filter_map(Value::as_str)
+                        .collect();
+                    Some(if outlives.is_empty() {
+                        name.to_string()
+                    } else {
+                        format!("{}: {}", name, outlives.join(" + "))
+                    })
+                }
+                ("type", ty) => {
+                    // Parameters made up for `impl Trait` arguments aren't written
+                    if flag(ty, "is_synthetic", "synthetic") {
+                        return None;
+                    }
+                    let bounds = bounds(&ty["bounds"]);
+                    let mut param = name.to_string();
+                    if !bounds.is_empty() {
+                        param.push_str(&format!(": {}", bounds));
+                    }
+                    if !ty["default"].is_null() {
+                        param.push_str(&format!(" = {}", type_str(&ty["default"])));
+                    }
+                    Some(param)
+                }
+                ("const", constant) => {
+                    Some(format!("const {}: {}", name, type_str(&constant["type"])))
+                }
+                _ => None,
+            }
+        })
+        .collect();
+
+    let predicates: Vec<String> = ids(&generics["where_predicates"])
+        .into_iter()
+        .filter_map(|predicate| match single(predicate)? {
+            ("bound_predicate", bound) => Some(format!(
+                "{}: {}",
+                type_str(&bound["type"]),
+                bounds(&bound["bounds"])
+            )),
+            ("lifetime_predicate", lifetime) => Some(format!(
+                "{}: {}",
+                lifetime["lifetime"].as_str().unwrap_or("'_"),
+                ids(&lifetime["outlives"])
+                    .into_iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" + ")
+            )),
+            ("eq_predicate", eq) => {
+                Some(format!("{} = {}", type_str(&eq["lhs"]), term(&eq["rhs"])))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let params = if params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", params.join(", "))
+    };
+    let where_clause = if predicates.is_empty() {
+        String::new()
+    } else {
+        format!(" where {}", predicates.join(", "))
+    };
+    (params, where_clause)
+}
+
+fn bounds(bounds: &Value) -> String {
+    ids(bounds)
+        .into_iter()
+        .filter_map(|bound| match single(bound)? {
+            ("trait_bound", trait_bound) => {
+                let maybe = if trait_bound["modifier"].as_str() == Some("maybe") {
+                    "?"
+                } else {
+                    ""
+                };
+                Some(format!(
+                    "{}{}{}",
+                    higher_ranked(&trait_bound["generic_params"]),
+                    maybe,
+                    path_str(&trait_bound["trait"])
+                ))
+            }
+            ("outlives", lifetime) => lifetime.as_str().map(str::to_string),
+            ("use", captured) => {
+                let captured: Vec<String> = ids(captured)
+                    .into_iter()
+                    .map(|c| match single(c) {
+                        Some((_, name)) => name.as_str().unwrap_or("_").to_string(),
+                        None => c.as_str().unwrap_or("_").to_string(),
+                    })
+                    .collect();
+                Some(format!("use<{}>", captured.join(", ")))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+// `for<'a> ` before a bound that is generic over lifetimes
+fn higher_ranked(params: &Value) -> String {
+    let names: Vec<&str> = ids(params)
+        .into_iter()
+        .filter_map(|p| p["name"].as_str())
+        .collect();
+    if names.is_empty() {
+        String::new()
+    } else {
+        format!("for<{}> ", names.join(", "))
+    }
+}
+
+// A path like `Vec<T>` or `Fn(u8) -> bool`, by its last segment as
+// rustdoc's pages show it
+fn path_str(path: &Value) -> String {
+    let full = either(path, "path", "name").as_str().unwrap_or("_");
+    let name = full.rsplit("::").next().unwrap_or(full);
+    format!("{}{}", name, args_str(&path["args"]))
+}
+
+fn args_str(args: &Value) -> String {
+    match single(args) {
+        Some(("angle_bracketed", angle)) => {
+            let mut parts: Vec<String> = ids(&angle["args"])
+                .into_iter()
+                .map(|arg| match single(arg) {
+                    Some(("lifetime", lifetime)) => lifetime.as_str().unwrap_or("'_").to_string(),
+                    Some(("type", ty)) => type_str(ty),
+                    Some(("const", constant)) => {
+                        constant["expr"].as_str().unwrap_or("_").to_string()
+                    }
+                    _ => "_".to_string(),
+                })
+                .collect();
+            for constraint in ids(either(angle, "constraints", "bindings")) {
+                let name = format!(
+                    "{}{}",
+                    constraint["name"].as_str().unwrap_or("_"),
+                    args_str(&constraint["args"])
+                );
+                parts.push(match single(&constraint["binding"]) {
+                    Some(("equality", value)) => format!("{} = {}", name, term(value)),
+                    Some(("constraint", bound)) => format!("{}: {}", name, bounds(bound)),
+                    _ => name,
+                });
+            }
+            if parts.is_empty() {
+                String::new()
+            } else {
+                format!("<{}>", parts.join(", "))
+            }
+        }
+        Some(("parenthesized", parenthesized)) => {
+            let inputs: Vec<String> = ids(&parenthesized["inputs"])
+                .into_iter()
+                .map(type_str)
+                .collect();
+            let output = if parenthesized["output"].is_null() {
+                String::new()
+            } else {
+                format!(" -> {}", type_str(&parenthesized["output"]))
+            };
+            format!("({}){}", inputs.join(", "), output)
+        }
+        _ if args.as_str() == Some("return_type_notation") => "(..)".to_string(),
+        _ => String::new(),
+    }
+}
+
+// The right-hand side of an associated type or const binding
+fn term(value: &Value) -> String {
+    match single(value) {
+        Some(("type", ty)) => type_str(ty),
+        Some(("constant", constant)) => constant["expr"].as_str().unwrap_or("_").to_string(),
+        _ => "_".to_string(),
+    }
+}
+
+fn type_str(ty: &Value) -> String {
+    let Some((kind, value)) = single(ty) else {
+        return "_".to_string();
+    };
+    match kind {
+        "resolved_path" => path_str(value),
+        "generic" | "primitive" => value.as_str().unwrap_or("_").to_string(),
+        "dyn_trait" => {
+            let mut parts: Vec<String> = ids(&value["traits"])
+                .into_iter()
+                .map(|poly| {
+                    format!(
+                        "{}{}",
+                        higher_ranked(&poly["generic_params"]),
+                        path_str(&poly["trait"])
+                    )
+                })
+                .collect();
+            if let Some(lifetime) = value["lifetime"].as_str() {
+                parts.push(lifetime.to_string());
+            }
+            format!("dyn {}", parts.join(" + "))
+        }
+        "function_pointer" => {
+            let sig = either(value, "sig", "decl");
+            let inputs: Vec<String> = ids(&sig["inputs"])
+                .into_iter()
+                .map(|input| type_str(&input[1]))
+                .collect();
+            let output = if sig["output"].is_null() {
+                String::new()
+            } else {
+                format!(" -> {}", type_str(&sig["output"]))
+            };
+            format!(
+                "{}fn({}){}",
+                higher_ranked(&value["generic_params"]),
+                inputs.join(", "),
+                output
+            )
+        }
+        "tuple" => {
+            let types: Vec<String> = ids(value).into_iter().map(type_str).collect();
+            match types.len() {
+                1 => format!("({},)", types[0]),
+                _ => format!("({})", types.join(", ")),
+            }
+        }
+        "slice" => format!("[{}]", type_str(value)),
+        "array" => format!(
+            "[{}; {}]",
+            type_str(&value["type"]),
+            value["len"].as_str().unwrap_or("_")
+        ),
+        "pat" => type_str(&value["type"]),
+        "impl_trait" => format!("impl {}", bounds(value)),
+        "raw_pointer" => format!(
+            "*{} {}",
+            if flag(value, "is_mutable", "mutable") {
+                "mut"
+            } else {
+                "const"
+            },
+            type_str(&value["type"])
+        ),
+        "borrowed_ref" => format!(
+            "&{}{}{}",
+            value["lifetime"]
+                .as_str()
+                .map_or(String::new(), |l| format!("{} ", l)),
+            if flag(value, "is_mutable", "mutable") {
+                "mut "
+            } else {
+                ""
+            },
+            type_str(&value["type"])
+        ),
+        "qualified_path" => {
+            let name = format!(
+                "{}{}",
+                value["name"].as_str().unwrap_or("_"),
+                args_str(&value["args"])
+            );
+            // An empty trait path is the `T::Assoc` shorthand
+            if either(&value["trait"], "path", "name")
+                .as_str()
+                .is_none_or(str::is_empty)
+            {
+                format!("{}::{}", type_str(&value["self_type"]), name)
+            } else {
+                format!(
+                    "<{} as {}>::{}",
+                    type_str(&value["self_type"]),
+                    path_str(&value["trait"]),
+                    name
+                )
+            }
+        }
+        _ => "_".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // rustdoc's JSON for fixtures/rustdoc.rs, with the `paths` table and
+    // most of the auto trait and blanket impls cut to keep it small
+    fn demo() -> ApiDocs {
+        let json: Value = serde_json::from_str(include_str!("../fixtures/rustdoc.json")).unwrap();
+        parse("demo", "0.1.0", &json).unwrap()
+    }
+
+    fn item<'d>(docs: &'d ApiDocs, path: &str) -> &'d ApiItem {
+        docs.items
+            .iter()
+            .find(|i| i.path == path)
+            .unwrap_or_else(|| panic!("no item {}", path))
+    }
+
+    fn children(docs: &ApiDocs, path: &str) -> Vec<(ItemKind, String)> {
+        item(docs, path)
+            .children
+            .iter()
+            .map(|&c| (docs.items[c].kind, docs.items[c].name.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn modules_list_public_items_by_kind_then_name() {
+        let docs = demo();
+        assert_eq!(
+            children(&docs, "demo"),
+            [
+                (ItemKind::Module, "shapes".to_string()),
+                (ItemKind::Macro, "list".to_string()),
+                (ItemKind::Struct, "Labelled".to_string()),
+                (ItemKind::Struct, "Round".to_string()),
+                (ItemKind::Enum, "Level".to_string()),
+                (ItemKind::Enum, "Outcome".to_string()),
+                (ItemKind::Function, "apply_all".to_string()),
+                (ItemKind::Function, "old".to_string()),
+                (ItemKind::TypeAlias, "Res".to_string()),
+                (ItemKind::Constant, "MAX".to_string()),
+            ]
+        );
+        assert!(!docs.items.iter().any(|i| i.name == "not_exported"));
+    }
+
+    #[test]
+    fn signatures_read_like_rust() {
+        let docs = demo();
+        for (path, signature) in [
+            ("demo::shapes", "pub mod shapes"),
+            ("demo::shapes::Area", "pub trait Area"),
+            ("demo::shapes::Area::Unit", "type Unit;"),
+            ("demo::shapes::Area::SIDES", "const SIDES: u32 = 0;"),
+            ("demo::shapes::Area::area", "fn area(&self) -> f64"),
+            (
+                "demo::shapes::Circle",
+                "pub struct Circle {\n    pub radius: f64,\n}",
+            ),
+            (
+                "demo::shapes::Circle::new",
+                "pub const fn new(radius: f64) -> Self",
+            ),
+            (
+                "demo::shapes::Circle::scale",
+                "pub fn scale(&mut self, by: f64)",
+            ),
+            (
+                "demo::Outcome",
+                "pub enum Outcome<T> {\n    Done(T),\n    Failed { reason: String },\n    Skipped,\n}",
+            ),
+            ("demo::Level::High", "High = 10"),
+            (
+                "demo::Labelled",
+                "pub struct Labelled<'a, T: Clone + 'a>(pub &'a str, pub T);",
+            ),
+            (
+                "demo::apply_all",
+                "pub fn apply_all<'a, T, F>(\n    items: &'a mut [T],\n    keep: impl Fn(&T) -> bool,\n    f: F,\n) -> usize where F: FnMut(&mut T)",
+            ),
+            (
+                "demo::old",
+                "pub async unsafe fn old(\n    values: Vec<Option<u8>>,\n) -> Result<(), Box<dyn Error + Send>>",
+            ),
+            ("demo::MAX", "pub const MAX: usize = 64;"),
+            ("demo::Res", "pub type Res<T> = Result<T, String>;"),
+            (
+                "demo::list",
+                "macro_rules! list {\n    ($($x:expr),*) => { ... };\n}",
+            ),
+        ] {
+            assert_eq!(item(&docs, path).signature, signature, "{}", path);
+        }
+    }
+
+    #[test]
+    fn impls_add_methods_and_list_traits() {
+        let docs = demo();
+        assert_eq!(
+            children(&docs, "demo::shapes::Circle"),
+            [
+                (ItemKind::Field, "radius".to_string()),
+                (ItemKind::Method, "new".to_string()),
+                (ItemKind::Method, "scale".to_string()),
+            ]
+        );
+
+        // Auto traits and blanket impls are left out
+        let implements = &item(&docs, "demo::shapes::Circle").implements;
+        for name in ["Debug", "Clone", "Copy", "PartialEq", "Area"] {
+            assert!(implements.iter().any(|i| i == name), "{}", name);
+        }
+        for name in ["Send", "Sync", "Unpin", "Into<U>", "From<T>", "Any"] {
+            assert!(!implements.iter().any(|i| i == name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn reexports_show_the_item_under_its_new_name() {
+        let docs = demo();
+        let round = item(&docs, "demo::Round");
+        assert_eq!(round.kind, ItemKind::Struct);
+        assert_eq!(round.docs, "A circle.");
+        assert_eq!(
+            children(&docs, "demo::Round"),
+            children(&docs, "demo::shapes::Circle")
+        );
+    }
+
+    #[test]
+    fn docs_and_deprecation_are_kept() {
+        let docs = demo();
+        assert_eq!(
+            item(&docs, "demo").docs,
+            "A small crate documenting one of each kind of item."
+        );
+        assert_eq!(
+            item(&docs, "demo::old").deprecation.as_deref(),
+            Some("since 0.1.0: use apply_all")
+        );
+        assert_eq!(item(&docs, "demo::MAX").deprecation, None);
+    }
+
+    #[test]
+    fn search_finds_items_anywhere_in_the_tree() {
+        let docs = demo();
+        let found: Vec<&str> = docs
+            .rows(&HashSet::new(), "scale")
+            .into_iter()
+            .map(|(i, _)| docs.items[i].path.as_str())
+            .collect();
+        assert_eq!(found, ["demo::Round::scale", "demo::shapes::Circle::scale"]);
+    }
+}
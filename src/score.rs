@@ -0,0 +1,323 @@
+use crate::api::{self, Crate, DocsBuild, Finding, Severity, User, Version};
+use chrono::DateTime;
+
+/// One health signal feeding into the composite trust score
+#[derive(Debug, Clone)]
+pub struct Signal {
+    pub name: &'static str,
+    /// 0-100, or `None` when the signal could not be determined
+    pub score: Option<u8>,
+    pub weight: u32,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Scorecard {
+    pub signals: Vec<Signal>,
+}
+
+impl Scorecard {
+    /// Weighted average of the signals that could be determined
+    pub fn total(&self) -> u8 {
+        let (sum, weights) = self
+            .signals
+            .iter()
+            .filter_map(|s| s.score.map(|score| (score as u32 * s.weight, s.weight)))
+            .fold((0, 0), |(sum, weights), (score, weight)| {
+                (sum + score, weights + weight)
+            });
+
+        sum.checked_div(weights).unwrap_or(0) as u8
+    }
+}
+
+/// Build a scorecard for a crate. Owners and versions are passed in because
+/// callers usually have them already; everything else is fetched here.
+pub fn scorecard(
+    crate_data: &Crate,
+    findings: &[Finding],
+    owners: Option<&[User]>,
+    versions: &[Version],
+) -> Scorecard {
+    let name = crate_data.name.as_str();
+    let version = crate_data.max_version.as_str();
+
+    let signals = vec![
+        heuristics_signal(findings),
+        reverse_dependencies_signal(api::reverse_dependency_count(name).ok()),
+        owners_signal(owners),
+        cadence_signal(versions),
+        archived_signal(
+            crate_data
+                .repository
+                .as_deref()
+                .and_then(|repo| api::repository_info(repo).ok().map(|r| r.archived)),
+        ),
+        advisories_signal(api::advisories(name, version).ok().map(|a| a.len())),
+        docs_signal(api::docs_status(name, version).ok().map(|s| s.build)),
+        age_signal(days_since(&crate_data.created_at)),
+    ];
+
+    Scorecard { signals }
+}
+
+fn heuristics_signal(findings: &[Finding]) -> Signal {
+    let penalty: u32 = findings
+        .iter()
+        .map(|f| match f.severity {
+            Severity::High => 40,
+            Severity::Medium => 20,
+            Severity::Low => 5,
+        })
+        .sum();
+
+    Signal {
+        name: "Heuristics",
+        score: Some(100u32.saturating_sub(penalty) as u8),
+        weight: 25,
+        detail: match findings.len() {
+            0 => "no warnings".to_string(),
+            1 => "1 warning".to_string(),
+            n => format!("{} warnings", n),
+        },
+    }
+}
+
+fn reverse_dependencies_signal(count: Option<u64>) -> Signal {
+    Signal {
+        name: "Reverse deps",
+        score: count.map(|n| match n {
+            0 => 20,
+            1..=9 => 50,
+            10..=99 => 75,
+            _ => 100,
+        }),
+        weight: 15,
+        detail: count
+            .map(|n| format!("{} dependents", n))
+            .unwrap_or_else(|| "unknown".to_string()),
+    }
+}
+
+// More owners means a lower bus factor
+fn owners_signal(owners: Option<&[User]>) -> Signal {
+    let count = owners.map(|o| o.len());
+
+    Signal {
+        name: "Owners",
+        score: count.map(|n| match n {
+            0 => 0,
+            1 => 50,
+            2 => 80,
+            _ => 100,
+        }),
+        weight: 10,
+        detail: count
+            .map(|n| format!("{} owners", n))
+            .unwrap_or_else(|| "unknown".to_string()),
+    }
+}
+
+fn cadence_signal(versions: &[Version]) -> Signal {
+    let releases: Vec<&Version> = versions.iter().filter(|v| !v.yanked).collect();
+    let last_release = releases.first().and_then(|v| days_since(&v.created_at));
+
+    let score = last_release.map(|days| {
+        let recency = match days {
+            0..=89 => 100,
+            90..=364 => 75,
+            365..=729 => 50,
+            _ => 25,
+        };
+        // A single release says little about how the crate is maintained
+        if releases.len() < 2 {
+            recency.min(50)
+        } else {
+            recency
+        }
+    });
+
+    Signal {
+        name: "Release cadence",
+        score,
+        weight: 15,
+        detail: match last_release {
+            Some(days) => format!("{} releases, last {} days ago", releases.len(), days),
+            None => "unknown".to_string(),
+        },
+    }
+}
+
+fn archived_signal(archived: Option<bool>) -> Signal {
+    Signal {
+        name: "Repository",
+        score: archived.map(|a| if a { 0 } else { 100 }),
+        weight: 10,
+        detail: match archived {
+            Some(true) => "archived".to_string(),
+            Some(false) => "active".to_string(),
+            None => "unknown".to_string(),
+        },
+    }
+}
+
+fn advisories_signal(count: Option<usize>) -> Signal {
+    Signal {
+        name: "Advisories",
+        score: count.map(|n| match n {
+            0 => 100,
+            1 => 30,
+            _ => 0,
+        }),
+        weight: 15,
+        detail: count
+            .map(|n| format!("{} open for latest version", n))
+            .unwrap_or_else(|| "unknown".to_string()),
+    }
+}
+
+fn docs_signal(build: Option<DocsBuild>) -> Signal {
+    Signal {
+        name: "docs.rs build",
+        score: match build {
+            Some(DocsBuild::Built) => Some(100),
+            Some(DocsBuild::Failed) => Some(0),
+            _ => None,
+        },
+        weight: 5,
+        detail: build
+            .map(|b| b.label().to_string())
+            .unwrap_or_else(|| "unknown".to_string()),
+    }
+}
+
+fn age_signal(days: Option<i64>) -> Signal {
+    Signal {
+        name: "Age",
+        score: days.map(|d| match d {
+            0..=29 => 20,
+            30..=179 => 50,
+            180..=364 => 75,
+            _ => 100,
+        }),
+        weight: 5,
+        detail: days
+            .map(|d| format!("{} days", d))
+            .unwrap_or_else(|| "unknown".to_string()),
+    }
+}
+
+fn days_since(timestamp: &str) -> Option<i64> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|t| chrono::Utc::now().signed_duration_since(t).num_days())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::Level;
+
+    fn signal(score: Option<u8>, weight: u32) -> Signal {
+        Signal {
+            name: "test",
+            score,
+            weight,
+            detail: String::new(),
+        }
+    }
+
+    fn total(signals: Vec<Signal>) -> u8 {
+        Scorecard { signals }.total()
+    }
+
+    fn version(num: &str, days_old: i64, yanked: bool) -> Version {
+        let created_at = chrono::Utc::now() - chrono::Duration::days(days_old);
+        serde_json::from_value(serde_json::json!({
+            "num": num,
+            "created_at": created_at.to_rfc3339(),
+            "downloads": 0,
+            "yanked": yanked,
+            "license": null,
+            "crate_size": null,
+            "published_by": null,
+        }))
+        .unwrap()
+    }
+
+    fn finding(severity: Severity) -> Finding {
+        Finding {
+            rule: "test",
+            severity,
+            level: Level::Warn,
+            message: String::new(),
+        }
+    }
+
+    #[test]
+    fn total_is_a_weighted_average() {
+        assert_eq!(total(vec![signal(Some(100), 3), signal(Some(0), 1)]), 75);
+        assert_eq!(total(vec![signal(Some(80), 10), signal(Some(40), 10)]), 60);
+        // Rounded down
+        assert_eq!(total(vec![signal(Some(100), 2), signal(Some(0), 1)]), 66);
+    }
+
+    #[test]
+    fn missing_signals_are_left_out_of_the_average() {
+        assert_eq!(total(vec![signal(Some(90), 5), signal(None, 95)]), 90);
+        assert_eq!(total(vec![signal(None, 5), signal(None, 10)]), 0);
+        assert_eq!(total(Vec::new()), 0);
+
+        let signals = vec![
+            heuristics_signal(&[]),
+            reverse_dependencies_signal(None),
+            owners_signal(None),
+            archived_signal(None),
+            advisories_signal(Some(1)),
+            docs_signal(None),
+        ];
+        // Heuristics 100 * 25 and advisories 30 * 15 over 40
+        assert_eq!(total(signals), 73);
+    }
+
+    #[test]
+    fn heuristics_lose_points_per_finding() {
+        let signal = heuristics_signal(&[finding(Severity::High), finding(Severity::Low)]);
+        assert_eq!(signal.score, Some(55));
+        assert_eq!(signal.detail, "2 warnings");
+
+        let findings: Vec<Finding> = (0..3).map(|_| finding(Severity::High)).collect();
+        assert_eq!(heuristics_signal(&findings).score, Some(0));
+    }
+
+    #[test]
+    fn cadence_counts_releases_that_were_not_yanked() {
+        assert_eq!(cadence_signal(&[]).score, None);
+
+        let versions = [version("1.1.0", 10, false), version("1.0.0", 400, false)];
+        assert_eq!(cadence_signal(&versions).score, Some(100));
+
+        // The newest release was yanked, leaving one release over a year old
+        let versions = [version("1.1.0", 10, true), version("1.0.0", 400, false)];
+        let signal = cadence_signal(&versions);
+        assert_eq!(signal.score, Some(50));
+        assert!(signal.detail.starts_with("1 releases, last 400 days ago"));
+
+        // A single recent release is capped
+        assert_eq!(
+            cadence_signal(&[version("0.1.0", 3, false)]).score,
+            Some(50)
+        );
+    }
+
+    #[test]
+    fn signal_thresholds() {
+        assert_eq!(reverse_dependencies_signal(Some(0)).score, Some(20));
+        assert_eq!(reverse_dependencies_signal(Some(100)).score, Some(100));
+        assert_eq!(archived_signal(Some(true)).score, Some(0));
+        assert_eq!(advisories_signal(Some(2)).score, Some(0));
+        assert_eq!(docs_signal(Some(DocsBuild::Failed)).score, Some(0));
+        assert_eq!(age_signal(Some(29)).score, Some(20));
+        assert_eq!(age_signal(Some(365)).score, Some(100));
+    }
+}
@@ -0,0 +1,56 @@
+use crate::app::{AppResult, Tab};
+use crate::columns::Column;
+use crate::config;
+use crate::store::data_dir;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+const SESSION_FILE: &str = "session.json";
+
+/// Where the user left off, saved on exit and restored on the next launch
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub tab: Tab,
+    pub search_query: String,
+    /// Index into the tab's underlying list, ignoring any quick filter
+    pub selected_index: usize,
+    pub trend_period: String,
+    /// Names of the compared crates, in order
+    pub compared: Vec<String>,
+    pub current_set: Option<String>,
+    /// Compare table columns, in order; empty for the defaults
+    pub compare_columns: Vec<Column>,
+    pub compare_sort: Option<Column>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            tab: Tab::Search,
+            search_query: String::new(),
+            selected_index: 0,
+            trend_period: config::get().general.trend_period.clone(),
+            compared: Vec::new(),
+            current_set: None,
+            compare_columns: Vec::new(),
+            compare_sort: None,
+        }
+    }
+}
+
+impl Session {
+    /// The last saved session, or `None` on first launch or if it is unreadable
+    pub fn load() -> Option<Self> {
+        let path = data_dir()?.join(SESSION_FILE);
+        let json = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    pub fn save(&self) -> AppResult<()> {
+        let dir = data_dir().ok_or("Could not determine data directory")?;
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join(SESSION_FILE), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use crate::app::AppResult;
+use crate::store::{load_json, save_json};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const SETS_FILE: &str = "comparison_sets.json";
+
+/// Named lists of crates to compare, persisted as JSON in the data directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ComparisonSets {
+    /// Set name -> crate names, in the order they were compared
+    pub sets: BTreeMap<String, Vec<String>>,
+}
+
+impl ComparisonSets {
+    /// Load the saved sets, falling back to none if the file is missing or
+    /// damaged. A damaged file is kept as a backup, and a message says where.
+    pub fn load() -> (Self, Option<String>) {
+        load_json(SETS_FILE)
+    }
+
+    pub fn save(&self) -> AppResult<()> {
+        save_json(SETS_FILE, self)
+    }
+
+    /// Look a set up by its exact name or its slug, so "HTTP clients" can
+    /// be given as `http-clients` on the command line
+    pub fn find(&self, name: &str) -> Option<(&String, &Vec<String>)> {
+        self.sets.get_key_value(name).or_else(|| {
+            let wanted = slug(name);
+            self.sets.iter().find(|(set, _)| slug(set) == wanted)
+        })
+    }
+
+    /// Save a set under a name, replacing any set already called that
+    pub fn store(&mut self, name: &str, crates: Vec<String>) -> AppResult<()> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("A comparison set needs a name".into());
+        }
+        self.sets.insert(name.to_string(), crates);
+        Ok(())
+    }
+
+    pub fn rename(&mut self, old: &str, new: &str) -> AppResult<()> {
+        let new = new.trim();
+        if new.is_empty() {
+            return Err("A comparison set needs a name".into());
+        }
+        if new != old && self.sets.contains_key(new) {
+            return Err(format!("A set called '{}' already exists", new).into());
+        }
+        let crates = self
+            .sets
+            .remove(old)
+            .ok_or_else(|| format!("No set called '{}'", old))?;
+        self.sets.insert(new.to_string(), crates);
+        Ok(())
+    }
+
+    pub fn delete(&mut self, name: &str) {
+        self.sets.remove(name);
+    }
+}
+
+// Lowercase with runs of anything but letters and digits turned into dashes
+fn slug(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
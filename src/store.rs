@@ -0,0 +1,192 @@
+use crate::api::{User, Version};
+use crate::app::AppResult;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+
+const STORE_FILE: &str = "store.json";
+
+/// What we knew about a crate the last time it was inspected
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CrateRecord {
+    /// Owners the last time the crate was seen
+    pub owners: Vec<String>,
+    /// Owners the user last accepted. Changes are reported against these
+    /// until they are acknowledged, not just the first time they're seen.
+    #[serde(default)]
+    pub acknowledged_owners: Option<Vec<String>>,
+    /// Version number -> login of the account that published it
+    pub publishers: BTreeMap<String, String>,
+    pub last_seen: String,
+}
+
+impl CrateRecord {
+    /// The owners that changes are reported against
+    pub fn baseline(&self) -> &[String] {
+        self.acknowledged_owners.as_deref().unwrap_or(&self.owners)
+    }
+}
+
+/// Local store of crate metadata, persisted as JSON in the data directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Store {
+    pub crates: BTreeMap<String, CrateRecord>,
+}
+
+/// Directory for persistent application data (e.g. `~/.local/share/crates`)
+pub fn data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("crates"))
+}
+
+/// An exclusive advisory lock on a data file (`watchlist.json` is locked
+/// through `watchlist.lock`), released when the returned file is dropped
+pub fn lock(dir: &Path, file: &str) -> AppResult<File> {
+    fs::create_dir_all(dir)?;
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(dir.join(Path::new(file).with_extension("lock")))?;
+    lock.lock()?;
+    Ok(lock)
+}
+
+/// Read a JSON data file, `None` if there isn't one yet. A file that can't
+/// be parsed is moved to `<file>.bak`, so the next save doesn't overwrite
+/// what might still be recovered by hand, and the error says so.
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, String> {
+    let json = match fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Could not read {}: {}", path.display(), e)),
+    };
+    let e = match serde_json::from_str(&json) {
+        Ok(value) => return Ok(Some(value)),
+        Err(e) => e,
+    };
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    let backup = PathBuf::from(backup);
+    Err(match fs::rename(path, &backup) {
+        Ok(()) => format!(
+            "{} was damaged ({}) and has been moved to {}",
+            path.display(),
+            e,
+            backup.display()
+        ),
+        Err(_) => format!("{} is damaged: {}", path.display(), e),
+    })
+}
+
+/// Load a JSON data file, falling back to the default if it is missing or
+/// damaged. The message says what happened to a damaged file.
+pub fn load_json<T: DeserializeOwned + Default>(file: &str) -> (T, Option<String>) {
+    let Some(dir) = data_dir() else {
+        return (T::default(), None);
+    };
+    match read_json(&dir.join(file)) {
+        Ok(value) => (value.unwrap_or_default(), None),
+        Err(e) => (T::default(), Some(e)),
+    }
+}
+
+/// Save a JSON data file. The file is locked like the watchlist, and the new
+/// contents replace it in one rename, so another instance never reads it
+/// half written.
+pub fn save_json<T: Serialize>(file: &str, value: &T) -> AppResult<()> {
+    let dir = data_dir().ok_or("Could not determine data directory")?;
+    let lock = lock(&dir, file)?;
+    let temp = dir.join(format!("{}.tmp", file));
+    fs::write(&temp, serde_json::to_string_pretty(value)?)?;
+    fs::rename(&temp, dir.join(file))?;
+    drop(lock);
+    Ok(())
+}
+
+impl Store {
+    /// Load the store, falling back to an empty one if it is missing or
+    /// damaged. A damaged file is kept as a backup, and a message says where.
+    pub fn load() -> (Self, Option<String>) {
+        load_json(STORE_FILE)
+    }
+
+    pub fn save(&self) -> AppResult<()> {
+        save_json(STORE_FILE, self)
+    }
+
+    /// Record the current owners and publishers of a crate. The acknowledged
+    /// owners carry over from the previous record; the first time a crate is
+    /// seen they are its current owners.
+    pub fn record(&mut self, name: &str, owners: &[User], versions: &[Version]) -> &CrateRecord {
+        let publishers = versions
+            .iter()
+            .filter_map(|v| {
+                v.published_by
+                    .as_ref()
+                    .map(|u| (v.num.clone(), u.login.clone()))
+            })
+            .collect();
+
+        let owners: Vec<String> = owners.iter().map(|o| o.login.clone()).collect();
+        // Records written before acknowledgements existed count what was last
+        // seen as accepted
+        let acknowledged_owners = self
+            .crates
+            .get(name)
+            .map(|previous| previous.baseline().to_vec())
+            .unwrap_or_else(|| owners.clone());
+
+        let record = CrateRecord {
+            owners,
+            acknowledged_owners: Some(acknowledged_owners),
+            publishers,
+            last_seen: chrono::Utc::now().to_rfc3339(),
+        };
+
+        self.crates.insert(name.to_string(), record);
+        &self.crates[name]
+    }
+
+    /// Accept the owners last seen for a crate, so changes up to now are no
+    /// longer reported. Returns false if the crate has never been seen.
+    pub fn acknowledge(&mut self, name: &str) -> bool {
+        match self.crates.get_mut(name) {
+            Some(record) => {
+                record.acknowledged_owners = Some(record.owners.clone());
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn damaged_files_are_moved_aside() {
+        let dir = std::env::temp_dir().join(format!("crates-store-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sets.json");
+
+        assert_eq!(read_json::<Vec<u32>>(&path), Ok(None));
+
+        fs::write(&path, "[1, 2]").unwrap();
+        assert_eq!(read_json(&path), Ok(Some(vec![1u32, 2])));
+
+        fs::write(&path, "[1, 2").unwrap();
+        let e = read_json::<Vec<u32>>(&path).unwrap_err();
+        assert!(e.contains("sets.json.bak"), "{}", e);
+        assert!(!path.exists());
+        assert_eq!(
+            fs::read_to_string(dir.join("sets.json.bak")).unwrap(),
+            "[1, 2"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
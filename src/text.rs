@@ -0,0 +1,199 @@
+use unicode_width::UnicodeWidthStr;
+
+// Crate descriptions and READMEs come straight from crates.io, so every helper
+// here measures terminal columns rather than bytes or chars and never slices
+// inside a multi-byte character. Widths are always measured on whole strings:
+// a string isn't as wide as the sum of its characters when it holds control
+// characters or sequences like emoji variation selectors.
+
+/// Number of terminal columns needed to display a string
+pub fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// Make a string safe to render on a single line: tabs become spaces and
+/// newlines or other control characters become a single space
+pub fn single_line(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\t' => out.push_str("    "),
+            c if c.is_control() => out.push(' '),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Shorten a string to at most `max_width` columns, ending with "..." when
+/// anything was cut off
+pub fn truncate(s: &str, max_width: usize) -> String {
+    if display_width(s) <= max_width {
+        return s.to_string();
+    }
+
+    let ellipsis = if max_width >= 3 { "..." } else { "" };
+    let budget = max_width - ellipsis.len();
+
+    let mut out = String::new();
+    for c in s.chars() {
+        out.push(c);
+        if display_width(&out) > budget {
+            out.pop();
+            break;
+        }
+    }
+
+    out.push_str(ellipsis);
+    out
+}
+
+/// Wrap text into lines of at most `width` columns, breaking at whitespace
+/// and splitting words that are wider than a whole line
+pub fn wrap(s: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in s.split_whitespace() {
+        // Start a new line if the word doesn't fit after a separating space
+        if !line.is_empty() && display_width(&line) + 1 + display_width(word) > width {
+            lines.push(std::mem::take(&mut line));
+        }
+
+        if !line.is_empty() {
+            line.push(' ');
+        }
+
+        for c in word.chars() {
+            line.push(c);
+            // A character wider than the whole line gets one to itself
+            if display_width(&line) > width {
+                line.pop();
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                line.push(c);
+            }
+        }
+    }
+
+    if !line.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Wrap text to `width` columns but keep at most `max_lines` lines,
+/// truncating the last one if more text remains
+pub fn wrap_lines(s: &str, width: usize, max_lines: usize) -> Vec<String> {
+    let mut lines = wrap(s, width);
+    if lines.len() > max_lines && max_lines > 0 {
+        lines.truncate(max_lines);
+        let last = lines.pop().unwrap_or_default();
+        lines.push(truncate(&format!("{} ...", last), width));
+    }
+    lines
+}
+
+/// The visible part of an input field `width` columns wide, scrolled so the
+/// end of the text stays in view, and the cursor column within it
+pub fn input_view(s: &str, width: usize) -> (String, usize) {
+    // Keep one column free for the cursor itself
+    let available = width.saturating_sub(1);
+    let total = display_width(s);
+    if total <= available {
+        return (s.to_string(), total);
+    }
+
+    let mut start = s.len();
+    for (i, _) in s.char_indices().rev() {
+        if display_width(&s[i..]) > available {
+            break;
+        }
+        start = i;
+    }
+
+    let visible = &s[start..];
+    (visible.to_string(), display_width(visible))
+}
+
+/// Draw values as a row of block characters at most `width` columns wide,
+/// averaging neighbouring values when there are more than columns and
+/// scaling so the largest value fills a whole cell
+pub fn sparkline(values: &[u64], width: usize) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let width = width.min(values.len());
+    if width == 0 {
+        return String::new();
+    }
+
+    let buckets: Vec<u64> = (0..width)
+        .map(|i| {
+            let bucket = &values[i * values.len() / width..(i + 1) * values.len() / width];
+            bucket.iter().sum::<u64>() / bucket.len().max(1) as u64
+        })
+        .collect();
+    let max = buckets.iter().copied().max().unwrap_or(0).max(1);
+
+    buckets
+        .iter()
+        .map(|value| BARS[(value * (BARS.len() as u64 - 1) / max) as usize])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Arbitrary strings rarely hit the interesting cases, so mix in ASCII,
+    // whitespace, control characters, combining marks, CJK and emoji
+    fn text() -> impl Strategy<Value = String> {
+        prop_oneof![
+            any::<String>(),
+            "[a-z \t\n\r\u{7}\u{300}-\u{36f}\u{200b}\u{4e00}-\u{4e20}\u{ff01}-\u{ff20}\u{1f600}-\u{1f620}\u{2764}\u{fe0f}\u{200d}]{0,60}",
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn truncate_fits(s in text(), width in 0usize..40) {
+            prop_assert!(display_width(&truncate(&s, width)) <= width);
+        }
+
+        #[test]
+        fn wrapped_lines_fit(s in text(), width in 0usize..40) {
+            for line in wrap(&s, width) {
+                let line_width = display_width(&line);
+                // A wide character can't be split, so it gets a line of its own
+                let single_wide = width <= 1 && line.chars().count() == 1;
+                prop_assert!(line_width <= width.max(1) || single_wide, "{:?}", line);
+            }
+        }
+
+        #[test]
+        fn wrap_lines_never_panics(s in text(), width in 0usize..40, max_lines in 0usize..5) {
+            wrap_lines(&s, width, max_lines);
+        }
+
+        #[test]
+        fn single_line_has_no_control_chars(s in text()) {
+            prop_assert!(!single_line(&s).chars().any(char::is_control));
+        }
+
+        #[test]
+        fn input_view_leaves_room_for_the_cursor(s in text(), width in 1usize..40) {
+            let (visible, cursor) = input_view(&s, width);
+            prop_assert!(display_width(&visible) < width);
+            prop_assert!(cursor < width);
+        }
+
+        #[test]
+        fn sparkline_fits(values in prop::collection::vec(any::<u32>(), 0..50), width in 0usize..40) {
+            let values: Vec<u64> = values.into_iter().map(u64::from).collect();
+            prop_assert!(display_width(&sparkline(&values, width)) <= width);
+        }
+    }
+}
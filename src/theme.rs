@@ -0,0 +1,444 @@
+use crate::app::AppResult;
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+pub const BUILT_IN: [&str; 4] = ["dark", "light", "high-contrast", "no-color"];
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Colours the UI draws with, by role rather than by hue so a theme can
+/// swap them wholesale
+#[derive(Debug, Clone)]
+pub struct Theme {
+    /// The app title
+    pub title: Color,
+    /// Section headings, field labels, table headers and the active tab
+    pub heading: Color,
+    /// Field values and list entries
+    pub text: Color,
+    /// Secondary details like dates and descriptions
+    pub muted: Color,
+    /// Placeholders and empty score bars
+    pub faint: Color,
+    /// Hints, borders of focused blocks and crate names
+    pub accent: Color,
+    /// Keys, keywords and diff hunk headers
+    pub key: Color,
+    /// Categories, languages, licenses and publishers
+    pub tag: Color,
+    pub good: Color,
+    /// Downloads, stars, medium severity findings and modified files
+    pub warn: Color,
+    pub bad: Color,
+    pub selection_fg: Color,
+    pub selection_bg: Color,
+    pub status_fg: Color,
+    pub status_bg: Color,
+    /// Search filter chips
+    pub chip_fg: Color,
+    pub chip_bg: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+/// How many colours the terminal can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Guess from the environment; terminals announce truecolor through
+    /// `COLORTERM` and 256 colours through `TERM`
+    pub fn detect() -> Self {
+        let colorterm = env::var("COLORTERM").unwrap_or_default();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorDepth::TrueColor;
+        }
+        if env::var("TERM").is_ok_and(|term| term.contains("256color")) {
+            return ColorDepth::Ansi256;
+        }
+        ColorDepth::Ansi16
+    }
+}
+
+// A user theme: an optional base theme plus the roles it changes
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    extends: Option<String>,
+    #[serde(default)]
+    colors: BTreeMap<String, String>,
+}
+
+impl Theme {
+    /// The theme named in the config, adapted to the terminal. `NO_COLOR`
+    /// (https://no-color.org) wins over any configured theme.
+    pub fn load(name: &str) -> AppResult<Self> {
+        if env::var("NO_COLOR").is_ok_and(|v| !v.is_empty())
+            || env::var("TERM").is_ok_and(|term| term == "dumb")
+        {
+            return Ok(Theme::no_color());
+        }
+
+        let theme = match Theme::built_in(name) {
+            Some(theme) => theme,
+            None => Theme::from_file(name)?,
+        };
+        Ok(theme.fit(ColorDepth::detect()))
+    }
+
+    fn built_in(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            "no-color" => Some(Theme::no_color()),
+            _ => None,
+        }
+    }
+
+    fn from_file(name: &str) -> AppResult<Self> {
+        let path = themes_dir()
+            .map(|dir| dir.join(format!("{}.toml", name)))
+            .filter(|path| path.exists())
+            .ok_or_else(|| {
+                format!(
+                    "Unknown theme '{}'. Built-in themes are {}; others are read from {}",
+                    name,
+                    BUILT_IN.join(", "),
+                    themes_dir()
+                        .map(|dir| dir.join(format!("{}.toml", name)).display().to_string())
+                        .unwrap_or_else(|| "the config directory".to_string())
+                )
+            })?;
+
+        let text = fs::read_to_string(&path)
+            .map_err(|e| format!("Could not read theme {}: {}", path.display(), e))?;
+        let file: ThemeFile = toml::from_str(&text)
+            .map_err(|e| format!("Invalid theme {}: {}", path.display(), e))?;
+
+        let base = file.extends.as_deref().unwrap_or("dark");
+        let mut theme = Theme::built_in(base).ok_or_else(|| {
+            format!(
+                "Invalid theme {}: extends must be one of {}, got \"{}\"",
+                path.display(),
+                BUILT_IN.join(", "),
+                base
+            )
+        })?;
+
+        for (role, value) in &file.colors {
+            let color: Color = value.parse().map_err(|_| {
+                format!(
+                    "Invalid theme {}: colors.{} \"{}\" is not a colour name, #rrggbb or 0-255",
+                    path.display(),
+                    role,
+                    value
+                )
+            })?;
+            let slot = theme.role_mut(role).ok_or_else(|| {
+                format!(
+                    "Invalid theme {}: colors.{} is not a known role",
+                    path.display(),
+                    role
+                )
+            })?;
+            *slot = color;
+        }
+
+        Ok(theme)
+    }
+
+    fn role_mut(&mut self, role: &str) -> Option<&mut Color> {
+        Some(match role {
+            "title" => &mut self.title,
+            "heading" => &mut self.heading,
+            "text" => &mut self.text,
+            "muted" => &mut self.muted,
+            "faint" => &mut self.faint,
+            "accent" => &mut self.accent,
+            "key" => &mut self.key,
+            "tag" => &mut self.tag,
+            "good" => &mut self.good,
+            "warn" => &mut self.warn,
+            "bad" => &mut self.bad,
+            "selection-fg" => &mut self.selection_fg,
+            "selection-bg" => &mut self.selection_bg,
+            "status-fg" => &mut self.status_fg,
+            "status-bg" => &mut self.status_bg,
+            "chip-fg" => &mut self.chip_fg,
+            "chip-bg" => &mut self.chip_bg,
+            _ => return None,
+        })
+    }
+
+    pub fn dark() -> Self {
+        Theme {
+            title: Color::Cyan,
+            heading: Color::Yellow,
+            text: Color::White,
+            muted: Color::Gray,
+            faint: Color::DarkGray,
+            accent: Color::Blue,
+            key: Color::Cyan,
+            tag: Color::Magenta,
+            good: Color::Green,
+            warn: Color::Yellow,
+            bad: Color::Red,
+            selection_fg: Color::Reset,
+            selection_bg: Color::DarkGray,
+            status_fg: Color::White,
+            status_bg: Color::DarkGray,
+            chip_fg: Color::Black,
+            chip_bg: Color::Cyan,
+        }
+    }
+
+    // Dark text on a pale selection, with yellows and cyans darkened so
+    // they stay readable on a white background
+    pub fn light() -> Self {
+        Theme {
+            title: Color::Blue,
+            heading: Color::Rgb(135, 0, 135),
+            text: Color::Black,
+            muted: Color::DarkGray,
+            faint: Color::Gray,
+            accent: Color::Blue,
+            key: Color::Rgb(0, 135, 135),
+            tag: Color::Magenta,
+            good: Color::Rgb(0, 135, 0),
+            warn: Color::Rgb(175, 95, 0),
+            bad: Color::Red,
+            selection_fg: Color::Black,
+            selection_bg: Color::Rgb(208, 208, 208),
+            status_fg: Color::White,
+            status_bg: Color::Blue,
+            chip_fg: Color::White,
+            chip_bg: Color::Blue,
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Theme {
+            title: Color::LightCyan,
+            heading: Color::LightYellow,
+            text: Color::White,
+            muted: Color::White,
+            faint: Color::Gray,
+            accent: Color::LightBlue,
+            key: Color::LightCyan,
+            tag: Color::LightMagenta,
+            good: Color::LightGreen,
+            warn: Color::LightYellow,
+            bad: Color::LightRed,
+            selection_fg: Color::Black,
+            selection_bg: Color::White,
+            status_fg: Color::Black,
+            status_bg: Color::White,
+            chip_fg: Color::Black,
+            chip_bg: Color::LightCyan,
+        }
+    }
+
+    /// The terminal's own colours; selections and bars are shown reversed
+    pub fn no_color() -> Self {
+        Theme {
+            title: Color::Reset,
+            heading: Color::Reset,
+            text: Color::Reset,
+            muted: Color::Reset,
+            faint: Color::Reset,
+            accent: Color::Reset,
+            key: Color::Reset,
+            tag: Color::Reset,
+            good: Color::Reset,
+            warn: Color::Reset,
+            bad: Color::Reset,
+            selection_fg: Color::Reset,
+            selection_bg: Color::Reset,
+            status_fg: Color::Reset,
+            status_bg: Color::Reset,
+            chip_fg: Color::Reset,
+            chip_bg: Color::Reset,
+        }
+    }
+
+    /// Replace colours the terminal cannot show with the closest it can
+    pub fn fit(mut self, depth: ColorDepth) -> Self {
+        for role in ROLES {
+            if let Some(color) = self.role_mut(role) {
+                *color = fit_color(*color, depth);
+            }
+        }
+        self
+    }
+
+    pub fn fg(&self, color: Color) -> Style {
+        Style::default().fg(color)
+    }
+
+    /// A bold heading or label
+    pub fn heading(&self) -> Style {
+        self.fg(self.heading).add_modifier(Modifier::BOLD)
+    }
+
+    /// The selected row of a list or table
+    pub fn selected(&self) -> Style {
+        self.inverse(self.selection_fg, self.selection_bg)
+    }
+
+    pub fn status_bar(&self) -> Style {
+        self.inverse(self.status_fg, self.status_bg)
+    }
+
+    pub fn chip(&self) -> Style {
+        self.inverse(self.chip_fg, self.chip_bg)
+    }
+
+    /// A count that needs attention, like unread watchlist changes
+    pub fn badge(&self) -> Style {
+        self.inverse(self.chip_fg, self.bad)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    // Text on a coloured background, or reversed video when the theme
+    // leaves the background to the terminal
+    fn inverse(&self, fg: Color, bg: Color) -> Style {
+        if bg == Color::Reset {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else if fg == Color::Reset {
+            // Keep the colours of the text, e.g. in a selected row
+            Style::default().bg(bg)
+        } else {
+            Style::default().fg(fg).bg(bg)
+        }
+    }
+}
+
+const ROLES: [&str; 17] = [
+    "title",
+    "heading",
+    "text",
+    "muted",
+    "faint",
+    "accent",
+    "key",
+    "tag",
+    "good",
+    "warn",
+    "bad",
+    "selection-fg",
+    "selection-bg",
+    "status-fg",
+    "status-bg",
+    "chip-fg",
+    "chip-bg",
+];
+
+// The 16 ANSI colours as xterm draws them
+const ANSI: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+// Levels of each channel in the 6x6x6 cube of the 256-colour palette
+const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn fit_color(color: Color, depth: ColorDepth) -> Color {
+    match (color, depth) {
+        (Color::Rgb(r, g, b), ColorDepth::Ansi256) => Color::Indexed(to_256(r, g, b)),
+        (Color::Rgb(r, g, b), ColorDepth::Ansi16) => nearest_ansi(r, g, b),
+        (Color::Indexed(i), ColorDepth::Ansi16) => {
+            let (r, g, b) = from_256(i);
+            nearest_ansi(r, g, b)
+        }
+        _ => color,
+    }
+}
+
+// Closest entry of the colour cube or the grey ramp
+fn to_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |c: u8| {
+        (0..CUBE.len())
+            .min_by_key(|&i| (CUBE[i] as i32 - c as i32).abs())
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (16 + 36 * ri + 6 * gi + bi) as u8;
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let grey_step = (average.saturating_sub(8) / 10).min(23) as u8;
+    let grey = 232 + grey_step;
+
+    if distance((r, g, b), from_256(grey)) < distance((r, g, b), from_256(cube)) {
+        grey
+    } else {
+        cube
+    }
+}
+
+fn from_256(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI[index as usize].1,
+        16..=231 => {
+            let i = (index - 16) as usize;
+            (CUBE[i / 36], CUBE[(i / 6) % 6], CUBE[i % 6])
+        }
+        _ => {
+            let level = 8 + (index - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+fn nearest_ansi(r: u8, g: u8, b: u8) -> Color {
+    ANSI.iter()
+        .min_by_key(|(_, rgb)| distance((r, g, b), *rgb))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::Reset)
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+pub fn themes_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("crates").join("themes"))
+}
+
+/// Make a loaded theme the one `get` returns. Only the first call has an effect.
+pub fn init(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+/// The active theme, or the dark theme if none was loaded
+pub fn get() -> &'static Theme {
+    THEME.get_or_init(Theme::default)
+}
@@ -1,5 +1,20 @@
-use crate::api;
-use crate::app::{App, LoadingState, Tab};
+use crate::api::{self, Crate, DocsBuild, DocsStatus, Finding, Severity, Version};
+use crate::app::{App, BrowsePane, ComparedCrate, Listing, LoadingState, SetPrompt, Tab, Tag};
+use crate::browser::Link;
+use crate::columns::{self, Column};
+use crate::config;
+use crate::diff::{DiffLineKind, FileStatus};
+use crate::filter::format_count;
+use crate::fuzzy::{self, FuzzyMatch};
+use crate::keymap::{self, Action, Context};
+use crate::markdown;
+use crate::mouse::Target;
+use crate::msrv::{self, RustSupport, RustVersion};
+use crate::rustdoc::ItemKind;
+use crate::score::Scorecard;
+use crate::text;
+use crate::theme;
+use crate::watch::ChangeKind;
 use chrono::DateTime;
 
 use ratatui::widgets::Cell;
@@ -10,9 +25,15 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Tabs, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Tabs, Wrap},
     Frame,
 };
+use std::collections::HashSet;
+
+// Gap between the Compare table's columns
+const COLUMN_SPACING: u16 = 1;
+// Crate names stay readable however many columns are shown
+const MIN_NAME_WIDTH: u16 = 12;
 
 pub fn draw<B: Backend>(f: &mut Frame<B>, app: &mut App) {
     // Create the main layout
@@ -27,27 +48,33 @@ pub fn draw<B: Backend>(f: &mut Frame<B>, app: &mut App) {
         ])
         .split(f.size());
 
+    app.hits.clear();
     draw_title(f, chunks[0]);
     draw_tabs(f, app, chunks[1]);
 
     // Draw content based on current tab and detail view
-    if app.show_detail {
+    if app.show_diff {
+        draw_version_diff(f, app, chunks[2]);
+    } else if app.show_versions {
+        draw_version_history(f, app, chunks[2]);
+    } else if app.show_readme {
+        draw_readme(f, app, chunks[2]);
+    } else if app.show_api {
+        draw_api_docs(f, app, chunks[2]);
+    } else if app.show_side_by_side {
+        draw_side_by_side(f, app, chunks[2]);
+    } else if app.show_detail {
         match app.current_tab {
-            Tab::Recent | Tab::Search => {
-                if !app.crates.is_empty() && app.selected_index < app.crates.len() {
-                    draw_crate_detail(f, app, chunks[2]);
-                }
+            Tab::Recent | Tab::Search | Tab::Categories
+                if app.showing_crates() && app.selected_row().is_some() =>
+            {
+                draw_crate_detail(f, app, chunks[2]);
             }
-            Tab::Trending => {
-                if !app.repos.is_empty() && app.selected_index < app.repos.len() {
-                    draw_repo_detail(f, app, chunks[2]);
-                }
+            Tab::Trending if app.selected_row().is_some() => {
+                draw_repo_detail(f, app, chunks[2]);
             }
-            Tab::Compare => {
-                if !app.compared_crates.is_empty() && app.selected_index < app.compared_crates.len()
-                {
-                    draw_compared_crate_detail(f, app, chunks[2]);
-                }
+            Tab::Compare if app.selected_row().is_some() => {
+                draw_compared_crate_detail(f, app, chunks[2]);
             }
             _ => {}
         }
@@ -56,7 +83,13 @@ pub fn draw<B: Backend>(f: &mut Frame<B>, app: &mut App) {
             Tab::Search => draw_search_tab(f, app, chunks[2]),
             Tab::Recent => draw_crates_list(f, app, chunks[2], "Recent Crates"),
             Tab::Trending => draw_repos_list(f, app, chunks[2], "Trending Repositories"),
-            Tab::Compare => draw_compare_tab(f, app, chunks[2]),
+            Tab::Compare => {
+                draw_compare_tab(f, app, chunks[2]);
+                draw_comparison_sets(f, app, chunks[2]);
+                draw_column_menu(f, app, chunks[2]);
+            }
+            Tab::Categories => draw_categories_tab(f, app, chunks[2]),
+            Tab::Watchlist => draw_watchlist_tab(f, app, chunks[2]),
             Tab::Help => draw_help(f, app, chunks[2]),
         }
     }
@@ -65,23 +98,40 @@ pub fn draw<B: Backend>(f: &mut Frame<B>, app: &mut App) {
 }
 
 fn draw_title<B: Backend>(f: &mut Frame<B>, area: Rect) {
+    let theme = theme::get();
     let title = Paragraph::new(Text::styled(
         "Crates Explorer",
-        Style::default()
-            .fg(Color::Cyan)
-            .add_modifier(Modifier::BOLD),
+        theme.fg(theme.title).add_modifier(Modifier::BOLD),
     ))
     .alignment(ratatui::layout::Alignment::Center);
 
     f.render_widget(title, area);
 }
 
-fn draw_tabs<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
-    let titles = ["Search", "Recent", "Trending", "Compare", "Help"]
-        .iter()
-        .map(|t| Line::from(vec![Span::styled(*t, Style::default().fg(Color::White))]))
-        .collect();
+fn draw_tabs<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let theme = theme::get();
+    let mut titles: Vec<Line> = [
+        "Search",
+        "Recent",
+        "Trending",
+        "Compare",
+        "Categories",
+        "Watchlist",
+        "Help",
+    ]
+    .iter()
+    .map(|t| Line::from(vec![Span::styled(*t, theme.fg(theme.text))]))
+    .collect();
+
+    // Unread watchlist changes show as a badge on the tab
+    let unread = app.watchlist.unread();
+    if unread > 0 {
+        titles[5]
+            .spans
+            .push(Span::styled(format!(" {} ", unread), theme.badge()));
+    }
 
+    let widths: Vec<u16> = titles.iter().map(|t| t.width() as u16).collect();
     let tabs = Tabs::new(titles)
         .block(Block::default().borders(Borders::ALL).title("Tabs"))
         .select(match app.current_tab {
@@ -89,19 +139,43 @@ fn draw_tabs<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
             Tab::Recent => 1,
             Tab::Trending => 2,
             Tab::Compare => 3,
-            Tab::Help => 4,
+            Tab::Categories => 4,
+            Tab::Watchlist => 5,
+            Tab::Help => 6,
         })
-        .style(Style::default().fg(Color::White))
-        .highlight_style(
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        );
+        .style(theme.fg(theme.text))
+        .highlight_style(theme.heading());
 
     f.render_widget(tabs, area);
+
+    // Titles are padded by a space on each side and split by a one-cell divider
+    let mut x = area.x + 1;
+    for (tab, width) in [
+        Tab::Search,
+        Tab::Recent,
+        Tab::Trending,
+        Tab::Compare,
+        Tab::Categories,
+        Tab::Watchlist,
+        Tab::Help,
+    ]
+    .into_iter()
+    .zip(widths)
+    {
+        x += 1;
+        let title = Rect {
+            x,
+            y: area.y + 1,
+            width,
+            height: 1,
+        };
+        app.hits.add(title.intersection(area), Target::Tab(tab));
+        x += width + 2;
+    }
 }
 
 fn draw_compare_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let theme = theme::get();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -112,13 +186,18 @@ fn draw_compare_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
 
     // Draw input for adding crates
     let input_style = if app.compare_input_mode {
-        Style::default().fg(Color::Blue)
+        theme.fg(theme.accent)
     } else {
         Style::default()
     };
 
+    // Scroll long queries so the end stays visible, measuring in display columns
+    let (visible_query, cursor_column) = text::input_view(
+        &app.compare_search_query,
+        chunks[0].width.saturating_sub(2) as usize,
+    );
     let cursor_position = if app.compare_input_mode {
-        Some(app.compare_search_query.len())
+        Some(cursor_column)
     } else {
         None
     };
@@ -131,9 +210,9 @@ fn draw_compare_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
 
     let search_input = Paragraph::new(
         if app.compare_search_query.is_empty() && !app.compare_input_mode {
-            Text::styled(search_prompt, Style::default().fg(Color::DarkGray))
+            Text::styled(search_prompt, theme.fg(theme.faint))
         } else {
-            Text::raw(&app.compare_search_query)
+            Text::raw(visible_query)
         },
     )
     .style(input_style)
@@ -141,291 +220,739 @@ fn draw_compare_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
         Block::default()
             .borders(Borders::ALL)
             .border_style(if app.compare_input_mode {
-                Style::default().fg(Color::Blue)
+                theme.fg(theme.accent)
             } else {
                 Style::default()
             })
             .title(if app.compare_input_mode {
-                "Adding crate..."
+                "Adding crate...".to_string()
             } else {
-                "Press 'a' to add a crate | 'd' to remove selected"
+                format!(
+                    "Press '{}' to add a crate | '{}' to remove selected | '{}' to sort | '{}' for columns | '{}' to save set | '{}' to open sets",
+                    app.keymap.hint(Context::List, Action::Add),
+                    app.keymap.hint(Context::List, Action::Remove),
+                    app.keymap.hint(Context::List, Action::Sort),
+                    app.keymap.hint(Context::List, Action::Columns),
+                    app.keymap.hint(Context::List, Action::SaveSet),
+                    app.keymap.hint(Context::List, Action::OpenSets)
+                )
             }),
     );
 
     f.render_widget(search_input, chunks[0]);
 
     // Render cursor position when in input mode
-    if app.compare_input_mode && cursor_position.is_some() {
-        f.set_cursor(
-            chunks[0].x + 1 + cursor_position.unwrap() as u16,
-            chunks[0].y + 1,
+    if let Some(cursor_position) = cursor_position {
+        f.set_cursor(chunks[0].x + 1 + cursor_position as u16, chunks[0].y + 1);
+    }
+
+    let mut comparison_title = match app.current_set {
+        Some(ref set) => format!("Comparison: {}", set),
+        None => "Comparison".to_string(),
+    };
+    // Crates of a loaded set that could not be fetched
+    if let (Some(ref e), false) = (&app.set_error, app.sets_mode || app.set_prompt.is_some()) {
+        comparison_title = format!("{} ({})", comparison_title, e);
+    }
+
+    if !app.compare_pending.is_empty() {
+        comparison_title = format!(
+            "{} (loading {})",
+            comparison_title,
+            app.compare_pending.join(", ")
         );
     }
 
     // Draw comparison table if there are crates to compare
     if app.compared_crates.is_empty() {
-        let no_crates = Paragraph::new("No crates added for comparison. Press 'a' to add crates.")
-            .style(Style::default().fg(Color::Gray))
+        let message = if app.compare_pending.is_empty() {
+            format!(
+                "No crates added for comparison. Press '{}' to add crates or '{}' to open a saved set.",
+                app.keymap.hint(Context::List, Action::Add),
+                app.keymap.hint(Context::List, Action::OpenSets)
+            )
+        } else {
+            "Loading...".to_string()
+        };
+        let no_crates = Paragraph::new(message)
+            .style(theme.fg(theme.muted))
             .alignment(ratatui::layout::Alignment::Center)
-            .block(Block::default().borders(Borders::ALL).title("Comparison"));
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(comparison_title),
+            );
 
         f.render_widget(no_crates, chunks[1]);
         return;
     }
 
-    // Create a layout for the comparison table
-    // The first column is for crate names, the rest for metrics
-    let column_constraints = vec![
-        Constraint::Percentage(25), // Name
-        Constraint::Percentage(15), // Downloads
-        Constraint::Percentage(15), // License
-        Constraint::Percentage(15), // Security
-        Constraint::Percentage(15), // Updated
-        Constraint::Percentage(15), // Version
-    ];
+    // Metric columns take what their values need; the names get the rest
+    let table_area = chunks[1];
+    let inner_width = table_area.width.saturating_sub(2);
+    let metrics_width: u16 = app
+        .compare_columns
+        .iter()
+        .map(|c| c.width() + COLUMN_SPACING)
+        .sum();
+    let name_width = inner_width
+        .saturating_sub(metrics_width)
+        .max(MIN_NAME_WIDTH);
+    let mut column_constraints = vec![Constraint::Length(name_width)];
+    column_constraints.extend(
+        app.compare_columns
+            .iter()
+            .map(|c| Constraint::Length(c.width())),
+    );
 
-    let header_cells = [
-        "Crate",
-        "Downloads",
-        "License",
-        "Security",
-        "Updated",
-        "Version",
-    ]
-    .iter()
-    .map(|h| {
-        Cell::from(*h).style(
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )
-    });
+    let mut header_cells = vec![Cell::from("Crate").style(theme.heading())];
+    header_cells.extend(app.compare_columns.iter().map(|column| {
+        let label = if app.compare_sort == Some(*column) {
+            format!("{} ▼", column.label())
+        } else {
+            column.label().to_string()
+        };
+        Cell::from(label).style(theme.heading())
+    }));
     let header = Row::new(header_cells).height(1).bottom_margin(1);
 
+    // Best value of each column, to highlight the crates that have it
+    let best: Vec<Option<i64>> = app
+        .compare_columns
+        .iter()
+        .map(|column| columns::best(*column, &app.compared_crates))
+        .collect();
+
     let mut rows = vec![];
-    for (i, compared) in app.compared_crates.iter().enumerate() {
+    for (i, row) in app.visible_rows().into_iter().enumerate() {
+        let compared = &app.compared_crates[row];
         let crate_data = &compared.details;
+        let name_positions =
+            fuzzy_positions(fuzzy::match_crate(&app.fuzzy_query, crate_data).as_ref(), 0);
 
         // Style for highlighting the selected row
         let style = if i == app.selected_index {
-            Style::default().bg(Color::DarkGray)
+            theme.selected()
         } else {
             Style::default()
         };
 
-        // Security status indicator
-        let security_status = if compared.security.safe {
-            "✓ Safe"
-        } else {
-            "⚠ Warning"
-        };
-
-        // License display
-        let license_display = match &crate_data.license {
-            Some(license) if !license.is_empty() => license,
-            _ => "Unknown",
-        };
-
-        // Format the updated date
-        let updated = if let Ok(dt) = DateTime::parse_from_rfc3339(&crate_data.updated_at) {
-            dt.format("%Y-%m-%d").to_string()
+        // Crates marked for the side-by-side view
+        let mut name = vec![if compared.marked {
+            Span::styled("● ", theme.fg(theme.accent))
         } else {
-            "Unknown".to_string()
-        };
-
-        // Create the row
-        let cells = vec![
-            Cell::from(crate_data.name.clone()),
-            Cell::from(format!("{}", crate_data.downloads)),
-            Cell::from(license_display),
-            Cell::from(security_status).style(if compared.security.safe {
-                Style::default().fg(Color::Green)
-            } else {
-                Style::default().fg(Color::Red)
-            }),
-            Cell::from(updated),
-            Cell::from(crate_data.max_version.clone()),
-        ];
+            Span::raw("  ")
+        }];
+        name.extend(highlight_spans(&crate_data.name, &name_positions, 0, Style::default()).0);
+        let mut cells = vec![Cell::from(Line::from(name))];
+        for (column, best) in app.compare_columns.iter().zip(&best) {
+            let mut cell_style = compare_cell_style(*column, compared);
+            if best.is_some() && column.rank(compared) == *best {
+                cell_style = cell_style.fg(theme.good).add_modifier(Modifier::BOLD);
+            }
+            cells.push(Cell::from(column.cell(compared)).style(cell_style));
+        }
 
         rows.push(Row::new(cells).style(style));
     }
 
+    let row_count = rows.len();
     let table = Table::new(rows)
         .header(header)
-        .block(Block::default().borders(Borders::ALL).title("Comparison"))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(fuzzy_title(app, comparison_title)),
+        )
         .widths(&column_constraints)
-        .column_spacing(1)
+        .column_spacing(COLUMN_SPACING)
         .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
-    f.render_widget(table, chunks[1]);
+    f.render_widget(table, table_area);
+
+    // Clicking a heading sorts by its column
+    let mut x = table_area.x + 1 + name_width + COLUMN_SPACING;
+    for column in &app.compare_columns {
+        let right = table_area.right().saturating_sub(1);
+        if x >= right {
+            break;
+        }
+        app.hits.add(
+            Rect {
+                x,
+                y: table_area.y + 1,
+                width: column.width().min(right - x),
+                height: 1,
+            },
+            Target::Column(*column),
+        );
+        x += column.width() + COLUMN_SPACING;
+    }
+    app.hits
+        .add_rows(below_header(table_area), 0, &vec![1; row_count], |i| {
+            Some(Target::Row(i))
+        });
+}
+
+// Colours that carry meaning of their own, before best values are highlighted
+fn compare_cell_style(column: Column, compared: &ComparedCrate) -> Style {
+    let theme = theme::get();
+    match column {
+        Column::Security if compared.security.safe => theme.fg(theme.good),
+        Column::Security => theme.fg(theme.bad),
+        Column::Score => theme.fg(score_color(compared.score.total())),
+        Column::Advisories if compared.advisories.as_ref().is_some_and(|a| !a.is_empty()) => {
+            theme.fg(theme.bad)
+        }
+        Column::Version | Column::Msrv
+            if compared.rust.as_ref().is_some_and(|r| r.raise.is_some()) =>
+        {
+            theme.fg(theme.warn)
+        }
+        _ => Style::default(),
+    }
+}
+
+// Two to four compared crates in parallel columns. Each section is padded
+// to its tallest column so the rows line up, and values that differ between
+// the crates stand out.
+fn draw_side_by_side<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let theme = theme::get();
+    let crates: Vec<&ComparedCrate> = app
+        .side_by_side
+        .iter()
+        .filter_map(|name| app.compared_crates.iter().find(|c| c.details.name == *name))
+        .collect();
+    if crates.is_empty() {
+        return;
+    }
+
+    let count = crates.len() as u32;
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, count); crates.len()])
+        .split(area);
+    let width = panes[0].width.saturating_sub(2) as usize;
+
+    let sections = [
+        side_summary(&crates),
+        side_description(&crates, width),
+        side_features(&crates, width),
+        side_dependencies(&crates, width),
+        side_security(&crates, width),
+        side_downloads(&crates, width),
+    ];
+    let mut columns: Vec<Vec<Line<'static>>> = vec![Vec::new(); crates.len()];
+    for section in sections {
+        let height = section.iter().map(|lines| lines.len()).max().unwrap_or(0);
+        for (column, mut lines) in columns.iter_mut().zip(section) {
+            lines.resize(height + 1, Line::from(""));
+            column.extend(lines);
+        }
+    }
+    let titles: Vec<String> = crates
+        .iter()
+        .map(|c| format!("{} v{}", c.details.name, c.details.max_version))
+        .collect();
+
+    // Stop once the last line is at the bottom of the panes
+    let height = area.height.saturating_sub(2) as usize;
+    let max_scroll = columns[0].len().saturating_sub(height);
+    app.side_by_side_scroll = app.side_by_side_scroll.min(max_scroll);
+
+    for ((lines, title), pane) in columns.into_iter().zip(titles).zip(panes.iter()) {
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(Span::styled(title, theme.heading())),
+            )
+            .scroll((app.side_by_side_scroll as u16, 0));
+        f.render_widget(paragraph, *pane);
+    }
+}
+
+fn side_heading(title: &str) -> Line<'static> {
+    Line::from(Span::styled(title.to_string(), theme::get().heading()))
+}
+
+// Key metrics, with the best value of each in bold
+fn side_summary(crates: &[&ComparedCrate]) -> Vec<Vec<Line<'static>>> {
+    const METRICS: [Column; 9] = [
+        Column::Score,
+        Column::Downloads,
+        Column::RecentDownloads,
+        Column::ReverseDeps,
+        Column::Stars,
+        Column::LastRelease,
+        Column::Msrv,
+        Column::LicenseFamily,
+        Column::Advisories,
+    ];
+    let theme = theme::get();
+    let label_width = METRICS.iter().map(|c| c.label().len()).max().unwrap_or(0) + 1;
+    let best: Vec<Option<i64>> = METRICS
+        .iter()
+        .map(|column| columns::best(*column, crates.iter().copied()))
+        .collect();
+
+    crates
+        .iter()
+        .map(|compared| {
+            METRICS
+                .iter()
+                .zip(&best)
+                .map(|(column, best)| {
+                    let style = if best.is_some() && column.rank(compared) == *best {
+                        theme.fg(theme.good).add_modifier(Modifier::BOLD)
+                    } else {
+                        compare_cell_style(*column, compared)
+                    };
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{:<width$}", column.label(), width = label_width),
+                            theme.fg(theme.muted),
+                        ),
+                        Span::styled(column.cell(compared), style),
+                    ])
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn side_description(crates: &[&ComparedCrate], width: usize) -> Vec<Vec<Line<'static>>> {
+    let theme = theme::get();
+    crates
+        .iter()
+        .map(|compared| {
+            let description = compared
+                .details
+                .description
+                .as_deref()
+                .map(text::single_line)
+                .unwrap_or_else(|| "No description available.".to_string());
+            let mut lines = vec![side_heading("Description")];
+            lines.extend(
+                text::wrap(&description, width)
+                    .into_iter()
+                    .map(|line| Line::from(Span::styled(line, theme.fg(theme.text)))),
+            );
+            lines
+        })
+        .collect()
+}
+
+// Names that some of the crates have but not all, compared among the crates
+// whose names are known
+fn not_shared<'a>(sets: impl Iterator<Item = Option<Vec<&'a str>>>) -> HashSet<&'a str> {
+    let known: Vec<Vec<&str>> = sets.flatten().collect();
+    let mut names: HashSet<&str> = known.iter().flatten().copied().collect();
+    names.retain(|name| !known.iter().all(|set| set.contains(name)));
+    names
+}
+
+// Feature names, in the accent colour when not every crate has the feature
+fn side_features(crates: &[&ComparedCrate], width: usize) -> Vec<Vec<Line<'static>>> {
+    let theme = theme::get();
+    let different = not_shared(crates.iter().map(|c| {
+        c.features
+            .as_ref()
+            .map(|features| features.keys().map(String::as_str).collect())
+    }));
+
+    crates
+        .iter()
+        .map(|compared| {
+            let mut lines = vec![side_heading("Features")];
+            let Some(ref features) = compared.features else {
+                lines.push(Line::from(Span::styled("Unknown", theme.fg(theme.muted))));
+                return lines;
+            };
+            let names: Vec<&String> = features.keys().filter(|name| *name != "default").collect();
+            let count = match names.len() {
+                1 => "1 feature".to_string(),
+                n => format!("{} features", n),
+            };
+            lines.push(Line::from(Span::styled(count, theme.fg(theme.text))));
+            if let Some(default) = features.get("default") {
+                let default = format!("default: {}", default.join(", "));
+                lines.extend(
+                    text::wrap(&default, width)
+                        .into_iter()
+                        .map(|line| Line::from(Span::styled(line, theme.fg(theme.muted)))),
+                );
+            }
+            for name in names {
+                let style = if different.contains(name.as_str()) {
+                    theme.fg(theme.accent)
+                } else {
+                    theme.fg(theme.text)
+                };
+                lines.push(Line::from(Span::styled(
+                    text::truncate(&format!("• {}", name), width),
+                    style,
+                )));
+            }
+            lines
+        })
+        .collect()
+}
+
+// What a crate pulls in for its users; dependencies not every crate has are
+// in the accent colour
+fn side_dependencies(crates: &[&ComparedCrate], width: usize) -> Vec<Vec<Line<'static>>> {
+    let theme = theme::get();
+    let different = not_shared(crates.iter().map(|c| {
+        c.dependencies.as_ref().map(|dependencies| {
+            dependencies
+                .iter()
+                .filter(|d| d.is_used())
+                .map(|d| d.name.as_str())
+                .collect()
+        })
+    }));
+
+    crates
+        .iter()
+        .map(|compared| {
+            let mut lines = vec![side_heading("Dependencies")];
+            let Some(ref dependencies) = compared.dependencies else {
+                lines.push(Line::from(Span::styled("Unknown", theme.fg(theme.muted))));
+                return lines;
+            };
+            let kind = |kind: &str| dependencies.iter().filter(|d| d.kind == kind).count();
+            let optional = dependencies
+                .iter()
+                .filter(|d| d.is_used() && d.optional)
+                .count();
+            let summary = format!(
+                "{} normal · {} build · {} optional · {} dev",
+                kind("normal"),
+                kind("build"),
+                optional,
+                kind("dev")
+            );
+            lines.extend(
+                text::wrap(&summary, width)
+                    .into_iter()
+                    .map(|line| Line::from(Span::styled(line, theme.fg(theme.text)))),
+            );
+
+            for dependency in dependencies.iter().filter(|d| d.is_used()) {
+                let style = if different.contains(dependency.name.as_str()) {
+                    theme.fg(theme.accent)
+                } else {
+                    theme.fg(theme.text)
+                };
+                let mut entry = format!("• {} {}", dependency.name, dependency.req);
+                if dependency.optional {
+                    entry.push_str(" (optional)");
+                }
+                lines.push(Line::from(Span::styled(
+                    text::truncate(&entry, width),
+                    style,
+                )));
+            }
+            lines
+        })
+        .collect()
+}
+
+// Findings wrapped to the column, most severe first
+fn side_security(crates: &[&ComparedCrate], width: usize) -> Vec<Vec<Line<'static>>> {
+    let theme = theme::get();
+    crates
+        .iter()
+        .map(|compared| {
+            let mut lines = vec![side_heading("Security")];
+            if compared.security.warnings.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "✓ No security issues detected",
+                    theme.fg(theme.good),
+                )));
+                return lines;
+            }
+
+            let mut findings: Vec<&Finding> = compared.security.warnings.iter().collect();
+            findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+            for finding in findings {
+                let style = match finding.severity {
+                    Severity::High => theme.fg(theme.bad).add_modifier(Modifier::BOLD),
+                    Severity::Medium => theme.fg(theme.warn),
+                    Severity::Low => theme.fg(theme.muted),
+                };
+                let message = format!("[{}] {}", finding.severity.label(), finding.message);
+                for (i, line) in text::wrap(&message, width.saturating_sub(2))
+                    .into_iter()
+                    .enumerate()
+                {
+                    let bullet = if i == 0 { "• " } else { "  " };
+                    lines.push(Line::from(Span::styled(
+                        format!("{}{}", bullet, line),
+                        style,
+                    )));
+                }
+            }
+            lines
+        })
+        .collect()
+}
+
+// Daily downloads as a sparkline, each scaled to its own peak, with the
+// largest total in bold
+fn side_downloads(crates: &[&ComparedCrate], width: usize) -> Vec<Vec<Line<'static>>> {
+    let theme = theme::get();
+    let totals: Vec<Option<u64>> = crates
+        .iter()
+        .map(|c| c.download_history.as_ref().map(|days| days.iter().sum()))
+        .collect();
+    let known: Vec<u64> = totals.iter().flatten().copied().collect();
+    let best = known
+        .iter()
+        .max()
+        .filter(|max| known.iter().any(|total| total != *max));
+
+    crates
+        .iter()
+        .zip(&totals)
+        .map(|(compared, total)| {
+            let mut lines = vec![side_heading("Downloads (90 days)")];
+            let (Some(ref days), Some(total)) = (&compared.download_history, total) else {
+                lines.push(Line::from(Span::styled(
+                    "Unavailable",
+                    theme.fg(theme.muted),
+                )));
+                return lines;
+            };
+            lines.push(Line::from(Span::styled(
+                text::sparkline(days, width),
+                theme.fg(theme.accent),
+            )));
+            let style = if best == Some(total) {
+                theme.fg(theme.good).add_modifier(Modifier::BOLD)
+            } else {
+                theme.fg(theme.text)
+            };
+            let peak = days.iter().max().copied().unwrap_or(0);
+            lines.push(Line::from(vec![
+                Span::styled(format!("{} total", format_count(*total)), style),
+                Span::styled(
+                    format!(" · peak {}/day", format_count(peak)),
+                    theme.fg(theme.muted),
+                ),
+            ]));
+            lines
+        })
+        .collect()
+}
+
+// The column menu, drawn over the Compare tab: shown columns in table order
+// with a tick, then the hidden ones
+fn draw_column_menu<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    if !app.columns_mode {
+        return;
+    }
+    let theme = theme::get();
+    let menu = app.column_menu();
+    let width = area.width.saturating_sub(4).min(70);
+    let height = (menu.len() as u16 + 2).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+
+    let items: Vec<ListItem> = menu
+        .iter()
+        .map(|(column, shown)| {
+            let mark = if *shown { "[x] " } else { "[ ] " };
+            let mut spans = vec![
+                Span::styled(mark, theme.fg(theme.key)),
+                Span::styled(format!("{:<16}", column.label()), theme.fg(theme.text)),
+                Span::styled(column.description(), theme.fg(theme.muted)),
+            ];
+            if app.compare_sort == Some(*column) {
+                spans.push(Span::styled(" ▼ sorted", theme.fg(theme.accent)));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let key = |action: Action| app.keymap.hint(Context::Columns, action);
+    let title = format!(
+        "{} to show or hide | {}/{} to move | {} to sort by",
+        key(Action::Expand),
+        key(Action::MoveDown),
+        key(Action::MoveUp),
+        key(Action::Sort)
+    );
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme.fg(theme.accent))
+                .title(title),
+        )
+        .highlight_style(theme.heading())
+        .highlight_symbol("> ");
+
+    let mut state = ratatui::widgets::ListState::default();
+    state.select(Some(app.column_index));
+
+    f.render_widget(Clear, popup);
+    f.render_stateful_widget(list, popup, &mut state);
 }
 
-fn draw_compared_crate_detail<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
-    let compared = &app.compared_crates[app.selected_index];
+fn draw_compared_crate_detail<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let theme = theme::get();
+    let Some(row) = app.selected_row() else {
+        return;
+    };
+    let compared = &app.compared_crates[row];
     let crate_data = &compared.details;
 
     let title = format!("{} v{}", crate_data.name, crate_data.max_version);
 
     let mut content = vec![
-        Line::from(vec![Span::styled(
-            "Description:",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![Span::raw(
+        Line::from(vec![Span::styled("Description:", theme.heading())]),
+        Line::from(vec![Span::raw(text::single_line(
             crate_data
                 .description
-                .clone()
-                .unwrap_or_else(|| "No description available.".to_string()),
-        )]),
+                .as_deref()
+                .unwrap_or("No description available."),
+        ))]),
         Line::from(vec![]),
         // License information
         Line::from(vec![
-            Span::styled(
-                "License: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
+            Span::styled("License: ", theme.heading()),
             Span::styled(
                 crate_data
                     .license
                     .clone()
                     .unwrap_or_else(|| "Unknown".to_string()),
-                Style::default().fg(Color::White),
+                theme.fg(theme.text),
             ),
         ]),
         Line::from(vec![]),
         // Security information
-        Line::from(vec![Span::styled(
-            "Security Check:",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )]),
+        Line::from(vec![Span::styled("Security Check:", theme.heading())]),
     ];
 
-    if compared.security.warnings.is_empty() {
-        content.push(Line::from(vec![Span::styled(
-            "✓ No security issues detected",
-            Style::default().fg(Color::Green),
-        )]));
-    } else {
-        content.push(Line::from(vec![Span::styled(
-            "⚠ Security warnings:",
-            Style::default().fg(Color::Red),
-        )]));
-
-        for warning in &compared.security.warnings {
-            content.push(Line::from(vec![Span::styled(
-                format!("  • {}", warning),
-                Style::default().fg(Color::Red),
-            )]));
-        }
+    content.extend(findings_lines(&compared.security.warnings));
+    if compared.owners_changed {
+        content.push(acknowledge_line(app));
     }
+    content.extend(docs_lines(compared.docs.as_ref()));
+    content.extend(rust_lines(compared.rust.as_ref()));
+    content.extend(scorecard_lines(&compared.score));
+    content.extend(tags_lines(app));
 
     content.extend_from_slice(&[
         Line::from(vec![]),
         Line::from(vec![
-            Span::styled(
-                "Downloads: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                format!("{}", crate_data.downloads),
-                Style::default().fg(Color::Cyan),
-            ),
+            Span::styled("Downloads: ", theme.heading()),
+            Span::styled(format!("{}", crate_data.downloads), theme.fg(theme.key)),
         ]),
         Line::from(vec![]),
         Line::from(vec![
-            Span::styled(
-                "Created: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                format_date(&crate_data.created_at),
-                Style::default().fg(Color::White),
-            ),
+            Span::styled("Created: ", theme.heading()),
+            Span::styled(format_date(&crate_data.created_at), theme.fg(theme.text)),
         ]),
         Line::from(vec![
-            Span::styled(
-                "Updated: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                format_date(&crate_data.updated_at),
-                Style::default().fg(Color::White),
-            ),
+            Span::styled("Updated: ", theme.heading()),
+            Span::styled(format_date(&crate_data.updated_at), theme.fg(theme.text)),
         ]),
         Line::from(vec![]),
     ]);
 
-    if let Some(ref docs) = crate_data.documentation {
-        content.push(Line::from(vec![
-            Span::styled(
-                "Documentation: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                docs,
-                Style::default()
-                    .fg(Color::Blue)
-                    .add_modifier(Modifier::UNDERLINED),
-            ),
-        ]));
-    }
-
-    if let Some(ref repo) = crate_data.repository {
-        content.push(Line::from(vec![
-            Span::styled(
-                "Repository: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                repo,
-                Style::default()
-                    .fg(Color::Blue)
-                    .add_modifier(Modifier::UNDERLINED),
-            ),
-        ]));
-    }
+    content.extend(link_lines(app, crate_data));
 
     // Add navigation help
     content.extend_from_slice(&[
         Line::from(vec![]),
+        Line::from(vec![Span::styled(
+            format!(
+                "Press '{}' for version history and diffs",
+                app.keymap.hint(Context::Detail, Action::Versions)
+            ),
+            theme.fg(theme.accent),
+        )]),
+        Line::from(vec![Span::styled(
+            format!(
+                "Press '{}' to read the README",
+                app.keymap.hint(Context::Detail, Action::Readme)
+            ),
+            theme.fg(theme.accent),
+        )]),
+        Line::from(vec![Span::styled(
+            format!(
+                "Press '{}' to browse the API docs",
+                app.keymap.hint(Context::Detail, Action::Api)
+            ),
+            theme.fg(theme.accent),
+        )]),
+        Line::from(vec![Span::styled(
+            if app.watchlist.contains(&crate_data.name) {
+                format!(
+                    "Watched - press '{}' to stop watching",
+                    app.keymap.hint(Context::Detail, Action::Watch)
+                )
+            } else {
+                format!(
+                    "Press '{}' to watch for releases, yanks and advisories",
+                    app.keymap.hint(Context::Detail, Action::Watch)
+                )
+            },
+            theme.fg(theme.accent),
+        )]),
         Line::from(vec![]),
         Line::from(vec![Span::styled(
-            "Press ESC or q to go back",
-            Style::default().fg(Color::Gray),
+            format!(
+                "Press {} or {} to go back",
+                app.keymap.hint(Context::Detail, Action::Back),
+                app.keymap.hint(Context::Detail, Action::Quit)
+            ),
+            theme.fg(theme.muted),
         )]),
     ]);
 
-    let detail = Paragraph::new(content)
-        .block(Block::default().borders(Borders::ALL).title(title))
-        .wrap(Wrap { trim: true })
-        .scroll((app.detail_scroll as u16, 0));
-
-    f.render_widget(detail, area);
+    draw_detail(f, app, area, title, content);
 }
-fn draw_crates_list<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect, title: &str) {
+fn draw_crates_list<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect, title: &str) {
+    let theme = theme::get();
+    let filtered = app.listing_filtered();
+    let title = if filtered {
+        format!(
+            "{} ({} matching filters, {} fetched of {})",
+            title,
+            app.crates.len(),
+            (app.page * config::get().general.page_size).min(app.total_crates as usize),
+            app.total_crates
+        )
+    } else if app.total_crates > 0 {
+        format!("{} ({} of {})", title, app.crates.len(), app.total_crates)
+    } else {
+        title.to_string()
+    };
+    let title = fuzzy_title(app, title);
+    let title = title.as_str();
+
+    // Leave room for the borders and the highlight symbol
+    let text_width = area.width.saturating_sub(4) as usize;
+
     let items: Vec<ListItem> = app
-        .crates
-        .iter()
+        .visible_rows()
+        .into_iter()
         .enumerate()
-        .map(|(i, c)| {
-            let name = format!("{} v{}", c.name, c.max_version);
-            let desc = c.description.clone().unwrap_or_default();
+        .map(|(i, row)| {
+            let c = &app.crates[row];
+            let matched = fuzzy::match_crate(&app.fuzzy_query, c);
+            let name_positions = fuzzy_positions(matched.as_ref(), 0);
+            let desc_positions = fuzzy_positions(matched.as_ref(), 1);
+            let name = text::truncate(&format!("{} v{}", c.name, c.max_version), text_width);
+            let desc = text::single_line(c.description.as_deref().unwrap_or_default());
             let downloads = format!("{} downloads", c.downloads);
 
             // Parse and format date
@@ -438,42 +965,46 @@ fn draw_crates_list<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect, title:
             let mut content = vec![];
 
             // Name with version
-            content.push(Line::from(vec![Span::styled(
-                name,
-                Style::default()
-                    .fg(Color::Blue)
-                    .add_modifier(if i == app.selected_index {
-                        Modifier::BOLD | Modifier::UNDERLINED
-                    } else {
-                        Modifier::BOLD
-                    }),
-            )]));
+            let name_style = theme
+                .fg(theme.accent)
+                .add_modifier(if i == app.selected_index {
+                    Modifier::BOLD | Modifier::UNDERLINED
+                } else {
+                    Modifier::BOLD
+                });
+            content.push(Line::from(
+                highlight_spans(&name, &name_positions, 0, name_style).0,
+            ));
 
             // Repository URL in green (if available)
             if let Some(repo) = &c.repository {
                 content.push(Line::from(vec![Span::styled(
-                    truncate_str(repo, 60),
-                    Style::default().fg(Color::Green),
+                    text::truncate(repo, text_width),
+                    theme.fg(theme.good),
                 )]));
             }
 
-            // Description
-            if !desc.is_empty() {
-                content.push(Line::from(vec![Span::raw(truncate_str(&desc, 80))]));
+            // Description, wrapped onto at most two lines
+            let mut ordinal = 0;
+            for line in text::wrap_lines(&desc, text_width, 2) {
+                let (spans, next) =
+                    highlight_spans(&line, &desc_positions, ordinal, Style::default());
+                ordinal = next;
+                content.push(Line::from(spans));
             }
 
             // Stats line
             content.push(Line::from(vec![
-                Span::styled(downloads, Style::default().fg(Color::Yellow)),
+                Span::styled(downloads, theme.fg(theme.warn)),
                 Span::raw(" · Updated: "),
-                Span::styled(updated, Style::default().fg(Color::Gray)),
+                Span::styled(updated, theme.fg(theme.muted)),
             ]));
 
             // Add a blank line between results for better readability
             content.push(Line::from(vec![Span::raw("")]));
 
             ListItem::new(content).style(if i == app.selected_index {
-                Style::default().bg(Color::DarkGray)
+                theme.selected()
             } else {
                 Style::default()
             })
@@ -484,16 +1015,12 @@ fn draw_crates_list<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect, title:
     if matches!(app.loading_state, LoadingState::Loading) {
         let loading = ListItem::new(vec![Line::from(vec![Span::styled(
             "Loading...",
-            Style::default().fg(Color::Yellow),
+            theme.fg(theme.warn),
         )])]);
 
         let loading_list = List::new(vec![loading])
             .block(Block::default().borders(Borders::ALL).title(title))
-            .highlight_style(
-                Style::default()
-                    .bg(Color::DarkGray)
-                    .add_modifier(Modifier::BOLD),
-            );
+            .highlight_style(theme.selected().add_modifier(Modifier::BOLD));
 
         f.render_widget(loading_list, area);
         return;
@@ -503,7 +1030,7 @@ fn draw_crates_list<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect, title:
     if let LoadingState::Error(ref msg) = app.loading_state {
         let error = ListItem::new(vec![Line::from(vec![Span::styled(
             format!("Error: {}", msg),
-            Style::default().fg(Color::Red),
+            theme.fg(theme.bad),
         )])]);
 
         let error_list =
@@ -517,7 +1044,7 @@ fn draw_crates_list<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect, title:
     if items.is_empty() {
         let empty = ListItem::new(vec![Line::from(vec![Span::styled(
             "No items found",
-            Style::default().fg(Color::Gray),
+            theme.fg(theme.muted),
         )])]);
 
         let empty_list =
@@ -534,51 +1061,71 @@ fn draw_crates_list<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect, title:
         list_state.select(Some(app.selected_index.min(items_count - 1)));
     }
 
+    let heights: Vec<usize> = items.iter().map(ListItem::height).collect();
     let list = List::new(items)
         .block(Block::default().borders(Borders::ALL).title(title))
-        .highlight_style(
-            Style::default()
-                .bg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD),
-        )
+        .highlight_style(theme.selected().add_modifier(Modifier::BOLD))
         .highlight_symbol("> ");
 
     f.render_stateful_widget(list, area, &mut list_state);
+    app.hits.add_rows(area, list_state.offset(), &heights, |i| {
+        Some(Target::Row(i))
+    });
 }
 
-fn draw_repos_list<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect, title: &str) {
+fn draw_repos_list<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect, title: &str) {
+    let theme = theme::get();
+    let title = fuzzy_title(app, title.to_string());
+    let title = title.as_str();
+
+    // Leave room for the borders and the highlight symbol
+    let text_width = area.width.saturating_sub(4) as usize;
+
     let items: Vec<ListItem> = app
-        .repos
-        .iter()
+        .visible_rows()
+        .into_iter()
         .enumerate()
-        .map(|(i, r)| {
-            let name = &r.full_name;
-            let desc = r.description.clone().unwrap_or_default();
+        .map(|(i, row)| {
+            let r = &app.repos[row];
+            let matched = fuzzy::match_repo(&app.fuzzy_query, r);
+            let name = text::truncate(&r.full_name, text_width);
+            let desc = text::single_line(r.description.as_deref().unwrap_or_default());
             let stars = format!("★ {}", r.stargazers_count);
             let forks = format!("🍴 {}", r.forks_count);
             let language = r.language.clone().unwrap_or_else(|| "Unknown".to_string());
 
             let content = vec![
-                Line::from(vec![Span::styled(
-                    name,
-                    Style::default()
-                        .fg(Color::Green)
-                        .add_modifier(Modifier::BOLD),
-                )]),
-                Line::from(vec![Span::raw(truncate_str(&desc, 60))]),
+                Line::from(
+                    highlight_spans(
+                        &name,
+                        &fuzzy_positions(matched.as_ref(), 0),
+                        0,
+                        theme.fg(theme.good).add_modifier(Modifier::BOLD),
+                    )
+                    .0,
+                ),
+                Line::from(
+                    highlight_spans(
+                        &text::truncate(&desc, text_width),
+                        &fuzzy_positions(matched.as_ref(), 1),
+                        0,
+                        Style::default(),
+                    )
+                    .0,
+                ),
                 Line::from(vec![
-                    Span::styled(stars, Style::default().fg(Color::Yellow)),
+                    Span::styled(stars, theme.fg(theme.warn)),
                     Span::raw(" | "),
-                    Span::styled(forks, Style::default().fg(Color::Blue)),
+                    Span::styled(forks, theme.fg(theme.accent)),
                     Span::raw(" | Language: "),
-                    Span::styled(language, Style::default().fg(Color::Magenta)),
+                    Span::styled(language, theme.fg(theme.tag)),
                 ]),
             ];
 
-            ListItem::new(content).style(Style::default().fg(if i == app.selected_index {
-                Color::Yellow
+            ListItem::new(content).style(theme.fg(if i == app.selected_index {
+                theme.heading
             } else {
-                Color::White
+                theme.text
             }))
         })
         .collect();
@@ -587,7 +1134,7 @@ fn draw_repos_list<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect, title: &
     if matches!(app.loading_state, LoadingState::Loading) {
         let loading = ListItem::new(vec![Line::from(vec![Span::styled(
             "Loading...",
-            Style::default().fg(Color::Yellow),
+            theme.fg(theme.warn),
         )])]);
 
         let loading_list =
@@ -600,7 +1147,7 @@ fn draw_repos_list<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect, title: &
     if let LoadingState::Error(ref msg) = app.loading_state {
         let error = ListItem::new(vec![Line::from(vec![Span::styled(
             format!("Error: {}", msg),
-            Style::default().fg(Color::Red),
+            theme.fg(theme.bad),
         )])]);
 
         let error_list =
@@ -613,7 +1160,7 @@ fn draw_repos_list<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect, title: &
     if items.is_empty() {
         let empty = ListItem::new(vec![Line::from(vec![Span::styled(
             "No items found",
-            Style::default().fg(Color::Gray),
+            theme.fg(theme.muted),
         )])]);
 
         let empty_list =
@@ -630,23 +1177,25 @@ fn draw_repos_list<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect, title: &
         list_state.select(Some(app.selected_index.min(items_count - 1)));
     }
 
+    let heights: Vec<usize> = items.iter().map(ListItem::height).collect();
     let list = List::new(items)
         .block(Block::default().borders(Borders::ALL).title(title))
-        .highlight_style(
-            Style::default()
-                .bg(Color::DarkGray)
-                .add_modifier(Modifier::BOLD),
-        )
+        .highlight_style(theme.selected().add_modifier(Modifier::BOLD))
         .highlight_symbol("> ");
 
     f.render_stateful_widget(list, area, &mut list_state);
+    app.hits.add_rows(area, list_state.offset(), &heights, |i| {
+        Some(Target::Row(i))
+    });
 }
 
 fn draw_search_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let theme = theme::get();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3), // Search input
+            Constraint::Length(1), // Sort order and filter chips
             Constraint::Length(1), // Small padding
             Constraint::Min(0),    // Search results
         ])
@@ -654,13 +1203,18 @@ fn draw_search_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
 
     // Create a Google-like search input
     let input_style = if app.input_mode {
-        Style::default().fg(Color::Blue)
+        theme.fg(theme.accent)
     } else {
         Style::default()
     };
 
+    // Scroll long queries so the end stays visible, measuring in display columns
+    let (visible_query, cursor_column) = text::input_view(
+        &app.search_query,
+        chunks[0].width.saturating_sub(2) as usize,
+    );
     let cursor_position = if app.input_mode {
-        Some(app.search_query.len())
+        Some(cursor_column)
     } else {
         None
     };
@@ -672,52 +1226,58 @@ fn draw_search_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
     };
 
     let search_input = Paragraph::new(if app.search_query.is_empty() && !app.input_mode {
-        Text::styled(search_prompt, Style::default().fg(Color::DarkGray))
+        Text::styled(search_prompt, theme.fg(theme.faint))
     } else {
-        Text::raw(&app.search_query)
+        Text::raw(visible_query)
     })
     .style(input_style)
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(if app.input_mode {
-                Style::default().fg(Color::Blue)
+            .border_style(if app.query_error.is_some() {
+                theme.fg(theme.bad)
+            } else if app.input_mode {
+                theme.fg(theme.accent)
             } else {
                 Style::default()
             })
-            .title(if app.input_mode {
-                "🔍 Type to search"
-            } else {
-                "🔍 Press / to search"
+            .title(match app.query_error {
+                Some(ref e) => Span::styled(
+                    format!("⚠ {}", e),
+                    theme.fg(theme.bad).add_modifier(Modifier::BOLD),
+                ),
+                None if app.input_mode => {
+                    Span::raw("🔍 Type to search (license: downloads: updated: category: keyword:)")
+                }
+                None => Span::raw("🔍 Press / to search"),
             }),
     );
 
     f.render_widget(search_input, chunks[0]);
 
     // Render cursor position when in input mode
-    if app.input_mode && cursor_position.is_some() {
-        f.set_cursor(
-            chunks[0].x + 1 + cursor_position.unwrap() as u16,
-            chunks[0].y + 1,
-        );
+    if let Some(cursor_position) = cursor_position {
+        f.set_cursor(chunks[0].x + 1 + cursor_position as u16, chunks[0].y + 1);
     }
 
     // Add stats about results if we have searched - use String instead of &str
-    let stats_text = if !app.crates.is_empty() && !app.search_query.is_empty() {
+    let stats_text = if app.live.pending {
+        "Searching...".to_string()
+    } else if !app.crates.is_empty() && !app.search_query.is_empty() {
         format!(
             "Found {} results for \"{}\"",
-            app.crates.len(),
-            app.search_query
+            app.total_crates, app.search_query
         )
     } else {
         String::new()
     };
 
     let stats = Paragraph::new(stats_text)
-        .style(Style::default().fg(Color::Gray))
+        .style(theme.fg(theme.muted))
         .alignment(ratatui::layout::Alignment::Left);
 
-    f.render_widget(stats, chunks[1]);
+    draw_filter_bar(f, app, chunks[1]);
+    f.render_widget(stats, chunks[2]);
 
     // Draw search results with a simple title
     let title = if app.search_query.is_empty() {
@@ -736,479 +1296,2179 @@ fn draw_search_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
         app.search_crates_silently("rust");
     }
 
-    draw_crates_list(f, app, chunks[2], title);
+    draw_crates_list(f, app, chunks[3], title);
+
+    if app.input_mode && !app.suggestions.is_empty() {
+        draw_suggestions(f, app, chunks[0], area);
+    }
+}
+
+// Autocomplete dropdown drawn over the results, just below the search input
+fn draw_suggestions<B: Backend>(f: &mut Frame<B>, app: &App, input: Rect, area: Rect) {
+    let theme = theme::get();
+    let top = input.y + input.height;
+    let bottom = area.y + area.height;
+    let height = (app.suggestions.len() as u16 + 2).min(bottom.saturating_sub(top));
+    if height < 3 {
+        return;
+    }
+    let dropdown = Rect {
+        x: input.x + 1,
+        y: top,
+        width: input.width.saturating_sub(2).min(40),
+        height,
+    };
+
+    let items: Vec<ListItem> = app
+        .suggestions
+        .iter()
+        .map(|name| {
+            ListItem::new(text::truncate(
+                name,
+                dropdown.width.saturating_sub(4) as usize,
+            ))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme.fg(theme.accent))
+                .title("Tab to complete"),
+        )
+        .highlight_style(theme.heading())
+        .highlight_symbol("> ");
+
+    let mut state = ratatui::widgets::ListState::default();
+    state.select(app.suggestion_index);
+
+    f.render_widget(Clear, dropdown);
+    f.render_stateful_widget(list, dropdown, &mut state);
+}
+
+// Saved comparison sets and the set name prompt, drawn over the Compare tab
+fn draw_comparison_sets<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let theme = theme::get();
+    if app.sets_mode {
+        let width = area.width.saturating_sub(4).min(50);
+        let height = (app.comparison_sets.sets.len() as u16 + 2)
+            .max(3)
+            .min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        let items: Vec<ListItem> = if app.comparison_sets.sets.is_empty() {
+            vec![ListItem::new(Span::styled(
+                format!(
+                    "No saved sets yet. Press '{}' to save one.",
+                    app.keymap.hint(Context::List, Action::SaveSet)
+                ),
+                theme.fg(theme.muted),
+            ))]
+        } else {
+            app.comparison_sets
+                .sets
+                .iter()
+                .map(|(name, crates)| {
+                    ListItem::new(Line::from(vec![
+                        Span::raw(name.clone()),
+                        Span::styled(format!(" ({} crates)", crates.len()), theme.fg(theme.muted)),
+                    ]))
+                })
+                .collect()
+        };
+
+        let title = match app.set_error {
+            Some(ref e) => e.clone(),
+            None => {
+                let key = |action: Action| app.keymap.hint(Context::Sets, action);
+                format!(
+                    "{} to load | {} to rename | {} to delete",
+                    key(Action::Open),
+                    key(Action::Rename),
+                    key(Action::Remove)
+                )
+            }
+        };
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(theme.fg(theme.accent))
+                    .title(title),
+            )
+            .highlight_style(theme.heading())
+            .highlight_symbol("> ");
+
+        let mut state = ratatui::widgets::ListState::default();
+        if !app.comparison_sets.sets.is_empty() {
+            state.select(Some(app.set_index));
+        }
+
+        f.render_widget(Clear, popup);
+        f.render_stateful_widget(list, popup, &mut state);
+    }
+
+    if let Some(ref prompt) = app.set_prompt {
+        let width = area.width.saturating_sub(4).min(50);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + area.height.saturating_sub(3) / 2,
+            width,
+            height: 3.min(area.height),
+        };
+
+        let title = match (&app.set_error, prompt) {
+            (Some(e), _) => e.clone(),
+            (None, SetPrompt::Save) => "Save comparison as".to_string(),
+            (None, SetPrompt::Rename(old)) => format!("Rename '{}' to", old),
+        };
+        let (visible, cursor) =
+            text::input_view(&app.set_name_input, width.saturating_sub(2) as usize);
+        let input = Paragraph::new(visible).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme.fg(if app.set_error.is_some() {
+                    theme.bad
+                } else {
+                    theme.accent
+                }))
+                .title(title),
+        );
+
+        f.render_widget(Clear, popup);
+        f.render_widget(input, popup);
+        f.set_cursor(popup.x + 1 + cursor as u16, popup.y + 1);
+    }
+}
+
+fn draw_categories_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let theme = theme::get();
+    if app.browse_listing {
+        let title = match app.listing {
+            Listing::Category(ref slug) => format!(
+                "Category: {}",
+                app.categories
+                    .iter()
+                    .find(|n| n.category.slug == *slug)
+                    .map(|n| n.category.category.as_str())
+                    .unwrap_or(slug)
+            ),
+            Listing::Keyword(ref keyword) => format!("Keyword: #{}", keyword),
+            _ => "Crates".to_string(),
+        };
+        draw_crates_list(f, app, area, &title);
+        return;
+    }
+
+    if let LoadingState::Error(ref msg) = app.loading_state {
+        let error = Paragraph::new(format!("Error: {}", msg))
+            .style(theme.fg(theme.bad))
+            .block(Block::default().borders(Borders::ALL).title("Categories"));
+        f.render_widget(error, area);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(area);
+
+    let focused = |pane: BrowsePane| {
+        if app.browse_pane == pane {
+            theme.fg(theme.heading)
+        } else {
+            Style::default()
+        }
+    };
+    let highlight = theme.heading();
+
+    let width = chunks[0].width.saturating_sub(4) as usize;
+    let category_items: Vec<ListItem> = app
+        .categories
+        .iter()
+        .map(|node| {
+            // Subcategory names repeat their parents; the tree shows that already
+            let name = node
+                .category
+                .category
+                .rsplit("::")
+                .next()
+                .unwrap_or(&node.category.category);
+            let marker = if node.expanded { "▾" } else { "▸" };
+            let label = format!(
+                "{}{} {} ({})",
+                "  ".repeat(node.depth),
+                marker,
+                name,
+                node.category.crates_cnt
+            );
+            let description = text::single_line(&node.category.description);
+
+            ListItem::new(vec![
+                Line::from(Span::styled(
+                    text::truncate(&label, width),
+                    theme.fg(theme.good),
+                )),
+                Line::from(Span::styled(
+                    text::truncate(
+                        &format!("{}  {}", "  ".repeat(node.depth), description),
+                        width,
+                    ),
+                    theme.fg(theme.muted),
+                )),
+            ])
+        })
+        .collect();
+
+    let loading = matches!(app.loading_state, LoadingState::Loading) && app.categories.is_empty();
+    let category_title = if loading {
+        "Categories (loading...)"
+    } else {
+        "Categories"
+    };
+    let categories = List::new(category_items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(focused(BrowsePane::Categories))
+                .title(category_title),
+        )
+        .highlight_style(highlight)
+        .highlight_symbol("> ");
+    let mut category_state = ratatui::widgets::ListState::default();
+    if app.browse_pane == BrowsePane::Categories && !app.categories.is_empty() {
+        category_state.select(Some(app.category_index));
+    }
+    f.render_stateful_widget(categories, chunks[0], &mut category_state);
+    app.hits.add_rows(
+        chunks[0],
+        category_state.offset(),
+        &vec![1; app.categories.len()],
+        |i| Some(Target::Category(i)),
+    );
+
+    let keyword_items: Vec<ListItem> = app
+        .keywords
+        .iter()
+        .map(|k| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("#{}", k.keyword), theme.fg(theme.key)),
+                Span::styled(format!(" ({})", k.crates_cnt), theme.fg(theme.muted)),
+            ]))
+        })
+        .collect();
+
+    let keywords = List::new(keyword_items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(focused(BrowsePane::Keywords))
+                .title("Popular Keywords"),
+        )
+        .highlight_style(highlight)
+        .highlight_symbol("> ");
+    let mut keyword_state = ratatui::widgets::ListState::default();
+    if app.browse_pane == BrowsePane::Keywords && !app.keywords.is_empty() {
+        keyword_state.select(Some(app.keyword_index));
+    }
+    f.render_stateful_widget(keywords, chunks[1], &mut keyword_state);
+    app.hits.add_rows(
+        chunks[1],
+        keyword_state.offset(),
+        &vec![1; app.keywords.len()],
+        |i| Some(Target::Keyword(i)),
+    );
+}
+
+fn draw_watchlist_tab<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let theme = theme::get();
+    let checking = if app.watch_check.is_some() {
+        " (checking...)"
+    } else {
+        ""
+    };
+
+    if app.watchlist.crates.is_empty() {
+        let empty = Paragraph::new(format!(
+            "No crates watched yet. Press '{}' on a crate in any list or detail view to watch it.",
+            app.keymap.hint(Context::List, Action::Watch)
+        ))
+        .style(theme.fg(theme.muted))
+        .alignment(ratatui::layout::Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).title("Watchlist"));
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .split(area);
+
+    let header = Row::new(
+        ["Crate", "Version", "License", "Owners", "New", "Checked"]
+            .iter()
+            .map(|h| Cell::from(*h).style(theme.heading())),
+    )
+    .bottom_margin(1);
+
+    let rows: Vec<Row> = app
+        .watchlist
+        .crates
+        .iter()
+        .enumerate()
+        .map(|(i, (name, watched))| {
+            let unread = app.watchlist.unread_for(name);
+            let (version, license, owners) = match watched.snapshot {
+                Some(ref s) => (
+                    s.latest_version.clone(),
+                    s.license.clone().unwrap_or_else(|| "Unknown".to_string()),
+                    s.owners.len().to_string(),
+                ),
+                None => ("-".to_string(), "-".to_string(), "-".to_string()),
+            };
+            let checked = match (&watched.last_error, &watched.last_checked) {
+                (Some(e), _) => Cell::from(format!("error: {}", e)).style(theme.fg(theme.bad)),
+                (None, Some(at)) => Cell::from(format_date(at)),
+                (None, None) => Cell::from("never"),
+            };
+
+            Row::new(vec![
+                Cell::from(name.clone()),
+                Cell::from(version),
+                Cell::from(license),
+                Cell::from(owners),
+                Cell::from(if unread > 0 {
+                    unread.to_string()
+                } else {
+                    String::new()
+                })
+                .style(theme.fg(theme.bad).add_modifier(Modifier::BOLD)),
+                checked,
+            ])
+            .style(if i == app.selected_index {
+                theme.selected()
+            } else {
+                Style::default()
+            })
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Percentage(25),
+        Constraint::Percentage(12),
+        Constraint::Percentage(20),
+        Constraint::Percentage(8),
+        Constraint::Percentage(7),
+        Constraint::Percentage(28),
+    ];
+    let row_count = rows.len();
+    let table = Table::new(rows)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Watched Crates{}", checking)),
+        )
+        .widths(&widths)
+        .column_spacing(1);
+    f.render_widget(table, chunks[0]);
+    app.hits
+        .add_rows(below_header(chunks[0]), 0, &vec![1; row_count], |i| {
+            Some(Target::Row(i))
+        });
+
+    let events: Vec<ListItem> = app
+        .watchlist
+        .events
+        .iter()
+        .map(|event| {
+            let marker = if event.read { "  " } else { "● " };
+            let style = if event.read {
+                theme.fg(theme.muted)
+            } else {
+                Style::default().add_modifier(Modifier::BOLD)
+            };
+            let kind_color = match event.kind {
+                ChangeKind::Advisory | ChangeKind::Ownership | ChangeKind::Yanked => theme.bad,
+                ChangeKind::License => theme.tag,
+                ChangeKind::NewVersion => theme.good,
+            };
+
+            ListItem::new(Line::from(vec![
+                Span::styled(marker, theme.fg(theme.bad)),
+                Span::styled(format!("{} ", format_date(&event.at)), style),
+                Span::styled(format!("{} ", event.name), style.fg(theme.accent)),
+                Span::styled(format!("[{}] ", event.kind.label()), theme.fg(kind_color)),
+                Span::styled(event.message.clone(), style),
+            ]))
+        })
+        .collect();
+
+    let events = if events.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "No changes since the crates were added",
+            theme.fg(theme.muted),
+        ))]
+    } else {
+        events
+    };
+
+    let feed = List::new(events).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Changes ({} unread)", app.watchlist.unread())),
+    );
+    f.render_widget(feed, chunks[1]);
+}
+
+fn draw_filter_bar<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let theme = theme::get();
+    let key_style = theme.fg(theme.heading);
+
+    let mut spans = vec![
+        Span::styled("Sort: ", theme.fg(theme.muted)),
+        Span::styled(
+            app.search_sort.label(),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("  "),
+    ];
+
+    if app.filter_mode {
+        // Show what each key does while editing filters
+        for (key, label) in [
+            ("s", "sort"),
+            ("l", "license"),
+            ("m", "downloads"),
+            ("u", "updated"),
+            ("r", "repository"),
+            ("z", "hide 0.0.x"),
+            ("c", "clear"),
+        ] {
+            spans.push(Span::styled(key, key_style));
+            spans.push(Span::raw(format!(" {}  ", label)));
+        }
+    } else {
+        let chips = app.search_filters.chips();
+        if chips.is_empty() {
+            spans.push(Span::styled(
+                "No filters (F to filter)",
+                theme.fg(theme.faint),
+            ));
+        } else {
+            for chip in chips {
+                spans.push(Span::styled(format!(" {} ", chip), theme.chip()));
+                spans.push(Span::raw(" "));
+            }
+        }
+    }
+
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
 }
 
-fn draw_crate_detail<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
-    let crate_data = &app.crates[app.selected_index];
+fn draw_crate_detail<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let theme = theme::get();
+    let Some(row) = app.selected_row() else {
+        return;
+    };
+    let crate_data = &app.crates[row];
 
     let title = format!("{} v{}", crate_data.name, crate_data.max_version);
 
-    // Run security check
-    let security_warnings = api::security_check(crate_data);
-    let is_safe = security_warnings.is_empty();
+    // Run security check, including ownership, docs.rs and MSRV findings loaded when the view opened
+    let mut security_warnings = api::security_check(crate_data, &app.policy);
+    if let Some(ref ownership) = app.ownership {
+        security_warnings.extend(ownership.findings.iter().cloned());
+    }
+    security_warnings.extend(api::docs_check(
+        &crate_data.name,
+        app.docs_status.as_ref(),
+        &app.policy,
+    ));
+    security_warnings.extend(msrv::check(
+        &crate_data.name,
+        app.rust_support.as_ref(),
+        &app.policy,
+    ));
 
     let mut content = vec![
-        Line::from(vec![Span::styled(
-            "Description:",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![Span::raw(
+        Line::from(vec![Span::styled("Description:", theme.heading())]),
+        Line::from(vec![Span::raw(text::single_line(
             crate_data
                 .description
-                .clone()
-                .unwrap_or_else(|| "No description available.".to_string()),
-        )]),
+                .as_deref()
+                .unwrap_or("No description available."),
+        ))]),
         Line::from(vec![]),
         // Add license information
         Line::from(vec![
-            Span::styled(
-                "License: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
+            Span::styled("License: ", theme.heading()),
             Span::styled(
                 crate_data
                     .license
                     .clone()
                     .unwrap_or_else(|| "Unknown".to_string()),
                 if crate_data.license.is_some() {
-                    Style::default().fg(Color::Green)
+                    theme.fg(theme.good)
                 } else {
-                    Style::default().fg(Color::Red)
+                    theme.fg(theme.bad)
                 },
             ),
         ]),
         Line::from(vec![]),
         // Add security information
-        Line::from(vec![Span::styled(
-            "Security Check:",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )]),
+        Line::from(vec![Span::styled("Security Check:", theme.heading())]),
     ];
 
-    if is_safe {
-        content.push(Line::from(vec![Span::styled(
-            "✓ No security issues detected",
-            Style::default().fg(Color::Green),
-        )]));
-    } else {
-        content.push(Line::from(vec![Span::styled(
-            "⚠ Security warnings:",
-            Style::default().fg(Color::Red),
-        )]));
-
-        for warning in &security_warnings {
-            content.push(Line::from(vec![Span::styled(
-                format!("  • {}", warning),
-                Style::default().fg(Color::Red),
-            )]));
-        }
+    content.extend(findings_lines(&security_warnings));
+    content.extend(docs_lines(app.docs_status.as_ref()));
+    content.extend(rust_lines(app.rust_support.as_ref()));
+    content.extend(ownership_lines(app));
+    if let Some(ref scorecard) = app.scorecard {
+        content.extend(scorecard_lines(scorecard));
     }
+    content.extend(tags_lines(app));
 
     content.extend_from_slice(&[
         Line::from(vec![]),
         Line::from(vec![
-            Span::styled(
-                "Downloads: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                format!("{}", crate_data.downloads),
-                Style::default().fg(Color::Cyan),
-            ),
+            Span::styled("Downloads: ", theme.heading()),
+            Span::styled(format!("{}", crate_data.downloads), theme.fg(theme.key)),
         ]),
         Line::from(vec![]),
         Line::from(vec![
-            Span::styled(
-                "Created: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                format_date(&crate_data.created_at),
-                Style::default().fg(Color::White),
-            ),
+            Span::styled("Created: ", theme.heading()),
+            Span::styled(format_date(&crate_data.created_at), theme.fg(theme.text)),
         ]),
         Line::from(vec![
-            Span::styled(
-                "Updated: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                format_date(&crate_data.updated_at),
-                Style::default().fg(Color::White),
-            ),
+            Span::styled("Updated: ", theme.heading()),
+            Span::styled(format_date(&crate_data.updated_at), theme.fg(theme.text)),
         ]),
         Line::from(vec![]),
     ]);
 
-    if let Some(ref docs) = crate_data.documentation {
-        content.push(Line::from(vec![
-            Span::styled(
-                "Documentation: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                docs,
-                Style::default()
-                    .fg(Color::Blue)
-                    .add_modifier(Modifier::UNDERLINED),
-            ),
-        ]));
-    }
-
-    if let Some(ref repo) = crate_data.repository {
-        content.push(Line::from(vec![
-            Span::styled(
-                "Repository: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                repo,
-                Style::default()
-                    .fg(Color::Blue)
-                    .add_modifier(Modifier::UNDERLINED),
-            ),
-        ]));
-    }
+    content.extend(link_lines(app, crate_data));
 
     // Add option to add to comparison
     content.extend_from_slice(&[
         Line::from(vec![]),
         Line::from(vec![Span::styled(
-            "Press 'a' to add to comparison",
-            Style::default().fg(Color::Blue),
+            format!(
+                "Press '{}' to add to comparison",
+                app.keymap.hint(Context::List, Action::Add)
+            ),
+            theme.fg(theme.accent),
+        )]),
+        Line::from(vec![Span::styled(
+            format!(
+                "Press '{}' for version history and diffs",
+                app.keymap.hint(Context::Detail, Action::Versions)
+            ),
+            theme.fg(theme.accent),
+        )]),
+        Line::from(vec![Span::styled(
+            format!(
+                "Press '{}' to read the README",
+                app.keymap.hint(Context::Detail, Action::Readme)
+            ),
+            theme.fg(theme.accent),
+        )]),
+        Line::from(vec![Span::styled(
+            format!(
+                "Press '{}' to browse the API docs",
+                app.keymap.hint(Context::Detail, Action::Api)
+            ),
+            theme.fg(theme.accent),
+        )]),
+        Line::from(vec![Span::styled(
+            if app.watchlist.contains(&crate_data.name) {
+                format!(
+                    "Watched - press '{}' to stop watching",
+                    app.keymap.hint(Context::Detail, Action::Watch)
+                )
+            } else {
+                format!(
+                    "Press '{}' to watch for releases, yanks and advisories",
+                    app.keymap.hint(Context::Detail, Action::Watch)
+                )
+            },
+            theme.fg(theme.accent),
         )]),
         Line::from(vec![]),
         Line::from(vec![Span::styled(
-            "Press ESC or q to go back",
-            Style::default().fg(Color::Gray),
+            format!(
+                "Press {} or {} to go back",
+                app.keymap.hint(Context::Detail, Action::Back),
+                app.keymap.hint(Context::Detail, Action::Quit)
+            ),
+            theme.fg(theme.muted),
         )]),
     ]);
 
-    let detail = Paragraph::new(content)
+    draw_detail(f, app, area, title, content);
+}
+
+// A scrolling detail view. The content is wrapped here rather than by the
+// paragraph so the URLs in it can be made clickable where they end up,
+// including the rows a long one wraps onto.
+fn draw_detail<B: Backend>(
+    f: &mut Frame<B>,
+    app: &mut App,
+    area: Rect,
+    title: String,
+    content: Vec<Line>,
+) {
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let (rows, links) = wrap_with_links(content, inner.width);
+
+    let detail = Paragraph::new(rows)
         .block(Block::default().borders(Borders::ALL).title(title))
-        .wrap(Wrap { trim: true })
         .scroll((app.detail_scroll as u16, 0));
-
     f.render_widget(detail, area);
+
+    for link in links {
+        let Some(y) = link
+            .row
+            .checked_sub(app.detail_scroll)
+            .filter(|y| *y < inner.height as usize)
+        else {
+            continue;
+        };
+        let rect = Rect {
+            x: inner.x + link.column,
+            y: inner.y + y as u16,
+            width: link.width,
+            height: 1,
+        };
+        app.hits.add(rect, Target::Link(link.url));
+    }
+}
+
+/// The part of a URL on one wrapped row
+struct LinkSegment {
+    row: usize,
+    column: u16,
+    width: u16,
+    url: String,
+}
+
+// One character of wrapped content and the URL it belongs to, if any
+struct Glyph {
+    c: char,
+    style: Style,
+    link: Option<usize>,
+    width: usize,
+}
+
+// Word wrap lines to `width` columns, dropping whitespace at the start of
+// rows as `Wrap { trim: true }` does, and report where every piece of the
+// URLs found in the spans ended up
+fn wrap_with_links(lines: Vec<Line>, width: u16) -> (Vec<Line<'static>>, Vec<LinkSegment>) {
+    let width = width.max(1) as usize;
+    let mut urls = Vec::new();
+    let mut rows: Vec<Vec<Glyph>> = Vec::new();
+
+    for line in lines {
+        let mut glyphs = Vec::new();
+        for span in &line.spans {
+            let content = span.content.as_ref();
+            let mut links = Vec::new();
+            for scheme in ["https://", "http://"] {
+                let mut from = 0;
+                while let Some(start) = content[from..].find(scheme).map(|i| from + i) {
+                    let end = content[start..]
+                        .find(char::is_whitespace)
+                        .map_or(content.len(), |i| start + i);
+                    links.push((start..end, urls.len()));
+                    urls.push(content[start..end].to_string());
+                    from = end;
+                }
+            }
+            for (i, c) in content.char_indices() {
+                glyphs.push(Glyph {
+                    c,
+                    style: span.style,
+                    link: links
+                        .iter()
+                        .find(|(range, _)| range.contains(&i))
+                        .map(|(_, link)| *link),
+                    width: text::display_width(c.encode_utf8(&mut [0; 4])),
+                });
+            }
+        }
+        wrap_glyphs(glyphs, width, &mut rows);
+    }
+
+    let mut links = Vec::new();
+    let lines = rows
+        .into_iter()
+        .enumerate()
+        .map(|(row, glyphs)| {
+            let mut spans: Vec<Span<'static>> = Vec::new();
+            let mut column = 0;
+            for glyph in &glyphs {
+                match spans.last_mut() {
+                    Some(span) if span.style == glyph.style => span.content.to_mut().push(glyph.c),
+                    _ => spans.push(Span::styled(glyph.c.to_string(), glyph.style)),
+                }
+                if let Some(link) = glyph.link {
+                    match links.last_mut() {
+                        Some(LinkSegment {
+                            row: r,
+                            column: start,
+                            width,
+                            url,
+                        }) if *r == row
+                            && *url == urls[link]
+                            && *start + *width == column as u16 =>
+                        {
+                            *width += glyph.width as u16
+                        }
+                        _ => links.push(LinkSegment {
+                            row,
+                            column: column as u16,
+                            width: glyph.width as u16,
+                            url: urls[link].clone(),
+                        }),
+                    }
+                }
+                column += glyph.width;
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    (lines, links)
+}
+
+// Greedy word wrap of one line's glyphs; words wider than a row are split
+fn wrap_glyphs(glyphs: Vec<Glyph>, width: usize, rows: &mut Vec<Vec<Glyph>>) {
+    let first = rows.len();
+    // The width of the word starting at each glyph
+    let mut word_widths = vec![0; glyphs.len()];
+    let mut word = 0;
+    for (i, glyph) in glyphs.iter().enumerate().rev() {
+        word = if glyph.c.is_whitespace() {
+            0
+        } else {
+            word + glyph.width
+        };
+        word_widths[i] = word;
+    }
+
+    let mut row: Vec<Glyph> = Vec::new();
+    let mut row_width = 0;
+    for (glyph, word_width) in glyphs.into_iter().zip(word_widths) {
+        if glyph.c.is_whitespace() {
+            if !row.is_empty() && row_width + glyph.width <= width {
+                row_width += glyph.width;
+                row.push(glyph);
+            }
+            continue;
+        }
+
+        let starts_word = row.last().is_some_and(|last| last.c.is_whitespace());
+        if (starts_word && row_width + word_width > width)
+            || (!row.is_empty() && row_width + glyph.width > width)
+        {
+            trim_end(&mut row);
+            rows.push(std::mem::take(&mut row));
+            row_width = 0;
+        }
+        row_width += glyph.width;
+        row.push(glyph);
+    }
+
+    trim_end(&mut row);
+    if !row.is_empty() || rows.len() == first {
+        rows.push(row);
+    }
+}
+
+fn trim_end(row: &mut Vec<Glyph>) {
+    while row.last().is_some_and(|glyph| glyph.c.is_whitespace()) {
+        row.pop();
+    }
 }
 
-fn draw_repo_detail<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
-    let repo_data = &app.repos[app.selected_index];
+fn draw_repo_detail<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let theme = theme::get();
+    let Some(row) = app.selected_row() else {
+        return;
+    };
+    let repo_data = &app.repos[row];
 
     let title = &repo_data.full_name;
 
     let content = vec![
-        Line::from(vec![Span::styled(
-            "Description:",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![Span::raw(
+        Line::from(vec![Span::styled("Description:", theme.heading())]),
+        Line::from(vec![Span::raw(text::single_line(
             repo_data
                 .description
-                .clone()
-                .unwrap_or_else(|| "No description available.".to_string()),
-        )]),
+                .as_deref()
+                .unwrap_or("No description available."),
+        ))]),
         Line::from(vec![]),
         Line::from(vec![
-            Span::styled(
-                "Stars: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
+            Span::styled("Stars: ", theme.heading()),
             Span::styled(
                 format!("★ {}", repo_data.stargazers_count),
-                Style::default().fg(Color::Cyan),
+                theme.fg(theme.key),
             ),
         ]),
         Line::from(vec![
-            Span::styled(
-                "Forks: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                format!("🍴 {}", repo_data.forks_count),
-                Style::default().fg(Color::Cyan),
-            ),
+            Span::styled("Forks: ", theme.heading()),
+            Span::styled(format!("🍴 {}", repo_data.forks_count), theme.fg(theme.key)),
         ]),
         Line::from(vec![]),
         Line::from(vec![
-            Span::styled(
-                "Language: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
+            Span::styled("Language: ", theme.heading()),
             Span::styled(
                 repo_data
                     .language
                     .clone()
                     .unwrap_or_else(|| "Unknown".to_string()),
-                Style::default().fg(Color::Magenta),
+                theme.fg(theme.tag),
             ),
         ]),
         Line::from(vec![]),
         Line::from(vec![
+            Span::styled("URL: ", theme.heading()),
             Span::styled(
-                "URL: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                &repo_data.html_url,
-                Style::default()
-                    .fg(Color::Blue)
-                    .add_modifier(Modifier::UNDERLINED),
+                repo_data.html_url.clone(),
+                theme.fg(theme.accent).add_modifier(Modifier::UNDERLINED),
             ),
         ]),
         // Add navigation help
         Line::from(vec![]),
-        Line::from(vec![]),
         Line::from(vec![Span::styled(
-            "Press ESC or q to go back",
-            Style::default().fg(Color::Gray),
+            format!(
+                "Press '{}' to open it in the browser or '{}' to copy it",
+                app.keymap
+                    .hint(Context::Detail, Action::OpenLink(Link::Repository)),
+                app.keymap
+                    .hint(Context::Detail, Action::CopyLink(Link::Repository))
+            ),
+            theme.fg(theme.accent),
+        )]),
+        Line::from(vec![Span::styled(
+            format!(
+                "Press {} or {} to go back",
+                app.keymap.hint(Context::Detail, Action::Back),
+                app.keymap.hint(Context::Detail, Action::Quit)
+            ),
+            theme.fg(theme.muted),
         )]),
     ];
 
-    let detail = Paragraph::new(content)
-        .block(Block::default().borders(Borders::ALL).title(title.as_str()))
-        .wrap(Wrap { trim: true })
-        .scroll((app.detail_scroll as u16, 0));
+    let title = title.clone();
+    draw_detail(f, app, area, title, content);
+}
+fn draw_version_history<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let theme = theme::get();
+    let title = match app.detail_crate() {
+        Some(crate_data) => format!("{} - Version History", crate_data.name),
+        None => "Version History".to_string(),
+    };
 
-    f.render_widget(detail, area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Versions
+            Constraint::Length(4), // docs.rs build of the selected version, and diff hint or error
+        ])
+        .split(area);
+
+    let items: Vec<ListItem> = app
+        .versions
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let mark = if app.diff_marks.contains(&i) {
+                "[x] "
+            } else {
+                "[ ] "
+            };
+
+            let mut spans = vec![
+                Span::styled(mark, theme.fg(theme.key)),
+                Span::styled(
+                    format!("v{:<16}", v.num),
+                    theme.fg(theme.accent).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("{:<18}", format_date(&v.created_at)),
+                    theme.fg(theme.muted),
+                ),
+                Span::styled(
+                    format!("{:<18}", format!("{} downloads", v.downloads)),
+                    theme.fg(theme.warn),
+                ),
+                rust_version_span(v),
+            ];
+
+            // Filled in as the statuses arrive from docs.rs
+            spans.push(
+                match app.version_docs_status(&v.num).flatten().map(|d| d.build) {
+                    Some(DocsBuild::Built) => Span::styled("docs ✓", theme.fg(theme.good)),
+                    Some(DocsBuild::Failed) => Span::styled("docs ✗", theme.fg(theme.bad)),
+                    Some(DocsBuild::Building) => Span::styled("docs …", theme.fg(theme.warn)),
+                    Some(DocsBuild::NotBuilt) | None => {
+                        Span::styled("docs ?", theme.fg(theme.muted))
+                    }
+                },
+            );
+
+            if let Some(ref publisher) = v.published_by {
+                spans.push(Span::styled(
+                    format!(" by {}", publisher.login),
+                    theme.fg(theme.tag),
+                ));
+            }
+
+            if v.yanked {
+                spans.push(Span::styled(" (yanked)", theme.fg(theme.bad)));
+            }
+
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    if items.is_empty() {
+        let message = match app.version_error {
+            Some(ref msg) => Span::styled(format!("Error: {}", msg), theme.fg(theme.bad)),
+            None => Span::styled("No versions found", theme.fg(theme.muted)),
+        };
+        let empty = Paragraph::new(Line::from(message))
+            .block(Block::default().borders(Borders::ALL).title(title));
+
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let mut list_state = ratatui::widgets::ListState::default();
+    list_state.select(Some(app.version_index.min(items.len() - 1)));
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(theme.selected().add_modifier(Modifier::BOLD))
+        .highlight_symbol("> ");
+
+    f.render_stateful_widget(list, chunks[0], &mut list_state);
+    app.hits.add_rows(
+        chunks[0],
+        list_state.offset(),
+        &vec![1; app.versions.len()],
+        |i| Some(Target::Version(i)),
+    );
+
+    let hint = match app.version_error {
+        Some(ref msg) => Span::styled(format!("Error: {}", msg), theme.fg(theme.bad)),
+        None => Span::styled(
+            format!(
+                "{} to mark up to two versions | {} to diff marked (or selected vs previous)",
+                app.keymap.hint(Context::Versions, Action::Mark),
+                app.keymap.hint(Context::Versions, Action::Diff)
+            ),
+            theme.fg(theme.muted),
+        ),
+    };
+
+    let selected = app.versions.get(app.version_index);
+    let docs = match selected.and_then(|v| app.version_docs_status(&v.num).flatten()) {
+        Some(status) => docs_status_line(status),
+        None if selected.is_some_and(|v| app.version_docs_pending(&v.num)) => {
+            Line::from(Span::styled("docs.rs: checking…", theme.fg(theme.muted)))
+        }
+        None => Line::from(Span::styled(
+            "docs.rs: status unavailable",
+            theme.fg(theme.muted),
+        )),
+    };
+
+    let hint =
+        Paragraph::new(vec![docs, Line::from(hint)]).block(Block::default().borders(Borders::ALL));
+
+    f.render_widget(hint, chunks[1]);
 }
-fn draw_help<B: Backend>(f: &mut Frame<B>, _app: &App, area: Rect) {
-    let text = vec![
+
+fn draw_version_diff<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let theme = theme::get();
+    let version_diff = match app.version_diff {
+        Some(ref version_diff) => version_diff,
+        None => return,
+    };
+
+    let title = format!(
+        "{}: v{} → v{}",
+        version_diff.name, version_diff.old_version, version_diff.new_version
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Summary of concerns
+            Constraint::Min(0),    // File tree and diff
+        ])
+        .split(area);
+
+    // Summary line with the highlighted concerns
+    let concerns = version_diff.concerns();
+    let mut summary = vec![Span::styled(
+        format!("{} files changed", version_diff.files.len()),
+        theme.fg(theme.text),
+    )];
+    if concerns.is_empty() {
+        summary.push(Span::styled(
+            " · no build script, dependency or unsafe changes",
+            theme.fg(theme.good),
+        ));
+    }
+    for concern in &concerns {
+        summary.push(Span::styled(
+            format!(" · ⚠ {}", concern.label()),
+            theme.fg(theme.bad).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    let summary = Paragraph::new(Line::from(summary))
+        .block(Block::default().borders(Borders::ALL).title(title));
+
+    f.render_widget(summary, chunks[0]);
+
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .split(chunks[1]);
+
+    // File tree, with a directory row whenever the parent directory changes
+    let mut tree_items = Vec::new();
+    // The file each row shows; directory rows have none
+    let mut row_files = Vec::new();
+    let mut selected_row = 0;
+    let mut current_dir = "";
+
+    for (i, file) in version_diff.files.iter().enumerate() {
+        let (dir, file_name) = match file.path.rsplit_once('/') {
+            Some((dir, file_name)) => (dir, file_name),
+            None => ("", file.path.as_str()),
+        };
+
+        if dir != current_dir {
+            if !dir.is_empty() {
+                tree_items.push(ListItem::new(Line::from(Span::styled(
+                    format!("{}/", dir),
+                    theme.fg(theme.muted),
+                ))));
+                row_files.push(None);
+            }
+            current_dir = dir;
+        }
+
+        let (marker, color) = match file.status {
+            FileStatus::Added => ("+", theme.good),
+            FileStatus::Removed => ("-", theme.bad),
+            FileStatus::Modified => ("~", theme.warn),
+        };
+
+        let indent = if dir.is_empty() { "" } else { "  " };
+        let mut spans = vec![
+            Span::raw(indent),
+            Span::styled(format!("{} ", marker), theme.fg(color)),
+            Span::styled(file_name.to_string(), theme.fg(color)),
+        ];
+        if !file.concerns.is_empty() {
+            spans.push(Span::styled(
+                " ⚠",
+                theme.fg(theme.bad).add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        if i == app.diff_file_index {
+            selected_row = tree_items.len();
+        }
+        tree_items.push(ListItem::new(Line::from(spans)));
+        row_files.push(Some(i));
+    }
+
+    let mut tree_state = ratatui::widgets::ListState::default();
+    if !version_diff.files.is_empty() {
+        tree_state.select(Some(selected_row));
+    }
+
+    let tree = List::new(tree_items)
+        .block(Block::default().borders(Borders::ALL).title("Files"))
+        .highlight_style(theme.selected().add_modifier(Modifier::BOLD));
+
+    f.render_stateful_widget(tree, panes[0], &mut tree_state);
+    app.hits.add_rows(
+        panes[0],
+        tree_state.offset(),
+        &vec![1; row_files.len()],
+        |row| row_files[row].map(Target::DiffFile),
+    );
+
+    // Unified diff for the selected file
+    let file = match version_diff.files.get(app.diff_file_index) {
+        Some(file) => file,
+        None => {
+            let empty = Paragraph::new("The two versions have identical contents")
+                .style(theme.fg(theme.muted))
+                .block(Block::default().borders(Borders::ALL).title("Diff"));
+            f.render_widget(empty, panes[1]);
+            return;
+        }
+    };
+
+    let lines: Vec<Line> = file
+        .lines
+        .iter()
+        .map(|line| {
+            let (prefix, style) = match line.kind {
+                DiffLineKind::Hunk => ("", theme.fg(theme.key)),
+                DiffLineKind::Context => (" ", Style::default()),
+                DiffLineKind::Added => ("+", theme.fg(theme.good)),
+                DiffLineKind::Removed => ("-", theme.fg(theme.bad)),
+            };
+            let style = if line.flagged {
+                style.add_modifier(Modifier::BOLD | Modifier::REVERSED)
+            } else {
+                style
+            };
+
+            Line::from(Span::styled(
+                format!("{}{}", prefix, text::single_line(&line.text)),
+                style,
+            ))
+        })
+        .collect();
+
+    let diff = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(file.path.as_str()),
+        )
+        .scroll((app.diff_scroll as u16, 0));
+
+    f.render_widget(diff, panes[1]);
+}
+
+fn draw_readme<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let theme = theme::get();
+    let Some(ref readme) = app.readme else {
+        let error = Paragraph::new(app.readme_error.clone().unwrap_or_default())
+            .style(theme.fg(theme.bad))
+            .block(Block::default().borders(Borders::ALL).title("README"))
+            .wrap(Wrap { trim: true });
+        f.render_widget(error, area);
+        return;
+    };
+
+    let width = area.width.saturating_sub(2) as usize;
+    let lines = if readme.is_markdown() {
+        markdown::render(&readme.text, width)
+    } else {
+        markdown::render_plain(&readme.text, width)
+    };
+    let title = format!("{} v{} · {}", readme.name, readme.version, readme.path);
+
+    // Stop once the last line is at the bottom of the pane
+    let height = area.height.saturating_sub(2) as usize;
+    let max_scroll = lines.len().saturating_sub(height);
+    app.readme_scroll = app.readme_scroll.min(max_scroll);
+    let position = (app.readme_scroll * 100)
+        .checked_div(max_scroll)
+        .unwrap_or(100);
+
+    let readme = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("{} ({}%)", title, position)),
+        )
+        .scroll((app.readme_scroll as u16, 0));
+
+    f.render_widget(readme, area);
+}
+
+fn draw_api_docs<B: Backend>(f: &mut Frame<B>, app: &mut App, area: Rect) {
+    let theme = theme::get();
+    let Some(ref docs) = app.api_docs else {
+        let mut message = match (&app.api_error, app.detail_crate()) {
+            (Some(error), _) => vec![Line::from(Span::styled(
+                error.clone(),
+                theme.fg(theme.bad),
+            ))],
+            (None, Some(crate_data)) if app.api_building => vec![Line::from(Span::styled(
+                format!(
+                    "Building the API of {} v{} with cargo +nightly rustdoc… This can take a few minutes",
+                    crate_data.name, crate_data.max_version
+                ),
+                theme.fg(theme.muted),
+            ))],
+            (None, Some(crate_data)) => vec![Line::from(Span::styled(
+                format!(
+                    "Loading the API of {} v{} from docs.rs…",
+                    crate_data.name, crate_data.max_version
+                ),
+                theme.fg(theme.muted),
+            ))],
+            (None, None) => Vec::new(),
+        };
+        if app.api_build_prompt {
+            message.push(Line::from(""));
+            message.push(Line::from(vec![
+                Span::styled(
+                    "Build it here with cargo +nightly rustdoc? This compiles the crate, running its build script and proc macros on this machine. ",
+                    theme.fg(theme.text),
+                ),
+                Span::styled("y", theme.fg(theme.key)),
+                Span::styled(" to build, ", theme.fg(theme.muted)),
+                Span::styled("n", theme.fg(theme.key)),
+                Span::styled(" to cancel", theme.fg(theme.muted)),
+            ]));
+        } else if app.api_error.is_some()
+            && !app.api_building
+            && !config::get().api_docs.local_build
+        {
+            message.push(Line::from(""));
+            message.push(Line::from(Span::styled(
+                "Building the docs locally can be allowed with local-build = true under [api-docs] in the config",
+                theme.fg(theme.muted),
+            )));
+        }
+        let message = Paragraph::new(message)
+            .block(Block::default().borders(Borders::ALL).title("API Docs"))
+            .wrap(Wrap { trim: true });
+        f.render_widget(message, area);
+        return;
+    };
+
+    let searching = app.api_search_mode || !app.api_query.is_empty();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(if searching {
+            [Constraint::Length(3), Constraint::Min(0)]
+        } else {
+            [Constraint::Length(0), Constraint::Min(0)]
+        })
+        .split(area);
+
+    if searching {
+        let cursor = if app.api_search_mode { "_" } else { "" };
+        let search = Paragraph::new(Line::from(vec![
+            Span::styled(app.api_query.clone(), theme.fg(theme.text)),
+            Span::styled(cursor, theme.fg(theme.key)),
+        ]))
+        .block(Block::default().borders(Borders::ALL).title("Search items"));
+        f.render_widget(search, chunks[0]);
+    }
+
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(chunks[1]);
+
+    // Item tree, or the search results by full path
+    let rows = docs.rows(&app.api_expanded, &app.api_query);
+    app.api_index = app.api_index.min(rows.len().saturating_sub(1));
+    let items: Vec<ListItem> = rows
+        .iter()
+        .map(|&(index, depth)| {
+            let item = &docs.items[index];
+            let marker = if item.children.is_empty() || searching {
+                "  "
+            } else if app.api_expanded.contains(&index) || index == 0 {
+                "▾ "
+            } else {
+                "▸ "
+            };
+            let name = if searching { &item.path } else { &item.name };
+            let name_style = if item.deprecation.is_some() {
+                theme.fg(theme.muted).add_modifier(Modifier::CROSSED_OUT)
+            } else if matches!(item.kind, ItemKind::Module) {
+                theme.fg(theme.accent).add_modifier(Modifier::BOLD)
+            } else {
+                theme.fg(theme.text)
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{}{}", "  ".repeat(depth), marker)),
+                Span::styled(format!("{:<7}", item.kind.label()), theme.fg(theme.key)),
+                Span::styled(name.clone(), name_style),
+            ]))
+        })
+        .collect();
+
+    let title = format!("{} v{}", docs.name, docs.version);
+    if items.is_empty() {
+        let empty = Paragraph::new("No items match the search")
+            .style(theme.fg(theme.muted))
+            .block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(empty, panes[0]);
+    } else {
+        let mut tree_state = ratatui::widgets::ListState::default();
+        tree_state.select(Some(app.api_index));
+        let tree = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(theme.selected().add_modifier(Modifier::BOLD));
+        f.render_stateful_widget(tree, panes[0], &mut tree_state);
+        app.hits
+            .add_rows(panes[0], tree_state.offset(), &vec![1; rows.len()], |row| {
+                Some(Target::ApiItem(row))
+            });
+    }
+
+    // Signature and docs of the selected item
+    let Some(item) = rows
+        .get(app.api_index)
+        .map(|&(index, _)| &docs.items[index])
+    else {
+        f.render_widget(Block::default().borders(Borders::ALL), panes[1]);
+        return;
+    };
+    let mut source = format!("```rust\n{}\n```\n\n", item.signature);
+    if let Some(ref deprecation) = item.deprecation {
+        source.push_str(&format!("> **Deprecated** {}\n\n", deprecation));
+    }
+    if item.docs.is_empty() {
+        source.push_str("*No documentation*\n\n");
+    } else {
+        source.push_str(&item.docs);
+        source.push_str("\n\n");
+    }
+    if !item.implements.is_empty() {
+        let traits: Vec<String> = item.implements.iter().map(|t| format!("`{}`", t)).collect();
+        source.push_str(&format!("**Implements:** {}\n", traits.join(", ")));
+    }
+
+    let width = panes[1].width.saturating_sub(2) as usize;
+    let lines = markdown::render(&source, width);
+    let height = panes[1].height.saturating_sub(2) as usize;
+    app.api_scroll = app.api_scroll.min(lines.len().saturating_sub(height));
+
+    let details = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(item.path.clone()),
+        )
+        .scroll((app.api_scroll as u16, 0));
+    f.render_widget(details, panes[1]);
+}
+
+fn draw_help<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
+    let theme = theme::get();
+    let heading = |title: &str| Line::from(Span::styled(title.to_string(), theme.heading()));
+    let key_line = |keys: &str, description: &str| {
+        Line::from(vec![
+            Span::styled(keys.to_string(), theme.fg(theme.key)),
+            Span::raw(format!(" - {}", description)),
+        ])
+    };
+
+    let mut text = vec![
         Line::from(Span::styled(
             "Crates Explorer - Help",
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
+            theme.fg(theme.good).add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
-        Line::from(Span::styled(
-            "Keyboard Controls:",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Tab", Style::default().fg(Color::Cyan)),
-            Span::raw(" / "),
-            Span::styled("Shift+Tab", Style::default().fg(Color::Cyan)),
-            Span::raw(" - Switch between tabs"),
-        ]),
-        Line::from(vec![
-            Span::styled("j", Style::default().fg(Color::Cyan)),
-            Span::raw(" / "),
-            Span::styled("Down", Style::default().fg(Color::Cyan)),
-            Span::raw(" - Move down"),
-        ]),
-        Line::from(vec![
-            Span::styled("k", Style::default().fg(Color::Cyan)),
-            Span::raw(" / "),
-            Span::styled("Up", Style::default().fg(Color::Cyan)),
-            Span::raw(" - Move up"),
-        ]),
-        Line::from(vec![
-            Span::styled("Enter", Style::default().fg(Color::Cyan)),
-            Span::raw(" - Show details"),
-        ]),
-        Line::from(vec![
-            Span::styled("/", Style::default().fg(Color::Cyan)),
-            Span::raw(" - Search (in Search tab)"),
-        ]),
+    ];
+
+    // Generated from the active keymap, so rebound keys show up here
+    for context in Context::ALL {
+        text.push(heading(&format!("{}:", context.label())));
+        for (action, keys) in app.keymap.actions(context) {
+            text.push(key_line(&keys.join(" / "), action.description(context)));
+        }
+        text.push(Line::from(""));
+    }
+
+    let config_path = config::config_path()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| "config.toml".to_string());
+    text.extend([
+        heading("Other Keys:"),
+        key_line("ctrl-c", "Quit from anywhere"),
+        key_line(
+            "Mouse",
+            "Click to select, double-click to open, wheel to scroll, click a link to open it. Hold Shift to select text.",
+        ),
+        key_line(
+            "Search input",
+            "Enter to search, Up/Down to pick a suggestion, Tab to complete",
+        ),
         Line::from(vec![
-            Span::styled("1-4", Style::default().fg(Color::Cyan)),
-            Span::raw(" - Switch tabs directly"),
+            Span::raw("  Qualifiers: "),
+            Span::styled(
+                "license:mit downloads:>1M updated:<90d category:SLUG keyword:async",
+                theme.fg(theme.key),
+            ),
         ]),
+        key_line(
+            "Filters",
+            "l license, m downloads, u updated, r repository, z hide 0.0.x, c clear",
+        ),
         Line::from(vec![
-            Span::styled("q", Style::default().fg(Color::Cyan)),
-            Span::raw(" / "),
-            Span::styled("Ctrl+C", Style::default().fg(Color::Cyan)),
-            Span::raw(" - Quit"),
+            Span::raw("Rebind keys in the "),
+            Span::styled("[keys]", theme.fg(theme.key)),
+            Span::raw(" table of "),
+            Span::styled(config_path, theme.fg(theme.key)),
+            Span::raw(", e.g. "),
+            Span::styled("quick-filter = [\"ctrl-f\"]", theme.fg(theme.key)),
         ]),
         Line::from(""),
+        heading("Tab Guide:"),
         Line::from(vec![
-            Span::styled("Esc", Style::default().fg(Color::Cyan)),
-            Span::raw(" - Exit detail view or search input"),
+            Span::styled("Search", theme.fg(theme.good)),
+            Span::raw(" - Search for crates by name"),
         ]),
-        Line::from(""),
-        Line::from(Span::styled(
-            "In Detail View:",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )),
         Line::from(vec![
-            Span::styled("j/k", Style::default().fg(Color::Cyan)),
-            Span::raw(" - Scroll up/down"),
+            Span::styled("Recent", theme.fg(theme.good)),
+            Span::raw(" - Recently updated crates"),
         ]),
         Line::from(vec![
-            Span::styled("PageUp/PageDown", Style::default().fg(Color::Cyan)),
-            Span::raw(" - Scroll by page"),
+            Span::styled("Trending", theme.fg(theme.good)),
+            Span::raw(" - Trending Rust repositories on GitHub"),
         ]),
-        Line::from(""),
-        Line::from(Span::styled(
-            "Tab Guide:",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(""),
         Line::from(vec![
-            Span::styled("Search", Style::default().fg(Color::Green)),
-            Span::raw(" - Search for crates by name"),
+            Span::styled("Compare", theme.fg(theme.good)),
+            Span::raw(" - Compare key metrics and trust scores across crates"),
         ]),
         Line::from(vec![
-            Span::styled("Recent", Style::default().fg(Color::Green)),
-            Span::raw(" - Recently updated crates"),
+            Span::styled("Categories", theme.fg(theme.good)),
+            Span::raw(" - Browse categories and keywords and their top crates"),
         ]),
         Line::from(vec![
-            Span::styled("Trending", Style::default().fg(Color::Green)),
-            Span::raw(" - Trending Rust repositories on GitHub"),
+            Span::styled("Watchlist", theme.fg(theme.good)),
+            Span::raw(
+                " - New releases, yanks, advisories, license and owner changes of watched crates",
+            ),
         ]),
         Line::from(vec![
-            Span::styled("Help", Style::default().fg(Color::Green)),
+            Span::styled("Help", theme.fg(theme.good)),
             Span::raw(" - This help screen"),
         ]),
         Line::from(""),
-        Line::from(Span::styled(
-            "License & Security Features:",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )),
+        heading("License & Security Features:"),
         Line::from(vec![
             Span::raw("• Crate details now include "),
-            Span::styled("license information", Style::default().fg(Color::Green)),
+            Span::styled("license information", theme.fg(theme.good)),
             Span::raw(" and "),
-            Span::styled("security checks", Style::default().fg(Color::Red)),
+            Span::styled("security checks", theme.fg(theme.bad)),
         ]),
         Line::from(vec![Span::raw(
             "• Security warnings highlight potential issues with crates",
         )]),
-        Line::from(""),
-        Line::from(Span::styled(
-            "Compare Tab:",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )),
-        Line::from(vec![
-            Span::styled("5", Style::default().fg(Color::Cyan)),
-            Span::raw(" - Switch to Compare tab"),
-        ]),
-        Line::from(vec![
-            Span::styled("a", Style::default().fg(Color::Cyan)),
-            Span::raw(" - Add current crate to comparison or add new crate by name"),
-        ]),
         Line::from(vec![
-            Span::styled("d", Style::default().fg(Color::Cyan)),
-            Span::raw(" - Remove selected crate from comparison"),
+            Span::raw("• Tune rules in "),
+            Span::styled("~/.config/crates/policy.toml", theme.fg(theme.key)),
+            Span::raw(" or a project "),
+            Span::styled(".crates-policy.toml", theme.fg(theme.key)),
         ]),
-        Line::from(vec![
-            Span::styled("Enter", Style::default().fg(Color::Cyan)),
-            Span::raw(" - View detailed security and license info"),
-        ]),
-        Line::from(vec![Span::raw(
-            "Compare key metrics across multiple crates side by side",
-        )]),
-    ];
+    ]);
 
     let help = Paragraph::new(text)
         .block(Block::default().borders(Borders::ALL).title("Help"))
-        .wrap(Wrap { trim: true });
+        .wrap(Wrap { trim: true })
+        .scroll((app.help_scroll as u16, 0));
 
     f.render_widget(help, area);
 }
 
 fn draw_status_bar<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect) {
-    let mode_text = match app.current_tab {
-        Tab::Search => {
-            if app.show_detail {
-                "Search > Crate Detail"
-            } else if app.input_mode {
-                "Search > Input Mode"
-            } else {
-                "Search"
+    let theme = theme::get();
+    let mode_text = if app.fuzzy_mode {
+        "Quick Filter"
+    } else if app.show_diff {
+        "Crate Detail > Version Diff"
+    } else if app.show_versions {
+        "Crate Detail > Version History"
+    } else if app.show_readme {
+        "Crate Detail > README"
+    } else if app.show_api && app.api_search_mode {
+        "Crate Detail > API Docs > Search"
+    } else if app.show_api {
+        "Crate Detail > API Docs"
+    } else {
+        match app.current_tab {
+            Tab::Search => {
+                if app.show_detail {
+                    "Search > Crate Detail"
+                } else if app.input_mode {
+                    "Search > Input Mode"
+                } else if app.filter_mode {
+                    "Search > Filters"
+                } else {
+                    "Search"
+                }
             }
-        }
-        Tab::Recent => {
-            if app.show_detail {
-                "Recent > Crate Detail"
-            } else {
-                "Recent"
+            Tab::Recent => {
+                if app.show_detail {
+                    "Recent > Crate Detail"
+                } else {
+                    "Recent"
+                }
             }
-        }
-        Tab::Trending => {
-            if app.show_detail {
-                "Trending > Repository Detail"
-            } else {
-                "Trending"
+            Tab::Trending => {
+                if app.show_detail {
+                    "Trending > Repository Detail"
+                } else {
+                    "Trending"
+                }
             }
-        }
-        Tab::Compare => {
-            if app.show_detail {
-                "Compare > Crate Detail"
-            } else if app.compare_input_mode {
-                "Compare > Adding Crate"
-            } else {
-                "Compare"
+            Tab::Compare => {
+                if app.show_detail {
+                    "Compare > Crate Detail"
+                } else if app.show_side_by_side {
+                    "Compare > Side by Side"
+                } else if app.compare_input_mode {
+                    "Compare > Adding Crate"
+                } else if app.set_prompt.is_some() {
+                    "Compare > Set Name"
+                } else if app.sets_mode {
+                    "Compare > Saved Sets"
+                } else if app.columns_mode {
+                    "Compare > Columns"
+                } else {
+                    "Compare"
+                }
             }
+            Tab::Categories => {
+                if app.show_detail {
+                    "Categories > Crate Detail"
+                } else if app.browse_listing {
+                    "Categories > Crates"
+                } else {
+                    "Categories"
+                }
+            }
+            Tab::Watchlist => "Watchlist",
+            Tab::Help => "Help",
         }
-        Tab::Help => "Help",
     };
 
-    let navigation_help = if app.show_detail {
-        "ESC to go back | j/k to scroll"
-    } else if app.input_mode || app.compare_input_mode {
-        "ESC to cancel | Enter to confirm"
+    let key = |context: Context, action: Action| app.keymap.hint(context, action);
+    let list = |action: Action| key(Context::List, action);
+    let navigation_help = if app.fuzzy_mode {
+        "Type to narrow the list | Enter to keep the filter | ESC to clear it".to_string()
+    } else if app.show_diff {
+        format!(
+            "{} to go back | {}/{} to select file | {}/{} or {}/{} to scroll",
+            key(Context::Diff, Action::Back),
+            key(Context::Diff, Action::Down),
+            key(Context::Diff, Action::Up),
+            key(Context::Diff, Action::ScrollDown),
+            key(Context::Diff, Action::ScrollUp),
+            key(Context::Diff, Action::PageUp),
+            key(Context::Diff, Action::PageDown)
+        )
+    } else if app.show_versions {
+        format!(
+            "{} to go back | {} to mark | {} to diff",
+            key(Context::Versions, Action::Back),
+            key(Context::Versions, Action::Mark),
+            key(Context::Versions, Action::Diff)
+        )
+    } else if app.show_readme {
+        format!(
+            "{} to go back | {}/{} or {}/{} to scroll | {}/{} for start or end | click a link to open it",
+            key(Context::Readme, Action::Back),
+            key(Context::Readme, Action::Down),
+            key(Context::Readme, Action::Up),
+            key(Context::Readme, Action::PageDown),
+            key(Context::Readme, Action::PageUp),
+            key(Context::Readme, Action::Top),
+            key(Context::Readme, Action::Bottom)
+        )
+    } else if app.show_side_by_side {
+        format!(
+            "{} to go back | {}/{} or {}/{} to scroll | {}/{} for start or end",
+            key(Context::SideBySide, Action::Back),
+            key(Context::SideBySide, Action::Down),
+            key(Context::SideBySide, Action::Up),
+            key(Context::SideBySide, Action::PageDown),
+            key(Context::SideBySide, Action::PageUp),
+            key(Context::SideBySide, Action::Top),
+            key(Context::SideBySide, Action::Bottom)
+        )
+    } else if app.show_api && app.api_build_prompt {
+        "y to build the docs on this machine | n or ESC to cancel".to_string()
+    } else if app.api_search_mode {
+        "Type an item name | Enter to browse the results | ESC to clear the search".to_string()
+    } else if app.show_api {
+        format!(
+            "{} to go back | {}/{} to move | {} to expand | {} to search | {}/{} to scroll the docs",
+            key(Context::Api, Action::Back),
+            key(Context::Api, Action::Down),
+            key(Context::Api, Action::Up),
+            key(Context::Api, Action::Expand),
+            key(Context::Api, Action::Search),
+            key(Context::Api, Action::ScrollDown),
+            key(Context::Api, Action::ScrollUp)
+        )
+    } else if app.show_detail {
+        format!(
+            "{} to go back | {}/{} to scroll | {} for versions | {} to pick a tag | {} to watch",
+            key(Context::Detail, Action::Back),
+            key(Context::Detail, Action::Down),
+            key(Context::Detail, Action::Up),
+            key(Context::Detail, Action::Versions),
+            key(Context::Detail, Action::NextTag),
+            key(Context::Detail, Action::Watch)
+        )
+    } else if app.filter_mode {
+        "ESC or Enter when done | filter changes apply immediately".to_string()
+    } else if app.input_mode {
+        "ESC to cancel | Enter to search | Up/Down to pick a suggestion | Tab to complete"
+            .to_string()
+    } else if app.compare_input_mode || app.set_prompt.is_some() {
+        "ESC to cancel | Enter to confirm".to_string()
+    } else if app.sets_mode {
+        format!(
+            "{}/{} to select | {} to load | {} to rename | {} to delete | {} to close",
+            key(Context::Sets, Action::Down),
+            key(Context::Sets, Action::Up),
+            key(Context::Sets, Action::Open),
+            key(Context::Sets, Action::Rename),
+            key(Context::Sets, Action::Remove),
+            key(Context::Sets, Action::Back)
+        )
+    } else if app.columns_mode {
+        format!(
+            "{}/{} to select | {} to show or hide | {}/{} to move | {} to sort by | {} to close",
+            key(Context::Columns, Action::Down),
+            key(Context::Columns, Action::Up),
+            key(Context::Columns, Action::Expand),
+            key(Context::Columns, Action::MoveDown),
+            key(Context::Columns, Action::MoveUp),
+            key(Context::Columns, Action::Sort),
+            key(Context::Columns, Action::Back)
+        )
+    } else if !app.pending_keys.is_empty() {
+        format!("{} …", keymap::show_sequence(&app.pending_keys))
     } else if matches!(app.current_tab, Tab::Search) {
-        "/ to search | Enter to view details | a to add to comparison | q to quit"
+        format!(
+            "{} to search | {} to sort | {} to filter | {} to quick filter | {} to view details | {} to add to comparison | {} to quit",
+            list(Action::Search),
+            list(Action::Sort),
+            list(Action::Filters),
+            list(Action::QuickFilter),
+            list(Action::Open),
+            list(Action::Add),
+            list(Action::Quit)
+        )
     } else if matches!(app.current_tab, Tab::Recent) {
-        "Enter to view details | a to add to comparison | q to quit"
+        format!(
+            "{} to quick filter | {} to view details | {} to add to comparison | {} to quit",
+            list(Action::QuickFilter),
+            list(Action::Open),
+            list(Action::Add),
+            list(Action::Quit)
+        )
+    } else if matches!(app.current_tab, Tab::Watchlist) {
+        format!(
+            "{} to check now | {} to mark all read | {} to stop watching | {} to quit",
+            list(Action::CheckWatchlist),
+            list(Action::MarkRead),
+            list(Action::Remove),
+            list(Action::Quit)
+        )
+    } else if matches!(app.current_tab, Tab::Categories) && app.browse_listing {
+        format!(
+            "{} to go back | {} to view details | {} to add to comparison | {} to quit",
+            list(Action::Back),
+            list(Action::Open),
+            list(Action::Add),
+            list(Action::Quit)
+        )
+    } else if matches!(app.current_tab, Tab::Categories) {
+        format!(
+            "{} to switch pane | {} to expand | {} to list crates | {} to quit",
+            list(Action::SwitchPane),
+            list(Action::Expand),
+            list(Action::Open),
+            list(Action::Quit)
+        )
     } else if matches!(app.current_tab, Tab::Compare) {
-        "a to add crate | d to remove | Enter to view details | q to quit"
+        format!(
+            "{} to add crate | {} to remove | {} to sort | {} for columns | {} to mark | {} side by side | {} to save set | {} to open sets | {} to quick filter | {} to view details | {} to quit",
+            list(Action::Add),
+            list(Action::Remove),
+            list(Action::Sort),
+            list(Action::Columns),
+            list(Action::Mark),
+            list(Action::SideBySide),
+            list(Action::SaveSet),
+            list(Action::OpenSets),
+            list(Action::QuickFilter),
+            list(Action::Open),
+            list(Action::Quit)
+        )
+    } else if matches!(app.current_tab, Tab::Help) {
+        format!(
+            "{}/{} to scroll | {} to quit",
+            list(Action::Down),
+            list(Action::Up),
+            list(Action::Quit)
+        )
     } else {
-        "Enter to view details | q to quit"
+        format!(
+            "{} to view details | {} to quit",
+            list(Action::Open),
+            list(Action::Quit)
+        )
     };
 
-    let status = format!("{} | {}", mode_text, navigation_help);
+    let status = match app.notice {
+        Some(ref notice) => format!("{} | {}", mode_text, notice),
+        None => format!("{} | {}", mode_text, navigation_help),
+    };
 
-    let status_bar = Paragraph::new(Span::styled(
-        status,
-        Style::default().fg(Color::White).bg(Color::DarkGray),
-    ))
-    .block(Block::default().borders(Borders::ALL))
-    .alignment(ratatui::layout::Alignment::Center);
+    let status_bar = Paragraph::new(Span::styled(status, theme.status_bar()))
+        .block(Block::default().borders(Borders::ALL))
+        .alignment(ratatui::layout::Alignment::Center);
 
     f.render_widget(status_bar, area);
 }
 
+// Security findings, worst first, coloured by severity
+fn findings_lines(findings: &[Finding]) -> Vec<Line<'static>> {
+    let theme = theme::get();
+    if findings.is_empty() {
+        return vec![Line::from(vec![Span::styled(
+            "✓ No security issues detected",
+            theme.fg(theme.good),
+        )])];
+    }
+
+    let mut sorted: Vec<&Finding> = findings.iter().collect();
+    sorted.sort_by_key(|f| std::cmp::Reverse(f.severity));
+
+    let mut lines = vec![Line::from(vec![Span::styled(
+        "⚠ Security warnings:",
+        theme.fg(theme.bad),
+    )])];
+
+    for finding in sorted {
+        let severity_style = match finding.severity {
+            Severity::High => theme.fg(theme.bad).add_modifier(Modifier::BOLD),
+            Severity::Medium => theme.fg(theme.warn),
+            Severity::Low => theme.fg(theme.muted),
+        };
+
+        lines.push(Line::from(vec![
+            Span::raw("  • "),
+            Span::styled(format!("[{}] ", finding.severity.label()), severity_style),
+            Span::styled(finding.message.clone(), theme.fg(theme.bad)),
+        ]));
+    }
+
+    lines
+}
+
+// Composite trust score with a bar per signal
+fn scorecard_lines(scorecard: &Scorecard) -> Vec<Line<'static>> {
+    let theme = theme::get();
+    let total = scorecard.total();
+    let mut lines = vec![
+        Line::from(vec![]),
+        Line::from(vec![
+            Span::styled("Trust Score: ", theme.heading()),
+            Span::styled(
+                format!("{}/100", total),
+                theme.fg(score_color(total)).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+    ];
+
+    for signal in &scorecard.signals {
+        let (bar, value, color) = match signal.score {
+            Some(score) => {
+                let filled = (score as usize + 5) / 10;
+                (
+                    format!("{}{}", "█".repeat(filled), "░".repeat(10 - filled)),
+                    format!("{:>3}", score),
+                    score_color(score),
+                )
+            }
+            None => ("░".repeat(10), "  -".to_string(), theme.faint),
+        };
+
+        lines.push(Line::from(vec![
+            Span::raw(format!("  {:<16}", signal.name)),
+            Span::styled(bar, theme.fg(color)),
+            Span::styled(format!(" {} ", value), theme.fg(color)),
+            Span::styled(signal.detail.clone(), theme.fg(theme.muted)),
+        ]));
+    }
+
+    lines
+}
+
+fn score_color(score: u8) -> Color {
+    let theme = theme::get();
+    match score {
+        75..=100 => theme.good,
+        50..=74 => theme.warn,
+        _ => theme.bad,
+    }
+}
+
+// A table's area without its header row and the margin below it, so its
+// rows can be recorded like a list's
+fn below_header(area: Rect) -> Rect {
+    Rect {
+        y: area.y + 2,
+        height: area.height.saturating_sub(2),
+        ..area
+    }
+}
+
+// Show the quick filter in a list title while it's being typed or applied
+fn fuzzy_title(app: &App, title: String) -> String {
+    if app.fuzzy_mode {
+        format!("{} [filter: {}▏]", title, app.fuzzy_query)
+    } else if !app.fuzzy_query.is_empty() {
+        format!(
+            "{} [filter: {} · {} shown, Esc to clear]",
+            title,
+            app.fuzzy_query,
+            app.visible_rows().len()
+        )
+    } else {
+        title
+    }
+}
+
+// Matched positions of a quick-filter match if it fell in the given field
+fn fuzzy_positions(matched: Option<&FuzzyMatch>, field: usize) -> Vec<usize> {
+    match matched {
+        Some(m) if m.field == field => m.positions.clone(),
+        _ => Vec::new(),
+    }
+}
+
+// Split text into spans, highlighting the characters at the given positions.
+// Positions count non-whitespace characters, starting at `first` for this
+// piece of text; the count after it is returned so wrapped lines can continue.
+fn highlight_spans(
+    text: &str,
+    positions: &[usize],
+    first: usize,
+    style: Style,
+) -> (Vec<Span<'static>>, usize) {
+    let highlight = style
+        .fg(theme::get().heading)
+        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_highlighted = false;
+    let mut ordinal = first;
+
+    for c in text.chars() {
+        let highlighted = !c.is_whitespace() && positions.contains(&ordinal);
+        if highlighted != run_highlighted && !run.is_empty() {
+            let style = if run_highlighted { highlight } else { style };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_highlighted = highlighted;
+        run.push(c);
+        if !c.is_whitespace() {
+            ordinal += 1;
+        }
+    }
+
+    if !run.is_empty() {
+        spans.push(Span::styled(
+            run,
+            if run_highlighted { highlight } else { style },
+        ));
+    }
+
+    (spans, ordinal)
+}
+
+// Keywords and categories of the crate in the detail view; the one picked
+// with 't' is highlighted
+fn tags_lines(app: &App) -> Vec<Line<'static>> {
+    let theme = theme::get();
+    if app.detail_tags.is_empty() {
+        return Vec::new();
+    }
+
+    let heading = theme.heading();
+    let mut keywords = vec![Span::styled("Keywords: ", heading)];
+    let mut categories = vec![Span::styled("Categories: ", heading)];
+
+    for (i, tag) in app.detail_tags.iter().enumerate() {
+        let (spans, label, color) = match tag {
+            Tag::Keyword(keyword) => (&mut keywords, format!("#{}", keyword), theme.key),
+            Tag::Category(slug) => (&mut categories, slug.clone(), theme.tag),
+        };
+
+        let mut style = theme.fg(color);
+        if app.detail_tag_index == Some(i) {
+            style = style.add_modifier(Modifier::REVERSED | Modifier::BOLD);
+        }
+        spans.push(Span::styled(label, style));
+        spans.push(Span::raw("  "));
+    }
+
+    let mut lines = vec![Line::from(vec![])];
+    if keywords.len() > 1 {
+        lines.push(Line::from(keywords));
+    }
+    if categories.len() > 1 {
+        lines.push(Line::from(categories));
+    }
+    lines.push(Line::from(vec![Span::styled(
+        format!(
+            "Press '{}' to pick a keyword or category, {} to browse crates with it",
+            app.keymap.hint(Context::Detail, Action::NextTag),
+            app.keymap.hint(Context::Detail, Action::Open)
+        ),
+        theme.fg(theme.accent),
+    )]));
+    lines
+}
+
+// The crate's pages, with the keys that open or copy them
+fn link_lines(app: &App, crate_data: &Crate) -> Vec<Line<'static>> {
+    let theme = theme::get();
+    let link = |label: &str, url: String| {
+        Line::from(vec![
+            Span::styled(format!("{}: ", label), theme.heading()),
+            Span::styled(
+                url,
+                theme.fg(theme.accent).add_modifier(Modifier::UNDERLINED),
+            ),
+        ])
+    };
+
+    let mut lines = vec![link("Documentation", api::docs_url(crate_data))];
+    if let Some(ref repo) = crate_data.repository {
+        lines.push(link("Repository", repo.clone()));
+    }
+    // Many crates point both at the same place
+    if let Some(ref homepage) = crate_data.homepage {
+        if crate_data.repository.as_ref() != Some(homepage) {
+            lines.push(link("Homepage", homepage.clone()));
+        }
+    }
+    lines.push(link("Crates.io", api::crate_page_url(&crate_data.name)));
+
+    let key = |action: Action| app.keymap.hint(Context::Detail, action);
+    lines.push(Line::from(Span::styled(
+        format!(
+            "Open with '{}' docs, '{}' repository, '{}' crates.io, '{}' homepage | '{}' copies {} = \"{}\"",
+            key(Action::OpenLink(Link::Docs)),
+            key(Action::OpenLink(Link::Repository)),
+            key(Action::OpenLink(Link::CratesIo)),
+            key(Action::OpenLink(Link::Homepage)),
+            key(Action::CopySnippet),
+            crate_data.name,
+            crate_data.max_version
+        ),
+        theme.fg(theme.muted),
+    )));
+    lines
+}
+
+// The docs.rs build of the version shown: whether it built and when. The
+// documented targets only appear on docs.rs's HTML pages, so they're unknown.
+fn docs_lines(status: Option<&DocsStatus>) -> Vec<Line<'static>> {
+    let theme = theme::get();
+    let Some(status) = status else {
+        return Vec::new();
+    };
+    vec![
+        Line::from(vec![]),
+        docs_status_line(status),
+        Line::from(vec![
+            Span::styled("Documented targets: ", theme.heading()),
+            Span::styled("unknown", theme.fg(theme.muted)),
+        ]),
+    ]
+}
+
+fn docs_status_line(status: &DocsStatus) -> Line<'static> {
+    let theme = theme::get();
+    let color = match status.build {
+        DocsBuild::Built => theme.good,
+        DocsBuild::Failed => theme.bad,
+        DocsBuild::Building => theme.warn,
+        DocsBuild::NotBuilt => theme.muted,
+    };
+
+    let mut spans = vec![
+        Span::styled("docs.rs: ", theme.heading()),
+        Span::styled(status.build.label(), theme.fg(color)),
+    ];
+    if let Some(ref built_at) = status.built_at {
+        spans.push(Span::styled(
+            format!(" · last build {}", format_date(built_at)),
+            theme.fg(theme.muted),
+        ));
+    }
+    if let Some(ref rustc) = status.rustc {
+        spans.push(Span::styled(format!(" · {}", rustc), theme.fg(theme.muted)));
+    }
+    Line::from(spans)
+}
+
+// The Rust the latest version needs next to what the project builds with,
+// with the version to stay on when an update would raise the MSRV
+fn rust_lines(support: Option<&RustSupport>) -> Vec<Line<'static>> {
+    let theme = theme::get();
+    let Some(support) = support else {
+        return Vec::new();
+    };
+
+    let needed = match support.rust_version {
+        Some(version) => format!("rust-version {}", version),
+        None => "no rust-version declared".to_string(),
+    };
+    let mut spans = vec![
+        Span::styled("Rust: ", theme.heading()),
+        Span::styled(
+            needed,
+            theme.fg(if support.raise.is_some() {
+                theme.bad
+            } else {
+                theme.text
+            }),
+        ),
+    ];
+    if let Some(ref edition) = support.edition {
+        spans.push(Span::styled(
+            format!(" · edition {}", edition),
+            theme.fg(theme.muted),
+        ));
+    }
+    let mut lines = vec![Line::from(vec![]), Line::from(spans)];
+
+    let local = msrv::local();
+    let mut spans = vec![Span::styled("Local: ", theme.heading())];
+    match local.rustc {
+        Some(version) => spans.push(Span::styled(
+            format!("rustc {}", version),
+            theme.fg(theme.text),
+        )),
+        None => spans.push(Span::styled("rustc not found", theme.fg(theme.muted))),
+    }
+    match local.msrv {
+        Some(version) => spans.push(Span::styled(
+            format!(" · project MSRV {}", version),
+            theme.fg(theme.text),
+        )),
+        None if local.manifest.is_some() => spans.push(Span::styled(
+            " · project declares no MSRV",
+            theme.fg(theme.muted),
+        )),
+        None => {}
+    }
+    if let Some(ref edition) = local.edition {
+        spans.push(Span::styled(
+            format!(" · edition {}", edition),
+            theme.fg(theme.muted),
+        ));
+    }
+    lines.push(Line::from(spans));
+
+    if let Some(ref raise) = support.raise {
+        let suggestion = match raise.compatible {
+            Some(ref version) => {
+                format!("Stay on v{} to keep building with {}", version, raise.limit)
+            }
+            None => format!("No published version builds with {}", raise.limit),
+        };
+        lines.push(Line::from(Span::styled(suggestion, theme.fg(theme.warn))));
+    }
+    lines
+}
+
+// A version's `rust-version` and edition, in the bad colour when it needs a
+// newer Rust than the project builds with
+fn rust_version_span(version: &Version) -> Span<'static> {
+    let theme = theme::get();
+    let needed = version.rust_version.as_deref().and_then(RustVersion::parse);
+    let label = match (needed, version.edition.as_deref()) {
+        (Some(needed), Some(edition)) => format!("Rust {} · {}", needed, edition),
+        (Some(needed), None) => format!("Rust {}", needed),
+        (None, Some(edition)) => format!("edition {}", edition),
+        (None, None) => String::new(),
+    };
+    let above = match (needed, msrv::local().limit()) {
+        (Some(needed), Some(limit)) => needed > limit.version(),
+        _ => false,
+    };
+    Span::styled(
+        format!("{:<18}", label),
+        theme.fg(if above { theme.bad } else { theme.muted }),
+    )
+}
+
+// Owners and the latest publisher of the crate in the detail view
+fn ownership_lines(app: &App) -> Vec<Line<'static>> {
+    let theme = theme::get();
+    let ownership = match app.ownership {
+        Some(ref ownership) => ownership,
+        None => return Vec::new(),
+    };
+
+    let owners = ownership
+        .owners
+        .iter()
+        .map(|o| match (&o.name, o.kind.as_deref()) {
+            (_, Some("team")) => format!("{} (team)", o.login),
+            (Some(name), _) if !name.is_empty() => format!("{} ({})", o.login, name),
+            _ => o.login.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut lines = vec![
+        Line::from(vec![]),
+        Line::from(vec![
+            Span::styled("Owners: ", theme.heading()),
+            Span::styled(owners, theme.fg(theme.text)),
+        ]),
+    ];
+
+    if let Some(ref publisher) = ownership.latest_publisher {
+        lines.push(Line::from(vec![
+            Span::styled("Latest release published by: ", theme.heading()),
+            Span::styled(publisher.login.clone(), theme.fg(theme.text)),
+        ]));
+    }
+    if ownership.owners_changed {
+        lines.push(acknowledge_line(app));
+    }
+
+    lines
+}
+
+// Owner changes keep being reported until the user accepts them
+fn acknowledge_line(app: &App) -> Line<'static> {
+    let theme = theme::get();
+    Line::from(vec![Span::styled(
+        format!(
+            "Owners changed - press '{}' to accept the current owners",
+            app.keymap.hint(Context::Detail, Action::AcknowledgeOwners)
+        ),
+        theme.fg(theme.accent),
+    )])
+}
+
 // Helper function to format dates nicely
 fn format_date(date_str: &str) -> String {
     if let Ok(dt) = DateTime::parse_from_rfc3339(date_str) {
@@ -1218,11 +3478,44 @@ fn format_date(date_str: &str) -> String {
     }
 }
 
-// Helper function to truncate strings to a maximum length
-fn truncate_str(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len - 3])
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn wrapped_url_is_linked_on_every_row() {
+        let url = "https://example.com/a/very/long/path";
+        let content = vec![
+            Line::from(vec![
+                Span::raw("Homepage: "),
+                Span::styled(url, Style::default().fg(Color::Blue)),
+            ]),
+            Line::from(""),
+            Line::from("  plain words"),
+        ];
+        let (lines, links) = wrap_with_links(content, 20);
+
+        assert_eq!(
+            rows(&lines),
+            [
+                "Homepage:",
+                "https://example.com/",
+                "a/very/long/path",
+                "",
+                "plain words",
+            ]
+        );
+        let pieces: Vec<_> = links
+            .iter()
+            .map(|l| (l.row, l.column, l.width, l.url.as_str()))
+            .collect();
+        assert_eq!(pieces, [(1, 0, 20, url), (2, 0, 16, url)]);
     }
 }
@@ -0,0 +1,416 @@
+use crate::api;
+use crate::app::AppResult;
+use crate::store::{data_dir, lock};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+const WATCHLIST_FILE: &str = "watchlist.json";
+// Keep the change feed from growing without bound
+const MAX_EVENTS: usize = 200;
+
+/// The state of a watched crate as of its last check
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub latest_version: String,
+    pub yanked: Vec<String>,
+    pub license: Option<String>,
+    pub owners: Vec<String>,
+    /// Advisory ids affecting the latest version
+    pub advisories: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeKind {
+    NewVersion,
+    Yanked,
+    Advisory,
+    License,
+    Ownership,
+}
+
+impl ChangeKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeKind::NewVersion => "release",
+            ChangeKind::Yanked => "yank",
+            ChangeKind::Advisory => "advisory",
+            ChangeKind::License => "license",
+            ChangeKind::Ownership => "owners",
+        }
+    }
+}
+
+/// A change noticed while checking a watched crate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchEvent {
+    pub name: String,
+    pub kind: ChangeKind,
+    pub message: String,
+    pub at: String,
+    #[serde(default)]
+    pub read: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatchedCrate {
+    /// `None` until the first check has recorded a baseline
+    pub snapshot: Option<Snapshot>,
+    pub last_checked: Option<String>,
+    pub last_error: Option<String>,
+}
+
+/// Crates the team depends on, persisted as JSON in the data directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Watchlist {
+    pub crates: BTreeMap<String, WatchedCrate>,
+    /// Newest first
+    pub events: Vec<WatchEvent>,
+}
+
+/// Outcome of checking one crate, produced off the UI thread
+pub type CheckResult = (String, Result<Snapshot, String>);
+
+impl Watchlist {
+    /// Load the watchlist, falling back to an empty one if it is missing or unreadable
+    pub fn load() -> Self {
+        data_dir()
+            .map(|dir| dir.join(WATCHLIST_FILE))
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Make a change and save it. The TUI and `crates watch` (often run from
+    /// cron) may have the watchlist open at the same time, so the file is
+    /// locked, the change is made to what was last saved rather than to this
+    /// possibly stale copy, and the result replaces the file in one rename.
+    /// If the file can't be locked or read, the change is still made here.
+    pub fn update<T>(&mut self, change: impl FnOnce(&mut Watchlist) -> T) -> AppResult<T> {
+        let dir = match data_dir().ok_or("Could not determine data directory") {
+            Ok(dir) => dir,
+            Err(e) => {
+                change(self);
+                return Err(e.into());
+            }
+        };
+        let lock = match lock(&dir, WATCHLIST_FILE) {
+            Ok(lock) => lock,
+            Err(e) => {
+                change(self);
+                return Err(e);
+            }
+        };
+
+        let path = dir.join(WATCHLIST_FILE);
+        if let Some(saved) = fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+        {
+            *self = saved;
+        }
+        let result = change(self);
+
+        let temp = dir.join(format!("{}.tmp", WATCHLIST_FILE));
+        fs::write(&temp, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&temp, &path)?;
+        drop(lock);
+        Ok(result)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.crates.contains_key(name)
+    }
+
+    /// Watch a crate, or stop watching it if it already is. Returns whether
+    /// the crate is watched afterwards.
+    pub fn toggle(&mut self, name: &str) -> bool {
+        if self.crates.remove(name).is_some() {
+            self.events.retain(|e| e.name != name);
+            false
+        } else {
+            self.crates
+                .insert(name.to_string(), WatchedCrate::default());
+            true
+        }
+    }
+
+    pub fn unread(&self) -> usize {
+        self.events.iter().filter(|e| !e.read).count()
+    }
+
+    pub fn unread_for(&self, name: &str) -> usize {
+        self.events
+            .iter()
+            .filter(|e| !e.read && e.name == name)
+            .count()
+    }
+
+    pub fn mark_all_read(&mut self) {
+        for event in &mut self.events {
+            event.read = true;
+        }
+    }
+
+    /// Record the result of a check, returning the changes it revealed.
+    /// The first successful check only records a baseline.
+    pub fn apply(&mut self, (name, result): CheckResult) -> Vec<WatchEvent> {
+        let Some(watched) = self.crates.get_mut(&name) else {
+            // Unwatched while the check was running
+            return Vec::new();
+        };
+        watched.last_checked = Some(chrono::Utc::now().to_rfc3339());
+
+        let snapshot = match result {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                watched.last_error = Some(e);
+                return Vec::new();
+            }
+        };
+        watched.last_error = None;
+
+        let events = match watched.snapshot {
+            Some(ref previous) => changes(&name, previous, &snapshot),
+            None => Vec::new(),
+        };
+        watched.snapshot = Some(snapshot);
+
+        for event in events.iter().rev() {
+            self.events.insert(0, event.clone());
+        }
+        self.events.truncate(MAX_EVENTS);
+        events
+    }
+}
+
+/// Fetch the current state of a crate
+pub fn snapshot(name: &str) -> AppResult<Snapshot> {
+    let versions = api::crate_versions(name)?;
+    let owners = api::crate_owners(name)?;
+
+    let latest = versions
+        .iter()
+        .find(|v| !v.yanked)
+        .or(versions.first())
+        .ok_or("Crate has no versions")?;
+    let advisories = api::advisories(name, &latest.num)?;
+
+    Ok(Snapshot {
+        latest_version: latest.num.clone(),
+        yanked: versions
+            .iter()
+            .filter(|v| v.yanked)
+            .map(|v| v.num.clone())
+            .collect(),
+        license: latest.license.clone(),
+        owners: owners.into_iter().map(|o| o.login).collect(),
+        advisories: advisories.into_iter().map(|a| a.id).collect(),
+    })
+}
+
+/// Check every crate on a background thread; results arrive on the receiver
+/// as each crate finishes
+pub fn spawn_check(names: Vec<String>) -> Receiver<CheckResult> {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        for name in names {
+            let result = snapshot(&name).map_err(|e| e.to_string());
+            if sender.send((name, result)).is_err() {
+                break;
+            }
+        }
+    });
+
+    receiver
+}
+
+fn changes(name: &str, old: &Snapshot, new: &Snapshot) -> Vec<WatchEvent> {
+    let mut events = Vec::new();
+    let mut push = |kind: ChangeKind, message: String| {
+        events.push(WatchEvent {
+            name: name.to_string(),
+            kind,
+            message,
+            at: chrono::Utc::now().to_rfc3339(),
+            read: false,
+        });
+    };
+
+    if new.latest_version != old.latest_version {
+        push(
+            ChangeKind::NewVersion,
+            format!("{} → {}", old.latest_version, new.latest_version),
+        );
+    }
+
+    for version in new.yanked.iter().filter(|v| !old.yanked.contains(v)) {
+        push(ChangeKind::Yanked, format!("{} was yanked", version));
+    }
+
+    for id in new
+        .advisories
+        .iter()
+        .filter(|a| !old.advisories.contains(a))
+    {
+        push(
+            ChangeKind::Advisory,
+            format!("{} affects {}", id, new.latest_version),
+        );
+    }
+
+    if new.license != old.license {
+        let show = |l: &Option<String>| l.clone().unwrap_or_else(|| "none".to_string());
+        push(
+            ChangeKind::License,
+            format!("{} → {}", show(&old.license), show(&new.license)),
+        );
+    }
+
+    let added: Vec<&String> = new
+        .owners
+        .iter()
+        .filter(|o| !old.owners.contains(o))
+        .collect();
+    let removed: Vec<&String> = old
+        .owners
+        .iter()
+        .filter(|o| !new.owners.contains(o))
+        .collect();
+    if !added.is_empty() || !removed.is_empty() {
+        let mut parts = Vec::new();
+        if !added.is_empty() {
+            parts.push(format!("added {}", join(&added)));
+        }
+        if !removed.is_empty() {
+            parts.push(format!("removed {}", join(&removed)));
+        }
+        push(ChangeKind::Ownership, parts.join(", "));
+    }
+
+    events
+}
+
+fn join(names: &[&String]) -> String {
+    names
+        .iter()
+        .map(|n| n.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(latest_version: &str, yanked: &[&str], owners: &[&str]) -> Snapshot {
+        Snapshot {
+            latest_version: latest_version.to_string(),
+            yanked: yanked.iter().map(|v| v.to_string()).collect(),
+            license: Some("MIT".to_string()),
+            owners: owners.iter().map(|o| o.to_string()).collect(),
+            advisories: Vec::new(),
+        }
+    }
+
+    fn summary(events: &[WatchEvent]) -> Vec<(ChangeKind, &str)> {
+        events
+            .iter()
+            .map(|e| (e.kind, e.message.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn nothing_changed() {
+        let old = snapshot("1.0.0", &["0.9.0"], &["alice"]);
+        assert!(changes("demo", &old, &old.clone()).is_empty());
+    }
+
+    #[test]
+    fn new_version() {
+        let old = snapshot("1.0.0", &[], &["alice"]);
+        let new = snapshot("1.1.0", &[], &["alice"]);
+        let events = changes("demo", &old, &new);
+        assert_eq!(
+            summary(&events),
+            [(ChangeKind::NewVersion, "1.0.0 → 1.1.0")]
+        );
+        assert_eq!(events[0].name, "demo");
+        assert!(!events[0].read);
+    }
+
+    #[test]
+    fn yank() {
+        // Yanking the latest version also moves it back
+        let old = snapshot("1.1.0", &["0.9.0"], &["alice"]);
+        let new = snapshot("1.0.0", &["0.9.0", "1.1.0"], &["alice"]);
+        assert_eq!(
+            summary(&changes("demo", &old, &new)),
+            [
+                (ChangeKind::NewVersion, "1.1.0 → 1.0.0"),
+                (ChangeKind::Yanked, "1.1.0 was yanked"),
+            ]
+        );
+    }
+
+    #[test]
+    fn owner_change() {
+        let old = snapshot("1.0.0", &[], &["alice", "bob"]);
+        let new = snapshot("1.0.0", &[], &["alice", "mallory", "eve"]);
+        assert_eq!(
+            summary(&changes("demo", &old, &new)),
+            [(ChangeKind::Ownership, "added mallory, eve, removed bob")]
+        );
+
+        let new = snapshot("1.0.0", &[], &["alice"]);
+        assert_eq!(
+            summary(&changes("demo", &old, &new)),
+            [(ChangeKind::Ownership, "removed bob")]
+        );
+    }
+
+    #[test]
+    fn license_and_advisory() {
+        let old = snapshot("1.0.0", &[], &["alice"]);
+        let mut new = old.clone();
+        new.license = None;
+        new.advisories = vec!["RUSTSEC-2024-0001".to_string()];
+        assert_eq!(
+            summary(&changes("demo", &old, &new)),
+            [
+                (ChangeKind::Advisory, "RUSTSEC-2024-0001 affects 1.0.0"),
+                (ChangeKind::License, "MIT → none"),
+            ]
+        );
+    }
+
+    #[test]
+    fn first_check_records_a_baseline() {
+        let mut watchlist = Watchlist::default();
+        watchlist.toggle("demo");
+
+        let first = snapshot("1.0.0", &[], &["alice"]);
+        assert!(watchlist.apply(("demo".to_string(), Ok(first))).is_empty());
+
+        let failed = watchlist.apply(("demo".to_string(), Err("timed out".to_string())));
+        assert!(failed.is_empty());
+        assert_eq!(
+            watchlist.crates["demo"].last_error.as_deref(),
+            Some("timed out")
+        );
+
+        let second = snapshot("1.1.0", &[], &["alice"]);
+        assert_eq!(watchlist.apply(("demo".to_string(), Ok(second))).len(), 1);
+        assert_eq!(watchlist.unread_for("demo"), 1);
+        assert!(watchlist.crates["demo"].last_error.is_none());
+
+        // A result for a crate unwatched while it was checked is dropped
+        let other = snapshot("2.0.0", &[], &[]);
+        assert!(watchlist.apply(("other".to_string(), Ok(other))).is_empty());
+        assert!(!watchlist.contains("other"));
+    }
+}